@@ -0,0 +1,172 @@
+// 球队筛选性能测试
+//
+// 运行: cargo bench --bench team_filter
+//
+// 模拟在不同规模的数据库中逐字输入搜索词，输出每次按键的筛选耗时。
+// 同时给出旧实现（每次按键复制整个球队列表并拼接搜索字符串）的耗时作为对照。
+// 任一按键的筛选耗时超过一帧时以非零状态退出，可用于持续集成中的性能检查。
+
+use std::time::{Duration, Instant};
+
+// 球队和筛选模块中的界面文本使用 t! 宏
+#[allow(dead_code)]
+#[macro_use]
+#[path = "../src/i18n.rs"]
+mod i18n;
+#[allow(dead_code)]
+#[path = "../src/data/team.rs"]
+pub mod team;
+#[allow(dead_code)]
+#[path = "../src/data/team_filter.rs"]
+pub mod team_filter;
+
+// 与主程序相同的模块路径，供 team_filter.rs 中的 crate::data::team 使用
+mod data {
+    pub use super::{team, team_filter};
+}
+
+use data::team::{Leagues, Team};
+use data::team_filter::{FilterField, TeamFilter, TeamFilterState};
+
+// 单帧时间预算（60帧）
+const FRAME_BUDGET: Duration = Duration::from_micros(16_667);
+
+const TEAM_COUNTS: [usize; 3] = [1_000, 10_000, 100_000];
+
+const QUERIES: [&str; 3] = ["united", "北京", "fc 19"];
+
+// 每个测量重复的次数，取中位数
+const ROUNDS: usize = 15;
+
+const SYLLABLES: [&str; 16] = [
+    "ar", "be", "ci", "do", "el", "fa", "go", "hu", "in", "jo", "ka", "lu", "mo", "ne", "or", "pa",
+];
+const SUFFIXES: [&str; 6] = ["United", "City", "FC", "Athletic", "Rovers", "体育"];
+const LOCATIONS: [&str; 8] = ["北京", "上海", "广州", "London", "Madrid", "Milano", "München", "Paris"];
+
+// 简单的线性同余生成器，保证每次生成相同的数据
+struct Lcg(u64);
+
+impl Lcg {
+    fn next(&mut self) -> usize {
+        self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        (self.0 >> 33) as usize
+    }
+
+    fn pick<'a>(&mut self, items: &[&'a str]) -> &'a str {
+        items[self.next() % items.len()]
+    }
+}
+
+fn generate_teams(count: usize) -> Vec<Team> {
+    let mut rng = Lcg(42);
+    (0..count)
+        .map(|i| {
+            let name = format!(
+                "{}{}{} {}",
+                rng.pick(&SYLLABLES),
+                rng.pick(&SYLLABLES),
+                rng.pick(&SYLLABLES),
+                rng.pick(&SUFFIXES)
+            );
+            Team {
+                id: i as i64 + 1,
+                name: name.clone(),
+                wealth: (rng.next() % 100_000) as i64,
+                found_year: 1850 + (rng.next() % 170) as i64,
+                location: rng.pick(&LOCATIONS).to_string(),
+                supporter_count: (rng.next() % 1_000_000) as i64,
+                stadium_name: format!("{} Stadium", name),
+                nickname: rng.pick(&SYLLABLES).to_string(),
+                league_id: (rng.next() % 40) as i64,
+            }
+        })
+        .collect()
+}
+
+// 旧实现：复制球队列表，并为每个球队拼接搜索字符串
+fn legacy_filter(teams: &[Team], search_text: &str) -> Vec<Team> {
+    let search_term = search_text.to_lowercase();
+    teams
+        .iter()
+        .filter(|team| team.search_string().to_lowercase().contains(&search_term))
+        .cloned()
+        .collect()
+}
+
+fn median(mut samples: Vec<Duration>) -> Duration {
+    samples.sort();
+    samples[samples.len() / 2]
+}
+
+// 逐字输入query，返回每次按键的耗时（中位数）
+fn measure_typing(teams: &[Team], query: &str) -> Vec<Duration> {
+    let prefixes: Vec<String> = query
+        .char_indices()
+        .map(|(i, c)| query[..i + c.len_utf8()].to_string())
+        .collect();
+
+    let mut samples = vec![Vec::with_capacity(ROUNDS); prefixes.len()];
+    for _ in 0..ROUNDS {
+        let mut state = TeamFilterState::new();
+        state.rebuild(teams, &Leagues::default());
+        let mut filter = TeamFilter::new();
+        filter.field = FilterField::All;
+        state.apply(teams, &filter);
+
+        for (i, prefix) in prefixes.iter().enumerate() {
+            filter.set_search_text(prefix);
+            let start = Instant::now();
+            let count = state.apply(teams, &filter).len();
+            samples[i].push(start.elapsed());
+            std::hint::black_box(count);
+        }
+    }
+
+    samples.into_iter().map(median).collect()
+}
+
+fn measure_legacy(teams: &[Team], query: &str) -> Duration {
+    let samples = (0..ROUNDS)
+        .map(|_| {
+            let start = Instant::now();
+            std::hint::black_box(legacy_filter(teams, query).len());
+            start.elapsed()
+        })
+        .collect();
+    median(samples)
+}
+
+fn main() {
+    let mut over_budget = false;
+
+    for &count in &TEAM_COUNTS {
+        let teams = generate_teams(count);
+
+        let start = Instant::now();
+        let mut state = TeamFilterState::new();
+        state.rebuild(&teams, &Leagues::default());
+        println!("\n{} 个球队（建立搜索键 {:?}）", count, start.elapsed());
+
+        for query in QUERIES {
+            let keystrokes = measure_typing(&teams, query);
+            let worst = keystrokes.iter().max().copied().unwrap_or_default();
+            let legacy = measure_legacy(&teams, query);
+
+            let timings: Vec<String> = keystrokes.iter().map(|d| format!("{:?}", d)).collect();
+            println!("  \"{}\": 每次按键 [{}]", query, timings.join(", "));
+            println!("      最慢按键 {:?}，旧实现单次筛选 {:?}", worst, legacy);
+
+            if worst > FRAME_BUDGET {
+                over_budget = true;
+            }
+        }
+    }
+
+    if over_budget {
+        eprintln!("\n失败: 部分按键的筛选耗时超过一帧 ({:?})", FRAME_BUDGET);
+        std::process::exit(1);
+    } else {
+        println!("\n所有按键的筛选耗时均在一帧 ({:?}) 以内", FRAME_BUDGET);
+    }
+}
@@ -1,19 +1,66 @@
 #[cfg(windows)]
 extern crate winres;
 
-#[cfg(windows)]
+use std::path::{Path, PathBuf};
+
+// 内置字体文件的路径，可以用环境变量指定，未指定时使用 assets/fonts 下的默认文件
+const EMBEDDED_FONT_ENV: &str = "CFS_EMBEDDED_FONT";
+const DEFAULT_EMBEDDED_FONT: &str = "assets/fonts/embedded-cjk-subset.otf";
+
 fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    if std::env::var_os("CARGO_FEATURE_EMBEDDED_FONT").is_some() {
+        embed_font();
+    }
+
+    compile_resources();
+}
+
+// 把内置字体复制到 OUT_DIR 供 include_bytes! 使用。字体文件不在仓库中，
+// 找不到时写入空文件并给出警告，程序会按没有内置字体处理
+fn embed_font() {
+    println!("cargo:rerun-if-env-changed={}", EMBEDDED_FONT_ENV);
+
+    let source = std::env::var_os(EMBEDDED_FONT_ENV)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_EMBEDDED_FONT));
+    println!("cargo:rerun-if-changed={}", source.display());
+
+    let out_dir = std::env::var_os("OUT_DIR").expect("cargo 未设置 OUT_DIR");
+    let target = Path::new(&out_dir).join("embedded-cjk-subset.otf");
+
+    if source.is_file() {
+        if let Err(e) = std::fs::copy(&source, &target) {
+            eprintln!("无法复制内置字体 {}: {}", source.display(), e);
+            std::process::exit(1);
+        }
+    } else {
+        println!(
+            "cargo:warning=未找到内置字体 {}，可通过环境变量 {} 指定字体文件；本次编译不包含内置字体",
+            source.display(),
+            EMBEDDED_FONT_ENV
+        );
+        if let Err(e) = std::fs::write(&target, b"") {
+            eprintln!("无法写入 {}: {}", target.display(), e);
+            std::process::exit(1);
+        }
+    }
+}
+
+#[cfg(windows)]
+fn compile_resources() {
     let mut res = winres::WindowsResource::new();
     res.set_icon("assets/favicon.ico"); // 使用已存在的图标文件
     res.set_language(0x0004); // 中文简体
-    
+
     // 设置版本信息
     res.set("FileVersion", "1.0.0.0");
     res.set("ProductVersion", "1.0.0.0");
     res.set("FileDescription", "CFS球队编辑器");
     res.set("ProductName", "CFS球队编辑器");
     res.set("CompanyName", "卡尔纳斯");
-    
+
     // 编译资源
     if let Err(e) = res.compile() {
         eprintln!("无法编译资源: {}", e);
@@ -22,6 +69,6 @@ fn main() {
 }
 
 #[cfg(not(windows))]
-fn main() {
+fn compile_resources() {
     // 非Windows平台不需要做任何事情
-} 
\ No newline at end of file
+}
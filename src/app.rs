@@ -1,38 +1,86 @@
-use std::path::PathBuf;
-use std::time::{Duration, Instant};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
 
-use egui::{Context, CentralPanel, SidePanel, TopBottomPanel, Ui, Color32, Layout, Align};
+use egui::{Context, CentralPanel, SidePanel, TopBottomPanel, ScrollArea, Ui, Color32, Layout, Align};
 use eframe::{App, CreationContext, Frame};
 use log::{error, info};
 use native_dialog::FileDialog;
 
+use crate::chart;
 use crate::data::database::Database;
-use crate::error::Result;
+use crate::data::worker::{DbCommand, DbEvent, DbWorker, LoadedData, SaveOrigin, WorkerState};
+use crate::error::{AppError, ErrorContext};
+use crate::settings::AppSettings;
+use crate::data::team::{Team, TeamField};
+use crate::data::audit;
+use crate::data::conflict;
+use crate::fonts::{self, FontSource};
+use crate::i18n::{self, Language};
+use crate::ui::audit_dialog::AuditDialog;
+use crate::ui::balance_dialog::BalanceDialog;
+use crate::ui::changes_view::{ChangesAction, ChangesView};
+use crate::ui::compare_view::{CompareView, ReferenceCopy};
+use crate::ui::conflict_dialog::{ConflictDialog, ConflictResolution};
+use crate::ui::error_dialog::ErrorDialog;
+use crate::ui::font_dialog::FontDialog;
+use crate::logo;
+use crate::report;
+use crate::ui::generator_dialog::GeneratorDialog;
 use crate::ui::dialogs::{BulkEditDialog, ConfirmDialog, MessageDialog as UiMessageDialog, StaffEditDialog};
+use crate::ui::logo_editor::LogoEditorDialog;
+use crate::ui::logo_gallery::{GalleryAction, LogoGalleryView};
+use crate::ui::logo_import::LogoImportDialog;
+use crate::ui::notifications::NotificationCenter;
 use crate::ui::staff_list::StaffListView;
-use crate::ui::team_details::TeamDetailsView;
+use crate::ui::team_details::{LogoAction, TeamDetailsView};
 use crate::ui::team_list::TeamListView;
-use crate::ui::visualization::VisualizationView;
+use crate::ui::theme::{self, Palette};
+use crate::ui::theme_dialog::ThemeDialog;
+use crate::ui::visualization::{VisualizationAction, VisualizationView};
+use crate::ui::statistics_view::StatisticsView;
 use crate::ui::widgets;
 use crate::utils;
 
+/// 窗口标题，切换语言时需要重新设置
+pub const WINDOW_TITLE: &str = "CFS球队编辑器 BY.卡尔纳斯";
+
 // 自动保存间隔（秒）
 const AUTO_SAVE_INTERVAL: u64 = 30;
 
+// 检查数据库文件是否被外部修改的间隔（秒）
+const FILE_CHECK_INTERVAL: u64 = 2;
+
 #[derive(PartialEq, Clone, Copy)]
 enum ActiveTab {
     TeamDetails,
     Visualization,
+    Statistics,
+    Compare,
+    Gallery,
+    Changes,
     // SponsorEditor已从实际功能中移除，但UI保留
     SponsorEditor,
 }
 
+// 等待确认对话框确认的操作
+enum ConfirmAction {
+    SaveTeam,
+    ExportTeams(PathBuf),
+    SwitchDatabase(PathBuf),
+    EnterEditMode,
+    ReloadExternal,
+}
+
 impl ActiveTab {
     fn as_str(&self) -> &'static str {
         match self {
-            ActiveTab::TeamDetails => "球队详情",
-            ActiveTab::Visualization => "数据可视化",
-            ActiveTab::SponsorEditor => "杂项编辑器",
+            ActiveTab::TeamDetails => t!("球队详情"),
+            ActiveTab::Visualization => t!("数据可视化"),
+            ActiveTab::Statistics => t!("统计分析"),
+            ActiveTab::Compare => t!("数据库对比"),
+            ActiveTab::Gallery => t!("Logo总览"),
+            ActiveTab::Changes => t!("打开后的修改"),
+            ActiveTab::SponsorEditor => t!("杂项编辑器"),
         }
     }
 }
@@ -40,126 +88,735 @@ impl ActiveTab {
 pub struct TeamEditorApp {
     // 数据
     database: Database,
+    worker: Option<DbWorker>,
+    // 参考数据库的路径，数据加载完成后才会设置
+    reference_path: Option<PathBuf>,
+    // 正在后台加载的参考数据库
+    reference_loading: Option<(DbWorker, PathBuf)>,
+    loading: bool,
     
     // UI组件
     team_list: TeamListView,
     team_details: TeamDetailsView,
     staff_list: StaffListView,
     visualization: VisualizationView,
+    statistics: StatisticsView,
+    compare_view: CompareView,
+    logo_gallery: LogoGalleryView,
+    changes_view: ChangesView,
     active_tab: ActiveTab,
     
     // 对话框
     staff_edit_dialog: StaffEditDialog,
     message_dialog: UiMessageDialog,
+    error_dialog: ErrorDialog,
     confirm_dialog: ConfirmDialog,
     bulk_edit_dialog: BulkEditDialog,
+    balance_dialog: BalanceDialog,
+    generator_dialog: GeneratorDialog,
+    audit_dialog: AuditDialog,
+    conflict_dialog: ConflictDialog,
+    logo_editor_dialog: LogoEditorDialog,
+    logo_import_dialog: LogoImportDialog,
+    font_dialog: FontDialog,
+    theme_dialog: ThemeDialog,
+    notifications: NotificationCenter,
     
     // 状态
     status_message: String,
-    export_path: Option<PathBuf>,
+    pending_confirm: Option<ConfirmAction>,
+    pending_open: Option<PathBuf>,
+    settings: AppSettings,
+    /// 已应用的配色和当时的系统外观，变化时重新应用
+    applied_theme: Option<(Palette, Option<eframe::Theme>)>,
     
     // 自动保存
     auto_save_enabled: bool,
     last_auto_save: Instant,
     has_unsaved_changes: bool,
     auto_save_countdown: u64,
+    
+    // 外部修改检测
+    db_modified: Option<SystemTime>,
+    last_file_check: Instant,
+    external_change: bool,
 }
 
 impl TeamEditorApp {
-    pub fn new(cc: &CreationContext) -> Self {
+    pub fn new(cc: &CreationContext, initial_db_path: Option<PathBuf>, settings: AppSettings) -> Self {
         // 应用Mac风格主题
-        crate::ui::theme::setup_mac_theme(&cc.egui_ctx);
+        theme::setup_mac_theme(&cc.egui_ctx);
 
-        TeamEditorApp {
+        let mut app = TeamEditorApp {
             database: Database::new(),
+            reference_path: None,
+            reference_loading: None,
+            worker: None,
+            loading: false,
             team_list: TeamListView::new(),
             team_details: TeamDetailsView::new(),
             staff_list: StaffListView::new(),
             visualization: VisualizationView::new(),
+            statistics: StatisticsView::new(),
+            compare_view: CompareView::new(),
+            logo_gallery: LogoGalleryView::new(),
+            changes_view: ChangesView::new(),
             active_tab: ActiveTab::TeamDetails,
             staff_edit_dialog: StaffEditDialog::new(),
             message_dialog: UiMessageDialog::new(),
+            error_dialog: ErrorDialog::new(),
             confirm_dialog: ConfirmDialog::new(),
             bulk_edit_dialog: BulkEditDialog::new(),
-            status_message: "就绪".to_string(),
-            export_path: None,
+            balance_dialog: BalanceDialog::new(),
+            generator_dialog: GeneratorDialog::new(),
+            audit_dialog: AuditDialog::new(),
+            conflict_dialog: ConflictDialog::new(),
+            logo_editor_dialog: LogoEditorDialog::new(),
+            logo_import_dialog: LogoImportDialog::new(),
+            font_dialog: FontDialog::new(),
+            theme_dialog: ThemeDialog::new(),
+            notifications: NotificationCenter::new(),
+            status_message: t!("就绪").to_string(),
+            pending_confirm: None,
+            pending_open: initial_db_path,
+            settings,
+            applied_theme: None,
             auto_save_enabled: true,
             last_auto_save: Instant::now(),
             has_unsaved_changes: false,
             auto_save_countdown: AUTO_SAVE_INTERVAL,
-        }
+            db_modified: None,
+            last_file_check: Instant::now(),
+            external_change: false,
+        };
+
+        // 字号在主题之后设置，避免被主题的默认字号覆盖
+        app.apply_fonts(&cc.egui_ctx);
+        app
     }
 
     fn load_database(&mut self, ctx: &Context) {
+        // 使用native-dialog库打开文件对话框
+        let dialog = FileDialog::new()
+            .add_filter(t!("SQLite数据库"), &utils::DATABASE_EXTENSIONS)
+            .add_filter(t!("所有文件"), &["*"])
+            .show_open_single_file();
+        
+        if let Ok(Some(path)) = dialog {
+            self.open_database(path, ctx);
+        }
+    }
+
+    fn open_database(&mut self, path: PathBuf, ctx: &Context) {
         if self.database.is_connected() {
-            self.show_message("警告", "已经连接到数据库");
+            // 已连接时询问是否切换到新的数据库
+            let mut message = t!("确定要关闭当前数据库并打开以下数据库吗？\n{}", path.display());
+            if self.has_unsaved_changes {
+                message.push_str(t!("\n\n当前球队有未保存的修改，切换后将丢失。"));
+            }
+            
+            self.pending_confirm = Some(ConfirmAction::SwitchDatabase(path));
+            self.confirm_dialog.show_confirm(t!("切换数据库"), &message);
             return;
         }
         
-        // 使用native-dialog库打开文件对话框
+        self.connect_database(&path, ctx);
+    }
+
+    fn connect_database(&mut self, path: &Path, ctx: &Context) {
+        if !utils::file_exists(path) {
+            let err = std::io::Error::new(std::io::ErrorKind::NotFound, t!("数据库文件不存在: {}", path.display()));
+            self.show_error(t!("打开数据库"), err);
+            self.settings.remove_recent_file(path);
+            self.save_settings();
+            return;
+        }
+        
+        let read_only = self.settings.open_read_only;
+        let result = if read_only {
+            self.database.connect_read_only(path)
+        } else {
+            self.database.connect(path)
+        };
+        
+        match result {
+            Ok(_) => {
+                let path_str = path.display().to_string();
+                let mode = if read_only { t!("（只读）") } else { "" };
+                self.notifications.success(&t!("已连接到数据库{}: {}", mode, path_str));
+                self.set_status(&t!("已连接到数据库{}: {}", mode, path_str));
+                
+                // 记录到最近打开列表
+                self.settings.add_recent_file(path);
+                self.save_settings();
+                
+                // 在后台线程中加载数据
+                self.db_modified = utils::database_modified_time(path);
+                self.external_change = false;
+                if self.start_worker(path, read_only, None, ctx) {
+                    self.load_data();
+                }
+            },
+            Err(e) => {
+                self.show_error(t!("连接数据库"), e);
+            }
+        }
+    }
+
+    fn start_worker(&mut self, path: &Path, read_only: bool, previous: Option<WorkerState>, ctx: &Context) -> bool {
+        match DbWorker::start(path, read_only, previous, ctx.clone()) {
+            Ok(worker) => {
+                self.worker = Some(worker);
+                true
+            },
+            Err(e) => {
+                self.show_error(t!("启动数据库后台线程"), e);
+                false
+            }
+        }
+    }
+
+    fn is_editable(&self) -> bool {
+        self.database.is_connected() && !self.database.is_read_only()
+    }
+
+    /// 检查是否允许写入，不允许时提示原因
+    fn ensure_editable(&mut self) -> bool {
+        if !self.database.is_connected() {
+            self.warn(t!("请先加载数据库"));
+            return false;
+        }
+        
+        if self.database.is_read_only() {
+            self.notifications.warn(t!("只读模式"), t!("当前数据库以只读方式打开，请先通过“编辑 → 切换到编辑模式”启用修改。"));
+            return false;
+        }
+        
+        true
+    }
+
+    fn request_edit_mode(&mut self) {
+        let Some(path) = self.database.get_db_path() else {
+            self.warn(t!("请先加载数据库"));
+            return;
+        };
+        
+        let message = t!(
+            "切换到编辑模式后可以修改数据库。\n切换前会把以下数据库备份到同目录的 DatabaseBackup 文件夹：\n{}",
+            path.display()
+        );
+        self.pending_confirm = Some(ConfirmAction::EnterEditMode);
+        self.confirm_dialog.show_confirm(t!("切换到编辑模式"), &message);
+    }
+
+    // 备份数据库后以读写方式重新连接，备份失败时保持只读
+    fn enter_edit_mode(&mut self, ctx: &Context) {
+        let Some(path) = self.database.get_db_path().map(Path::to_path_buf) else {
+            return;
+        };
+        
+        let backup_path = match utils::backup_database(&path) {
+            Ok(backup_path) => backup_path,
+            Err(e) => {
+                self.show_error(t!("备份数据库（仍保持只读模式）"), e);
+                return;
+            }
+        };
+        info!("已备份数据库: {}", backup_path.display());
+        
+        if !self.reconnect(&path, false, ctx) {
+            return;
+        }
+        
+        self.notifications.info(t!("编辑模式"), &t!("已切换到编辑模式，数据库备份: {}", backup_path.display()));
+        self.set_status(t!("已切换到编辑模式"));
+    }
+
+    fn leave_edit_mode(&mut self, ctx: &Context) {
+        let Some(path) = self.database.get_db_path().map(Path::to_path_buf) else {
+            return;
+        };
+        
+        if self.has_unsaved_changes {
+            self.warn(t!("当前球队有未保存的修改，请先保存后再切换到只读模式"));
+            return;
+        }
+        
+        if self.reconnect(&path, true, ctx) {
+            self.set_status(t!("已切换到只读模式"));
+        }
+    }
+
+    // 以指定方式重新连接当前数据库，界面中已加载的数据、修改快照和冲突检测的基准值保持不变
+    fn reconnect(&mut self, path: &Path, read_only: bool, ctx: &Context) -> bool {
+        // 等待后台线程完成未执行的写入
+        let previous = self.worker.take().and_then(DbWorker::stop);
+        
+        let result = if read_only {
+            self.database.connect_read_only(path)
+        } else {
+            self.database.connect(path)
+        };
+        if let Err(e) = result {
+            self.show_error(t!("重新连接数据库"), e);
+            return false;
+        }
+        
+        self.db_modified = utils::database_modified_time(path);
+        self.start_worker(path, read_only, previous, ctx)
+    }
+
+    fn switch_database(&mut self, path: PathBuf, ctx: &Context) {
+        // 等待后台线程完成未执行的写入
+        self.worker = None;
+        self.loading = false;
+        
+        if let Err(e) = self.database.close() {
+            self.show_error(t!("关闭数据库"), e);
+            return;
+        }
+        
+        // 清除上一个数据库的界面状态
+        self.team_list = TeamListView::new();
+        self.team_details = TeamDetailsView::new();
+        self.staff_list = StaffListView::new();
+        self.visualization = VisualizationView::new();
+        self.statistics = StatisticsView::new();
+        self.logo_gallery = LogoGalleryView::new();
+        self.changes_view = ChangesView::new();
+        self.has_unsaved_changes = false;
+        self.last_auto_save = Instant::now();
+        
+        self.connect_database(&path, ctx);
+    }
+
+    fn handle_dropped_files(&mut self, ctx: &Context) {
+        let dropped: Vec<PathBuf> = ctx.input(|i| {
+            i.raw.dropped_files
+                .iter()
+                .filter_map(|file| file.path.clone())
+                .collect()
+        });
+        
+        if let Some(path) = dropped.into_iter().next() {
+            if utils::is_database_file(&path) {
+                self.open_database(path, ctx);
+            } else {
+                self.warn(t!("只能拖放 .db、.sqlite 或 .sqlite3 数据库文件"));
+            }
+        }
+    }
+
+    fn show_drop_overlay(&self, ctx: &Context) {
+        if ctx.input(|i| i.raw.hovered_files.is_empty()) {
+            return;
+        }
+        
+        let painter = ctx.layer_painter(egui::LayerId::new(
+            egui::Order::Foreground,
+            egui::Id::new("file_drop_overlay"),
+        ));
+        let screen_rect = ctx.screen_rect();
+        painter.rect_filled(screen_rect, 0.0, Color32::from_black_alpha(160));
+        painter.text(
+            screen_rect.center(),
+            egui::Align2::CENTER_CENTER,
+            t!("松开鼠标以打开数据库"),
+            egui::FontId::proportional(24.0),
+            Color32::WHITE,
+        );
+    }
+
+    fn save_settings(&self) {
+        if let Err(e) = self.settings.save() {
+            error!("保存设置失败: {}", e);
+        }
+    }
+
+    fn open_reference_database(&mut self, ctx: &Context) {
         let dialog = FileDialog::new()
-            .add_filter("SQLite数据库", &["db", "sqlite", "sqlite3"])
-            .add_filter("所有文件", &["*"])
+            .add_filter(t!("SQLite数据库"), &utils::DATABASE_EXTENSIONS)
+            .add_filter(t!("所有文件"), &["*"])
             .show_open_single_file();
         
         if let Ok(Some(path)) = dialog {
-            match self.database.connect(&path) {
-                Ok(_) => {
-                    let path_str = path.display().to_string();
-                    self.show_message("成功", &format!("已连接到数据库: {}", path_str));
-                    self.set_status(&format!("已连接到数据库: {}", path_str));
-                    
-                    // 加载数据
-                    if let Err(e) = self.load_data(ctx) {
-                        self.show_message("错误", &format!("加载数据失败: {}", e));
-                        error!("加载数据失败: {}", e);
-                    }
+            // 在只读后台线程中加载，加载完成前保留当前的参考数据
+            match DbWorker::start_reference(&path, ctx.clone()) {
+                Ok(mut worker) => {
+                    worker.send(DbCommand::LoadAll);
+                    self.set_status(&t!("正在加载参考数据库: {}", path.display()));
+                    self.reference_loading = Some((worker, path));
                 },
-                Err(e) => {
-                    self.show_message("错误", &format!("连接数据库失败: {}", e));
-                    error!("连接数据库失败: {}", e);
-                }
+                Err(e) => self.show_error(t!("打开参考数据库"), e),
             }
         }
     }
 
-    fn load_data(&mut self, _ctx: &Context) -> Result<()> {
-        // 加载球队数据
-        let teams = self.database.load_teams()?;
-        self.team_list.set_teams(teams.clone());
+    fn process_reference_events(&mut self) {
+        let Some((worker, path)) = &mut self.reference_loading else {
+            return;
+        };
         
-        // 加载联赛数据
-        let leagues = self.database.load_leagues()?;
-        self.team_details.set_leagues(leagues);
+        let result = match worker.try_recv() {
+            Some(DbEvent::Loaded(data)) => Ok(data),
+            Some(DbEvent::Failed(_, e)) => Err(e),
+            _ => return,
+        };
+        let path = path.clone();
+        self.reference_loading = None;
+        
+        match result {
+            Ok(data) => {
+                let db_name = path.file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_else(|| path.display().to_string());
+                self.compare_view.set_reference_data(db_name, data.teams, data.staff, data.leagues);
+                
+                info!("已加载参考数据库: {}", path.display());
+                self.set_status(&t!("已打开参考数据库: {}", path.display()));
+                self.reference_path = Some(path);
+                self.active_tab = ActiveTab::Compare;
+            },
+            Err(e) => self.show_error(t!("加载参考数据"), e),
+        }
+    }
+
+    fn reference_directory(&self) -> Option<PathBuf> {
+        self.reference_path.as_ref().and_then(|path| path.parent()).map(Path::to_path_buf)
+    }
+
+    fn close_reference_database(&mut self) {
+        self.reference_path = None;
+        self.compare_view.clear();
+        self.set_status(t!("参考数据库已关闭"));
+    }
+
+    fn handle_compare_action(&mut self, action: ReferenceCopy, ctx: &Context) {
+        if !self.ensure_editable() {
+            return;
+        }
+        
+        let (working_team, ref_team) = match (self.team_details.team.clone(), self.compare_view.get_selected_ref_team().cloned()) {
+            (Some(w), Some(r)) => (w, r),
+            _ => {
+                self.warn(t!("请先选择要对比的两个球队"));
+                return;
+            }
+        };
+        
+        match action {
+            ReferenceCopy::Fields(fields) => {
+                let mut updated = working_team.clone();
+                for field in &fields {
+                    field.copy_value(&mut updated, &ref_team);
+                }
+                
+                let field_names = fields.iter()
+                    .map(TeamField::as_str)
+                    .collect::<Vec<_>>()
+                    .join(t!("、"));
+                self.send_command(DbCommand::SaveTeams(vec![updated], SaveOrigin::CopyFromReference(field_names)));
+            },
+            ReferenceCopy::Staff => {
+                let ref_staff = self.compare_view.get_selected_ref_staff();
+                self.send_command(DbCommand::CopyStaffToTeam(ref_staff, working_team.id));
+            },
+            ReferenceCopy::Logo => {
+                let (working_dir, ref_dir) = match (self.database.get_db_directory(), self.reference_directory()) {
+                    (Some(w), Some(r)) => (w, r),
+                    _ => return,
+                };
+                
+                if !logo::logo_info(&ref_dir, ref_team.id).exists() {
+                    self.warn(t!("参考球队没有Logo"));
+                    return;
+                }
+                
+                match logo::copy_logo(&ref_dir, ref_team.id, &working_dir, working_team.id) {
+                    Ok(_) => {
+                        if let Err(e) = self.team_details.load_logo(ctx, &working_dir, working_team.id) {
+                            error!("加载Logo失败: {}", e);
+                        }
+                        self.compare_view.invalidate_logos();
+                        self.set_status(t!("已从参考数据库复制Logo"));
+                    },
+                    Err(e) => {
+                        self.show_error(t!("复制Logo"), e);
+                    }
+                }
+            },
+        }
+    }
+
+    fn handle_changes_action(&mut self, action: ChangesAction) {
+        // 未打开数据库时没有快照可对比
+        if self.worker.is_none() {
+            self.changes_view.stop_waiting();
+            return;
+        }
+        
+        match action {
+            ChangesAction::Refresh => self.send_command(DbCommand::DiffSnapshot),
+            ChangesAction::Revert(reverts) => self.send_command(DbCommand::RevertChanges(reverts)),
+        }
+    }
+
+    fn send_command(&mut self, command: DbCommand) {
+        if command.is_write() && !self.ensure_editable() {
+            return;
+        }
+        
+        match &mut self.worker {
+            Some(worker) => worker.send(command),
+            None => self.warn(t!("请先加载数据库")),
+        }
+    }
+
+    fn load_data(&mut self) {
+        self.loading = true;
+        self.send_command(DbCommand::LoadAll);
+    }
+
+    fn apply_loaded_data(&mut self, data: LoadedData, ctx: &Context) {
+        let LoadedData { teams, leagues, staff, sponsors } = data;
+        let selected_team_id = self.team_list.get_selected_team_id();
         
-        // 加载员工数据
-        let staff = self.database.load_staff()?;
+        self.team_list.set_data(teams.clone(), leagues.clone());
+        self.visualization.set_leagues(leagues.clone());
+        self.statistics.set_leagues(leagues.clone());
+        self.bulk_edit_dialog.leagues = leagues.clone();
+        self.team_details.set_leagues(leagues);
+        self.visualization.set_staff(staff.clone());
         self.staff_list.set_all_staff(staff);
         
-        // 更新可视化数据
+        // 更新Logo总览
+        if let Some(db_dir) = self.database.get_db_directory() {
+            self.logo_gallery.set_data(&db_dir, &teams, sponsors);
+        }
+        
+        // 更新可视化和统计数据
+        self.statistics.set_teams(teams.clone());
         self.visualization.set_teams(teams);
         
+        // 重新加载后保持当前选择
+        if let Some(team_id) = selected_team_id {
+            self.team_list.select_team_by_id(team_id);
+            self.select_team(team_id, ctx);
+        }
+        
+        self.set_status(&t!("已加载 {} 个球队", self.team_list.teams.len()));
         info!("已加载 {} 个球队", self.team_list.teams.len());
-        Ok(())
+    }
+
+    fn process_db_events(&mut self, ctx: &Context) {
+        while let Some(event) = self.worker.as_mut().and_then(DbWorker::try_recv) {
+            match event {
+                DbEvent::Progress(..) => {},
+                DbEvent::Loaded(data) => {
+                    self.loading = false;
+                    self.external_change = false;
+                    self.changes_view.invalidate();
+                    self.apply_loaded_data(data, ctx);
+                },
+                DbEvent::TeamsSaved(teams, origin) => {
+                    self.changes_view.invalidate();
+                    self.handle_teams_saved(&teams, origin);
+                },
+                DbEvent::StaffSaved(staff) => {
+                    self.changes_view.invalidate();
+                    let team_id = self.team_list.get_selected_team_id();
+                    self.staff_list.update_staff(&staff, team_id);
+                    self.visualization.update_staff(&staff);
+                    self.notifications.success(&t!("已更新员工: {}", staff.name));
+                    self.set_status(&t!("已更新员工: {}", staff.name));
+                },
+                DbEvent::StaffCopied(staff) => {
+                    let message = t!("已复制 {} 个员工到当前球队", staff.len());
+                    self.notifications.success(&message);
+                    self.set_status(&message);
+                    // 新增的员工需要完整刷新所有视图
+                    self.changes_view.invalidate();
+                    self.load_data();
+                },
+                DbEvent::Generated(data) => {
+                    let message = t!("已生成 {} 个球队和 {} 个员工", data.teams.len(), data.staff.len());
+                    self.notifications.success(&message);
+                    self.set_status(&message);
+                    // 新增的球队和员工需要完整刷新所有视图
+                    self.changes_view.invalidate();
+                    self.load_data();
+                },
+                DbEvent::SnapshotDiff(changes, taken_at) => self.changes_view.set_changes(changes, taken_at),
+                DbEvent::Reverted(count) => {
+                    self.set_status(&t!("已还原 {} 行", count));
+                    info!("已还原 {} 行", count);
+                    // 还原可能涉及任意球队和员工，重新加载后再次对比
+                    self.changes_view.invalidate();
+                    self.load_data();
+                },
+                DbEvent::Conflict(conflicts, command) => {
+                    self.set_status(&t!("保存时发现 {} 处外部修改冲突", conflicts.len()));
+                    self.conflict_dialog.open(conflicts, command);
+                },
+                DbEvent::ExternalChanges(changed) => {
+                    if changed && !self.external_change {
+                        self.handle_external_change();
+                    }
+                },
+                DbEvent::Failed(task, e) => {
+                    self.loading = false;
+                    self.changes_view.stop_waiting();
+                    self.show_error(&task, e);
+                },
+            }
+        }
+    }
+
+    // 定期检查数据库文件的修改时间，变化时由后台线程判断是否来自其他程序
+    fn check_external_changes(&mut self) {
+        if self.last_file_check.elapsed().as_secs() < FILE_CHECK_INTERVAL {
+            return;
+        }
+        self.last_file_check = Instant::now();
+        
+        if self.worker.is_none() || self.loading {
+            return;
+        }
+        
+        let Some(path) = self.database.get_db_path() else {
+            return;
+        };
+        
+        let modified = utils::database_modified_time(path);
+        if modified != self.db_modified {
+            self.db_modified = modified;
+            self.send_command(DbCommand::CheckExternalChanges);
+        }
+    }
+
+    fn handle_external_change(&mut self) {
+        self.external_change = true;
+        self.set_status(t!("数据库已被其他程序修改"));
+        info!("检测到数据库被其他程序修改");
+        
+        // 其他确认对话框打开时只在状态栏提示
+        if self.confirm_dialog.open {
+            return;
+        }
+        
+        let mut message = t!("数据库已被游戏或其他工具修改，当前显示的数据可能已过期。\n是否重新加载？").to_string();
+        if self.has_unsaved_changes {
+            message.push_str(t!("\n\n当前球队有未保存的修改，重新加载后将丢失。"));
+        }
+        self.pending_confirm = Some(ConfirmAction::ReloadExternal);
+        self.confirm_dialog.show_confirm(t!("数据库已被修改"), &message);
+    }
+
+    fn handle_conflict(&mut self) {
+        let (Some(resolution), Some(command)) = (self.conflict_dialog.resolution, self.conflict_dialog.command.take()) else {
+            return;
+        };
+        
+        match resolution {
+            ConflictResolution::Reload => self.load_data(),
+            ConflictResolution::Merge => {
+                let merged = conflict::merge_command(command, &self.conflict_dialog.conflicts);
+                self.send_command(DbCommand::Overwrite(Box::new(merged)));
+            },
+            ConflictResolution::Overwrite => self.send_command(DbCommand::Overwrite(Box::new(command))),
+        }
+    }
+
+    // 保存完成后只刷新被修改的球队
+    fn handle_teams_saved(&mut self, teams: &[Team], origin: SaveOrigin) {
+        for team in teams {
+            self.team_list.update_team(team);
+            self.visualization.update_team(team);
+            self.statistics.update_team(team);
+            if self.team_details.update_saved_team(team) {
+                self.has_unsaved_changes = false;
+            }
+        }
+        self.compare_view.invalidate_logos();
+        
+        let name = teams.first().map(|t| t.name.clone()).unwrap_or_default();
+        match origin {
+            SaveOrigin::Manual => {
+                self.notifications.success(t!("球队数据已保存"));
+                self.set_status(&t!("已保存球队 {} 的修改", name));
+            },
+            SaveOrigin::AutoSave => {
+                self.set_status(&t!("已自动保存球队 {} 的修改", name));
+                info!("自动保存成功: 球队 {}", name);
+            },
+            SaveOrigin::BulkEdit => {
+                self.notifications.success(&t!("已批量更新 {} 个球队", teams.len()));
+                self.set_status(&t!("已批量更新 {} 个球队", teams.len()));
+            },
+            SaveOrigin::CopyFromReference(field_names) => {
+                self.set_status(&t!("已从参考数据库复制字段: {}", field_names));
+            },
+            SaveOrigin::Balance(league_name) => {
+                self.notifications.success(&t!("已重新分配联赛 {} 中 {} 个球队的财富", league_name, teams.len()));
+                self.set_status(&t!("已重新分配联赛 {} 的财富", league_name));
+            },
+        }
+    }
+
+    fn show_progress_overlay(&self, ctx: &Context) {
+        if !self.loading {
+            return;
+        }
+        
+        let (message, fraction) = self.worker
+            .as_ref()
+            .and_then(DbWorker::progress)
+            .unwrap_or((t!("正在加载数据..."), 0.0));
+        let screen_rect = ctx.screen_rect();
+        
+        // 加载期间遮挡界面，避免操作尚未加载的数据
+        egui::Area::new("db_progress_overlay")
+            .order(egui::Order::Foreground)
+            .fixed_pos(screen_rect.min)
+            .show(ctx, |ui| {
+                ui.allocate_rect(screen_rect, egui::Sense::click_and_drag());
+                ui.painter().rect_filled(screen_rect, 0.0, Color32::from_black_alpha(120));
+                
+                let box_rect = egui::Rect::from_center_size(screen_rect.center(), egui::vec2(320.0, 90.0));
+                ui.allocate_ui_at_rect(box_rect, |ui| {
+                    widgets::rounded_frame(ui, |ui| {
+                        ui.horizontal(|ui| {
+                            ui.spinner();
+                            ui.strong(message);
+                        });
+                        ui.add_space(5.0);
+                        ui.add(egui::ProgressBar::new(fraction).show_percentage());
+                    });
+                });
+            });
     }
 
     fn save_team_changes(&mut self) {
-        if !self.database.is_connected() {
-            self.show_message("警告", "请先加载数据库");
+        if !self.ensure_editable() {
             return;
         }
 
         if let Some(_team) = self.team_details.get_edited_team() {
+            self.pending_confirm = Some(ConfirmAction::SaveTeam);
             self.confirm_dialog.show_confirm(
-                "确认保存",
-                "您确定要保存对球队数据的修改吗？"
+                t!("确认保存"),
+                t!("您确定要保存对球队数据的修改吗？")
             );
         } else {
-            self.show_message("警告", "请先选择一个球队");
+            self.warn(t!("请先选择一个球队"));
         }
     }
 
     fn edit_staff(&mut self, staff_idx: usize) {
+        if !self.ensure_editable() {
+            return;
+        }
+        
         if let Some(staff) = self.staff_list.team_staff.get(staff_idx).cloned() {
             if let Err(e) = self.staff_edit_dialog.open(staff) {
                 error!("打开员工编辑对话框失败: {}", e);
@@ -170,33 +827,9 @@ impl TeamEditorApp {
     fn handle_staff_edit(&mut self, _ctx: &Context) {
         if self.staff_edit_dialog.confirmed {
             match self.staff_edit_dialog.get_updated_staff() {
-                Ok(updated_staff) => {
-                    match self.database.update_staff(&updated_staff) {
-                        Ok(_) => {
-                            // 刷新员工数据
-                            match self.database.load_staff() {
-                                Ok(staff) => {
-                                    self.staff_list.set_all_staff(staff);
-                                    if let Some(team_id) = self.team_list.get_selected_team_id() {
-                                        self.staff_list.update_team_staff(team_id);
-                                    }
-                                    self.show_message("成功", &format!("已更新员工: {}", updated_staff.name));
-                                    self.set_status(&format!("已更新员工: {}", updated_staff.name));
-                                },
-                                Err(e) => {
-                                    error!("刷新员工数据失败: {}", e);
-                                }
-                            }
-                        },
-                        Err(e) => {
-                            self.show_message("错误", &format!("更新员工失败: {}", e));
-                            error!("更新员工失败: {}", e);
-                        }
-                    }
-                },
+                Ok(updated_staff) => self.send_command(DbCommand::SaveStaff(updated_staff)),
                 Err(e) => {
-                    self.show_message("错误", &format!("获取更新后的员工数据失败: {}", e));
-                    error!("获取更新后的员工数据失败: {}", e);
+                    self.show_error(t!("获取更新后的员工数据"), e);
                 }
             }
         }
@@ -204,101 +837,245 @@ impl TeamEditorApp {
 
     fn export_team_list(&mut self) {
         if self.team_list.teams.is_empty() {
-            self.show_message("警告", "没有可导出的数据");
+            self.warn(t!("没有可导出的数据"));
             return;
         }
 
         // 使用native-dialog库打开文件对话框
         let dialog = FileDialog::new()
-            .add_filter("CSV文件", &["csv"])
-            .add_filter("所有文件", &["*"])
+            .add_filter(t!("CSV文件"), &["csv"])
+            .add_filter(t!("所有文件"), &["*"])
             .show_save_single_file();
         
         if let Ok(Some(path)) = dialog {
-            self.export_path = Some(path);
+            self.pending_confirm = Some(ConfirmAction::ExportTeams(path));
             self.confirm_dialog.show_confirm(
-                "确认导出",
-                &format!("确定要导出 {} 个球队数据吗？", self.team_list.teams.len())
+                t!("确认导出"),
+                &t!("确定要导出 {} 个球队数据吗？", self.team_list.teams.len())
             );
         }
     }
 
-    fn handle_confirm_export(&mut self) {
-        if self.confirm_dialog.confirmed {
-            if let Some(path) = self.export_path.clone() {
-                // 导出CSV
-                let mut content = String::from("ID,球队名称,球队财富,成立年份,所在地区,支持者数量,主场名称,球队昵称,联赛ID\n");
-                let teams_len = self.team_list.teams.len();
-                
-                for team in &self.team_list.teams {
-                    content.push_str(&format!(
-                        "{},{},{},{},{},{},{},{},{}\n",
-                        team.id, team.name, team.wealth, team.found_year,
-                        team.location, team.supporter_count, team.stadium_name,
-                        team.nickname, team.league_id
-                    ));
-                }
-                
-                match std::fs::write(&path, content) {
-                    Ok(_) => {
-                        let path_str = path.display().to_string();
-                        self.show_message(
-                            "成功",
-                            &format!("已导出 {} 个球队数据", teams_len)
-                        );
-                        self.set_status(&format!("已导出球队数据至: {}", path_str));
-                    },
-                    Err(e) => {
-                        self.show_message("错误", &format!("导出失败: {}", e));
-                        error!("导出球队列表失败: {}", e);
-                    }
-                }
-                
-                self.export_path = None;
+    fn handle_confirm(&mut self, ctx: &Context) {
+        match self.pending_confirm.take() {
+            Some(ConfirmAction::SaveTeam) => self.handle_confirm_save(ctx),
+            Some(ConfirmAction::ExportTeams(path)) => self.handle_confirm_export(&path),
+            Some(ConfirmAction::SwitchDatabase(path)) => self.switch_database(path, ctx),
+            Some(ConfirmAction::EnterEditMode) => self.enter_edit_mode(ctx),
+            Some(ConfirmAction::ReloadExternal) => self.load_data(),
+            None => {}
+        }
+    }
+
+    fn handle_confirm_export(&mut self, path: &Path) {
+        // 导出CSV
+        let mut content = String::from(t!("ID,球队名称,球队财富,成立年份,所在地区,支持者数量,主场名称,球队昵称,联赛ID,联赛名称\n"));
+        let leagues = &self.team_details.leagues;
+        let teams_len = self.team_list.teams.len();
+        
+        for team in &self.team_list.teams {
+            content.push_str(&format!(
+                "{},{},{},{},{},{},{},{},{},{}\n",
+                team.id, team.name, team.wealth, team.found_year,
+                team.location, team.supporter_count, team.stadium_name,
+                team.nickname, team.league_id, leagues.name(team.league_id)
+            ));
+        }
+        
+        match std::fs::write(path, content) {
+            Ok(_) => {
+                let path_str = path.display().to_string();
+                self.notifications.success(&t!("已导出 {} 个球队数据", teams_len));
+                self.set_status(&t!("已导出球队数据至: {}", path_str));
+            },
+            Err(e) => {
+                self.show_error(t!("导出球队列表"), e);
+            }
+        }
+    }
+
+    fn open_audit_log(&mut self) {
+        let Some(db_path) = self.database.get_db_path() else {
+            self.warn(t!("请先加载数据库"));
+            return;
+        };
+        
+        let log_path = audit::log_path(db_path);
+        match audit::load(&log_path) {
+            Ok(entries) => self.audit_dialog.open(entries, log_path.display().to_string()),
+            Err(e) => {
+                self.show_error(t!("读取审计日志"), e);
+            }
+        }
+    }
+
+    fn export_audit_log(&mut self) {
+        let dialog = FileDialog::new()
+            .add_filter(t!("CSV文件"), &["csv"])
+            .add_filter(t!("所有文件"), &["*"])
+            .show_save_single_file();
+        
+        let Ok(Some(path)) = dialog else {
+            return;
+        };
+        
+        let entries: Vec<_> = self.audit_dialog.filtered_entries().into_iter().cloned().collect();
+        match std::fs::write(&path, audit::to_csv(&entries)) {
+            Ok(_) => {
+                self.notifications.success(&t!("已导出 {} 条审计记录", entries.len()));
+                self.set_status(&t!("已导出审计日志至: {}", path.display()));
+            },
+            Err(e) => {
+                self.show_error(t!("导出审计日志"), e);
             }
         }
     }
 
     fn open_bulk_edit(&mut self) {
-        if !self.database.is_connected() {
-            self.show_message("警告", "请先加载数据库");
+        if !self.ensure_editable() {
             return;
         }
 
         if self.team_list.teams.is_empty() {
-            self.show_message("警告", "没有可编辑的球队数据");
+            self.warn(t!("没有可编辑的球队数据"));
             return;
         }
 
         self.bulk_edit_dialog.open(self.team_list.teams.clone());
     }
 
-    fn handle_bulk_edit(&mut self, ctx: &Context) {
+    fn handle_bulk_edit(&mut self, _ctx: &Context) {
         if self.bulk_edit_dialog.confirmed {
             let modified_teams = self.bulk_edit_dialog.get_modified_teams();
             
             if !modified_teams.is_empty() {
-                match self.database.update_teams_batch(&modified_teams) {
-                    Ok(count) => {
-                        // 刷新数据
-                        if let Err(e) = self.load_data(ctx) {
-                            error!("刷新数据失败: {}", e);
-                        }
-                        
-                        self.show_message("成功", &format!("已批量更新 {} 个球队", count));
-                        self.set_status(&format!("已批量更新 {} 个球队", count));
-                    },
-                    Err(e) => {
-                        self.show_message("错误", &format!("批量更新失败: {}", e));
-                        error!("批量更新球队失败: {}", e);
-                    }
-                }
+                self.send_command(DbCommand::SaveTeams(modified_teams, SaveOrigin::BulkEdit));
+            }
+        }
+    }
+
+    fn open_balance_dialog(&mut self) {
+        if !self.ensure_editable() {
+            return;
+        }
+
+        if self.team_list.teams.is_empty() {
+            self.warn(t!("没有可编辑的球队数据"));
+            return;
+        }
+
+        // 默认选中当前球队所在的联赛
+        let league_id = self.team_list.get_selected_team().map(|team| team.league_id);
+        self.balance_dialog.open(
+            self.team_list.teams.clone(),
+            self.team_details.leagues.clone(),
+            league_id,
+        );
+    }
+
+    fn handle_balance(&mut self) {
+        if self.balance_dialog.confirmed {
+            let modified_teams = self.balance_dialog.get_modified_teams();
+            
+            if !modified_teams.is_empty() {
+                let league_name = self.balance_dialog.league_name();
+                self.send_command(DbCommand::SaveTeams(modified_teams, SaveOrigin::Balance(league_name)));
+            }
+        }
+    }
+
+    fn open_generator_dialog(&mut self) {
+        if !self.ensure_editable() {
+            return;
+        }
+
+        let league_id = self.team_list.get_selected_team().map(|team| team.league_id);
+        self.generator_dialog.open(self.team_details.leagues.clone(), league_id);
+    }
+
+    fn handle_generator(&mut self) {
+        if self.generator_dialog.confirmed {
+            let data = self.generator_dialog.data.clone();
+            self.send_command(DbCommand::InsertGenerated(data));
+        }
+    }
+
+    fn show_message(&mut self, title: &str, message: &str) {
+        self.message_dialog.show_message(title, message);
+    }
+
+    /// 弹出错误报告，operation为出错时正在执行的操作
+    fn show_error(&mut self, operation: &str, err: impl Into<AppError>) {
+        let err = err.into().with_context(ErrorContext::new(operation));
+        error!("{}", err);
+        let report = err.report();
+        self.notifications.record_error(&t!("{}失败", operation), &report.summary);
+        self.error_dialog.show_report(report);
+    }
+
+    /// 警告显示在通知区域，不打断当前操作
+    fn warn(&mut self, message: &str) {
+        self.notifications.warn(t!("警告"), message);
+    }
+
+    fn switch_language(&mut self, language: Language, ctx: &Context) {
+        i18n::set_language(language);
+        self.settings.language = language;
+        self.save_settings();
+        ctx.send_viewport_cmd(egui::ViewportCommand::Title(t!(WINDOW_TITLE).to_string()));
+        self.set_status(t!("界面语言已切换"));
+    }
+
+    fn apply_fonts(&mut self, ctx: &Context) {
+        let active_fonts = fonts::apply(ctx, &self.settings.fonts);
+
+        if let Some(path) = &self.settings.fonts.custom_font {
+            let loaded = active_fonts.iter().any(|font| font.source == FontSource::Custom(path.clone()));
+            if !loaded {
+                let message = t!("无法加载字体文件 {}，已改用其他字体", path.display());
+                self.warn(&message);
             }
         }
+        if !active_fonts.iter().any(|font| font.covers_cjk) {
+            self.warn(t!("没有找到中文字体，中文可能显示为方框。请在“视图 → 字体和缩放”中选择字体文件。"));
+        }
+
+        self.font_dialog.active_fonts = active_fonts;
     }
 
-    fn show_message(&mut self, title: &str, message: &str) {
-        self.message_dialog.show_message(title, message);
+    fn handle_font_settings(&mut self, ctx: &Context) {
+        if self.font_dialog.confirmed {
+            self.settings.fonts = self.font_dialog.settings.clone();
+            self.save_settings();
+            self.apply_fonts(ctx);
+            self.set_status(t!("字体设置已应用"));
+        }
+    }
+
+    // 按主题设置和系统外观应用配色，主题对话框打开时预览正在编辑的配色
+    fn update_theme(&mut self, ctx: &Context, frame: &Frame) {
+        let system_theme = frame.info().system_theme;
+        self.theme_dialog.system_dark = system_theme == Some(eframe::Theme::Dark);
+
+        let palette = if self.theme_dialog.open {
+            self.theme_dialog.palette
+        } else {
+            self.settings.theme.palette(self.theme_dialog.system_dark)
+        };
+
+        // 系统外观变化时eframe会重置样式，因此系统外观也要比较
+        if self.applied_theme != Some((palette, system_theme)) {
+            theme::apply_palette(ctx, &palette);
+            self.applied_theme = Some((palette, system_theme));
+        }
+    }
+
+    fn handle_theme_settings(&mut self) {
+        if self.theme_dialog.confirmed {
+            self.settings.theme = self.theme_dialog.settings.clone();
+            self.save_settings();
+            self.set_status(&t!("已切换到主题: {}", self.settings.theme.mode.as_str()));
+        }
     }
 
     fn set_status(&mut self, message: &str) {
@@ -309,55 +1086,126 @@ impl TeamEditorApp {
     fn ui_top_panel(&mut self, ctx: &Context, ui: &mut Ui) {
         ui.horizontal(|ui| {
             // 文件菜单
-            ui.menu_button("文件", |ui| {
-                if ui.button("加载数据库").clicked() {
+            ui.menu_button(t!("文件"), |ui| {
+                if ui.button(t!("加载数据库")).clicked() {
                     ui.close_menu();
                 self.load_database(ctx);
             }
                 
-                if ui.button("关闭数据库").clicked() {
+                ui.menu_button(t!("最近打开"), |ui| {
+                    if self.settings.recent_files.is_empty() {
+                        ui.label(t!("没有最近打开的数据库"));
+                    }
+                    
+                    for path in self.settings.recent_files.clone() {
+                        if ui.button(path.display().to_string()).clicked() {
+                            ui.close_menu();
+                            self.open_database(path, ctx);
+                        }
+                    }
+                    
+                    if !self.settings.recent_files.is_empty() {
+                        ui.separator();
+                        if ui.button(t!("清除最近记录")).clicked() {
+                            ui.close_menu();
+                            self.settings.clear_recent_files();
+                            self.save_settings();
+                        }
+                    }
+                });
+                
+                if ui.checkbox(&mut self.settings.open_read_only, t!("以只读方式打开数据库")).changed() {
+                    self.save_settings();
+                }
+                
+                if ui.button(t!("关闭数据库")).clicked() {
                     ui.close_menu();
+                    self.worker = None;
+                    self.loading = false;
                     if let Err(e) = self.database.close() {
-                        self.show_message("错误", &format!("关闭数据库失败: {}", e));
-                        error!("关闭数据库失败: {}", e);
+                        self.show_error(t!("关闭数据库"), e);
                     } else {
-                        self.set_status("数据库已关闭");
+                        self.set_status(t!("数据库已关闭"));
                     }
                 }
                 
                 ui.separator();
                 
-                if ui.button("导出球队列表").clicked() {
+                if ui.add_enabled(self.reference_loading.is_none(), egui::Button::new(t!("打开参考数据库（只读）"))).clicked() {
+                    ui.close_menu();
+                    self.open_reference_database(ctx);
+                }
+                
+                if ui.add_enabled(self.reference_path.is_some(), egui::Button::new(t!("关闭参考数据库"))).clicked() {
+                    ui.close_menu();
+                    self.close_reference_database();
+                }
+                
+                ui.separator();
+                
+                if ui.button(t!("导出球队列表")).clicked() {
                     ui.close_menu();
                     self.export_team_list();
                 }
                 
+                if ui.button(t!("查看审计日志")).clicked() {
+                    ui.close_menu();
+                    self.open_audit_log();
+                }
+                
                 ui.separator();
                 
-                if ui.button("退出").clicked() {
+                if ui.button(t!("退出")).clicked() {
                     ui.close_menu();
                     ctx.send_viewport_cmd(egui::ViewportCommand::Close);
                 }
             });
             
             // 编辑菜单
-            ui.menu_button("编辑", |ui| {
-                if ui.button("保存球队修改").clicked() {
+            ui.menu_button(t!("编辑"), |ui| {
+                if self.database.is_read_only() {
+                    if ui.button(t!("切换到编辑模式")).clicked() {
+                        ui.close_menu();
+                        self.request_edit_mode();
+                    }
+                } else if ui.add_enabled(self.database.is_connected(), egui::Button::new(t!("切换到只读模式"))).clicked() {
+                    ui.close_menu();
+                    self.leave_edit_mode(ctx);
+                }
+                
+                ui.separator();
+                
+                if ui.button(t!("保存球队修改")).clicked() {
                     ui.close_menu();
                 self.save_team_changes();
             }
             
-                if ui.button("批量编辑").clicked() {
+                if ui.button(t!("批量编辑")).clicked() {
                     ui.close_menu();
                 self.open_bulk_edit();
             }
             
+                if ui.button(t!("批量导入Logo")).clicked() {
+                    ui.close_menu();
+                    self.open_logo_import();
+                }
+                
+                if ui.button(t!("联赛经济平衡")).clicked() {
+                    ui.close_menu();
+                    self.open_balance_dialog();
+                }
+                
+                if ui.button(t!("生成测试数据")).clicked() {
+                    ui.close_menu();
+                    self.open_generator_dialog();
+                }
+            
                 ui.separator();
                 
             let auto_save_text = if self.auto_save_enabled {
-                    "禁用自动保存"
+                    t!("禁用自动保存")
             } else {
-                    "启用自动保存"
+                    t!("启用自动保存")
             };
             
             if ui.button(auto_save_text).clicked() {
@@ -367,34 +1215,76 @@ impl TeamEditorApp {
             });
             
             // 视图菜单
-            ui.menu_button("视图", |ui| {
-                if ui.selectable_label(self.active_tab == ActiveTab::TeamDetails, "球队详情").clicked() {
+            ui.menu_button(t!("视图"), |ui| {
+                if ui.selectable_label(self.active_tab == ActiveTab::TeamDetails, t!("球队详情")).clicked() {
                     ui.close_menu();
                     self.active_tab = ActiveTab::TeamDetails;
                 }
                 
-                if ui.selectable_label(self.active_tab == ActiveTab::Visualization, "数据可视化").clicked() {
+                if ui.selectable_label(self.active_tab == ActiveTab::Visualization, t!("数据可视化")).clicked() {
                     ui.close_menu();
                     self.active_tab = ActiveTab::Visualization;
                 }
                 
-                if ui.selectable_label(self.active_tab == ActiveTab::SponsorEditor, "杂项编辑器").clicked() {
+                if ui.selectable_label(self.active_tab == ActiveTab::Statistics, t!("统计分析")).clicked() {
+                    ui.close_menu();
+                    self.active_tab = ActiveTab::Statistics;
+                }
+                
+                if ui.selectable_label(self.active_tab == ActiveTab::Compare, t!("数据库对比")).clicked() {
+                    ui.close_menu();
+                    self.active_tab = ActiveTab::Compare;
+                }
+                
+                if ui.selectable_label(self.active_tab == ActiveTab::Gallery, t!("Logo总览")).clicked() {
+                    ui.close_menu();
+                    self.active_tab = ActiveTab::Gallery;
+                }
+                
+                if ui.selectable_label(self.active_tab == ActiveTab::Changes, t!("打开后的修改")).clicked() {
+                    ui.close_menu();
+                    self.active_tab = ActiveTab::Changes;
+                }
+                
+                if ui.selectable_label(self.active_tab == ActiveTab::SponsorEditor, t!("杂项编辑器")).clicked() {
                     ui.close_menu();
                     self.active_tab = ActiveTab::SponsorEditor;
                 }
+                
+                ui.separator();
+                
+                if ui.button(t!("主题...")).clicked() {
+                    ui.close_menu();
+                    self.theme_dialog.open(&self.settings.theme);
+                }
+                
+                if ui.button(t!("字体和缩放...")).clicked() {
+                    ui.close_menu();
+                    self.font_dialog.open(&self.settings.fonts);
+                }
             });
             
             // 帮助菜单
-            ui.menu_button("帮助", |ui| {
-                if ui.button("关于").clicked() {
+            ui.menu_button(t!("帮助"), |ui| {
+                if ui.button(t!("关于")).clicked() {
                     ui.close_menu();
                     self.show_message(
-                        "关于",
-                        "CFS球队编辑器 v0.1.0\n作者: 卡尔纳斯\n\n用于编辑和管理CFS游戏的球队数据。"
+                        t!("关于"),
+                        t!("CFS球队编辑器 v0.1.0\n作者: 卡尔纳斯\n\n用于编辑和管理CFS游戏的球队数据。")
                     );
                 }
             });
             
+            // 语言菜单的标题同时显示两种语言，不熟悉当前语言时也能找到
+            ui.menu_button("语言 / Language", |ui| {
+                for language in Language::ALL {
+                    if ui.selectable_label(self.settings.language == language, language.native_name()).clicked() {
+                        ui.close_menu();
+                        self.switch_language(language, ctx);
+                    }
+                }
+            });
+            
             // 显示当前标签页
             ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
                 ui.label(self.active_tab.as_str());
@@ -403,26 +1293,70 @@ impl TeamEditorApp {
     }
 
     fn ui_bottom_panel(&mut self, _ctx: &Context, ui: &mut Ui) {
+        let palette = theme::palette();
         ui.horizontal(|ui| {
-            ui.strong("状态:");
+            // 只读/编辑模式标识，只读时点击可切换到编辑模式
+            if self.database.is_read_only() {
+                let lock = egui::RichText::new(t!(" 只读 ")).strong().color(palette.on_accent).background_color(palette.warning);
+                if ui.add(egui::Label::new(lock).sense(egui::Sense::click()))
+                    .on_hover_text(t!("数据库以只读方式打开，点击切换到编辑模式"))
+                    .clicked()
+                {
+                    self.request_edit_mode();
+                }
+                ui.add_space(5.0);
+            } else if self.database.is_connected() {
+                ui.label(egui::RichText::new(t!(" 编辑 ")).strong().color(palette.on_accent).background_color(palette.success))
+                    .on_hover_text(t!("数据库可以修改"));
+                ui.add_space(5.0);
+            }
+            
+            ui.strong(t!("状态:"));
             ui.add_space(5.0);
             ui.label(&self.status_message);
             
-            // 显示自动保存状态
-            if self.auto_save_enabled {
-                ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
-                    ui.label(format!("自动保存: {}秒", self.auto_save_countdown));
-                });
+            // 数据库在外部被修改
+            if self.external_change {
+                ui.add_space(10.0);
+                ui.colored_label(palette.warning, t!("数据库已被外部修改"));
+                if ui.small_button(t!("重新加载")).clicked() {
+                    self.load_data();
+                }
+            }
+            
+            // 后台写入进度
+            if let Some(worker) = self.worker.as_ref().filter(|w| w.is_busy() && !self.loading) {
+                ui.add_space(10.0);
+                ui.spinner();
+                ui.label(worker.progress().map_or(t!("正在处理..."), |(message, _)| message));
             }
+            
+            ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                // 通知历史，显示未读数量
+                let unread = self.notifications.unread;
+                let text = if unread > 0 { t!("通知 ({})", unread) } else { t!("通知").to_string() };
+                if ui.selectable_label(self.notifications.history_open, text)
+                    .on_hover_text(t!("查看通知历史"))
+                    .clicked()
+                {
+                    self.notifications.toggle_history();
+                }
+                
+                // 显示自动保存状态
+                if self.auto_save_enabled && !self.database.is_read_only() {
+                    ui.add_space(10.0);
+                    ui.label(t!("自动保存: {}秒", self.auto_save_countdown));
+                }
+            });
         });
     }
 
     fn toggle_auto_save(&mut self) {
         self.auto_save_enabled = !self.auto_save_enabled;
         if self.auto_save_enabled {
-            self.set_status("自动保存已启用");
+            self.set_status(t!("自动保存已启用"));
         } else {
-            self.set_status("自动保存已禁用");
+            self.set_status(t!("自动保存已禁用"));
         }
     }
 
@@ -439,74 +1373,27 @@ impl TeamEditorApp {
         }
     }
 
-    fn auto_save(&mut self, ctx: &Context) -> bool {
-        if !self.auto_save_enabled || !self.has_unsaved_changes || !self.database.is_connected() {
+    fn auto_save(&mut self, _ctx: &Context) -> bool {
+        if !self.auto_save_enabled || !self.has_unsaved_changes || self.worker.is_none() || !self.is_editable() {
             return false;
         }
         
         if let Some(team) = self.team_details.get_edited_team() {
-            match self.database.update_team(&team) {
-                Ok(_) => {
-                    // 刷新数据但不显示消息
-                    if let Err(e) = self.load_data(ctx) {
-                        error!("自动保存后刷新数据失败: {}", e);
-                    }
-                    
-                    // 重新选择当前球队
-                    self.team_list.select_team_by_id(team.id);
-                    if let Some(team_id) = self.team_list.get_selected_team_id() {
-                        self.select_team(team_id, ctx);
-                    }
-                    
-                    self.set_status(&format!("已自动保存球队 {} 的修改", team.name));
-                    info!("自动保存成功: 球队 {}", team.name);
-                    
-                    // 重置自动保存状态
-                    self.has_unsaved_changes = false;
-                    self.last_auto_save = Instant::now();
-                    self.auto_save_countdown = AUTO_SAVE_INTERVAL;
-                    
-                    return true;
-                },
-                Err(e) => {
-                    error!("自动保存失败: {}", e);
-                    return false;
-                }
-            }
+            self.send_command(DbCommand::SaveTeams(vec![team], SaveOrigin::AutoSave));
+            
+            // 保存结果返回前不再重复触发
+            self.last_auto_save = Instant::now();
+            self.auto_save_countdown = AUTO_SAVE_INTERVAL;
+            return true;
         }
         
         false
     }
 
-    fn handle_confirm_save(&mut self, ctx: &Context) {
-        if self.confirm_dialog.confirmed {
-            if let Some(team) = self.team_details.get_edited_team() {
-                match self.database.update_team(&team) {
-                    Ok(_) => {
-                        // 刷新数据
-                        if let Err(e) = self.load_data(ctx) {
-                            error!("刷新数据失败: {}", e);
-                        }
-                        
-                        // 重新选择当前球队
-                        self.team_list.select_team_by_id(team.id);
-                        if let Some(team_id) = self.team_list.get_selected_team_id() {
-                            self.select_team(team_id, ctx);
-                        }
-                        
-                        self.show_message("成功", "球队数据已保存");
-                        self.set_status(&format!("已保存球队 {} 的修改", team.name));
-                        
-                        // 重置自动保存状态
-                        self.has_unsaved_changes = false;
-                        self.last_auto_save = Instant::now();
-                    },
-                    Err(e) => {
-                        self.show_message("错误", &format!("保存失败: {}", e));
-                        error!("保存球队数据失败: {}", e);
-                    }
-                }
-            }
+    fn handle_confirm_save(&mut self, _ctx: &Context) {
+        if let Some(team) = self.team_details.get_edited_team() {
+            self.send_command(DbCommand::SaveTeams(vec![team], SaveOrigin::Manual));
+            self.last_auto_save = Instant::now();
         }
     }
 
@@ -525,7 +1412,7 @@ impl TeamEditorApp {
             // 更新员工列表
             self.staff_list.update_team_staff(team_id);
             
-            self.set_status(&format!("已选择球队: ID={}", team_id));
+            self.set_status(&t!("已选择球队: ID={}", team_id));
             
             // 重置自动保存状态
             self.has_unsaved_changes = false;
@@ -534,60 +1421,249 @@ impl TeamEditorApp {
     }
 
     fn replace_logo(&mut self, ctx: &Context) {
-        if !self.database.is_connected() {
-            self.show_message("警告", "请先加载数据库");
+        if !self.ensure_editable() {
             return;
         }
 
         if let Some(team_id) = self.team_list.get_selected_team_id() {
             let dialog = FileDialog::new()
-                .add_filter("图片文件", &["png", "jpg", "jpeg", "bmp"])
+                .add_filter(t!("图片文件"), &logo::IMAGE_EXTENSIONS)
                 .show_open_single_file();
             
             if let Ok(Some(path)) = dialog {
-                if let Some(db_dir) = self.database.get_db_directory() {
-                    // 创建logos目录（如果不存在）
-                    let logos_dir = db_dir.join("logos");
-                    if !logos_dir.exists() {
-                        if let Err(e) = std::fs::create_dir_all(&logos_dir) {
-                            self.show_message("错误", &format!("创建logos目录失败: {}", e));
-                            error!("创建logos目录失败: {}", e);
-                            return;
-                        }
-                    }
-                    
-                    // 保存Logo
-                    let target_path = logos_dir.join(format!("{}.png", team_id));
-                    if let Err(e) = utils::save_image_as_png(&path, &target_path, 256, 256) {
-                        self.show_message("错误", &format!("保存Logo失败: {}", e));
-                        error!("保存Logo失败: {}", e);
-                        return;
-                    }
-                    
-                    // 重新加载Logo
-                    if let Err(e) = self.team_details.load_logo(ctx, &db_dir, team_id) {
-                        self.show_message("错误", &format!("加载Logo失败: {}", e));
-                        error!("加载Logo失败: {}", e);
-                        return;
-                    }
-                    
-                    self.set_status("Logo已替换");
-                }
+                self.open_logo_editor(ctx, team_id, &path);
             }
         } else {
-            self.show_message("警告", "请先选择一个球队");
+            self.warn(t!("请先选择一个球队"));
+        }
+    }
+
+    fn open_logo_editor(&mut self, ctx: &Context, team_id: i64, path: &Path) {
+        if let Err(e) = self.logo_editor_dialog.open(ctx, team_id, path) {
+            self.show_error(t!("读取图片"), e);
         }
     }
+
+    fn handle_logo_editor(&mut self, ctx: &Context) {
+        let team_id = self.logo_editor_dialog.team_id;
+        let db_dir = match self.database.get_db_directory() {
+            Some(dir) => dir,
+            None => return,
+        };
+        
+        if let Some(img) = self.logo_editor_dialog.get_prepared_logo() {
+            if let Err(e) = logo::save_logo(&db_dir, team_id, img) {
+                self.show_error(t!("保存Logo"), e);
+                return;
+            }
+        }
+        
+        self.reload_logo(ctx, team_id);
+        self.set_status(t!("Logo已保存"));
+    }
+
+    fn handle_logo_action(&mut self, action: LogoAction, ctx: &Context) {
+        if !self.ensure_editable() {
+            return;
+        }
+        
+        if action == LogoAction::Replace {
+            self.replace_logo(ctx);
+            return;
+        }
+        
+        let (team_id, db_dir) = match (self.team_list.get_selected_team_id(), self.database.get_db_directory()) {
+            (Some(team_id), Some(db_dir)) => (team_id, db_dir),
+            _ => return,
+        };
+        
+        let (result, status) = match action {
+            LogoAction::Edit => {
+                self.open_logo_editor(ctx, team_id, &logo::logo_path(&db_dir, team_id));
+                return;
+            },
+            LogoAction::Remove => (logo::remove_logo(&db_dir, team_id), t!("Logo已移除")),
+            LogoAction::Restore => (logo::restore_original_logo(&db_dir, team_id), t!("已恢复原始Logo")),
+            LogoAction::Replace => unreachable!(),
+        };
+        
+        match result {
+            Ok(_) => {
+                self.reload_logo(ctx, team_id);
+                self.set_status(status);
+            },
+            Err(e) => {
+                self.show_error(t!("Logo操作"), e);
+            }
+        }
+    }
+
+    fn open_logo_import(&mut self) {
+        if !self.ensure_editable() {
+            return;
+        }
+        
+        let db_dir = match self.database.get_db_directory() {
+            Some(dir) => dir,
+            None => return,
+        };
+
+        if let Ok(Some(folder)) = FileDialog::new().show_open_single_dir() {
+            if let Err(e) = self.logo_import_dialog.open(&folder, &db_dir, &self.team_list.teams) {
+                self.show_error(t!("读取文件夹"), e);
+            }
+        }
+    }
+
+    fn handle_logo_import(&mut self, ctx: &Context) {
+        let count = self.logo_import_dialog.imported_count();
+        if let Some(team_id) = self.team_list.get_selected_team_id() {
+            self.reload_logo(ctx, team_id);
+        }
+        self.compare_view.invalidate_logos();
+        self.logo_gallery.invalidate_all_teams();
+        self.notifications.success(&t!("已批量导入 {} 个Logo", count));
+        self.set_status(&t!("已批量导入 {} 个Logo", count));
+        info!("已批量导入 {} 个Logo", count);
+    }
+
+    fn handle_visualization_action(&mut self, action: VisualizationAction, ctx: &Context) {
+        match action {
+            VisualizationAction::SelectTeam(team_id) => self.reveal_team(team_id, ctx),
+            VisualizationAction::ExportChart => self.export_chart(),
+            VisualizationAction::ExportReport => self.export_report(),
+            VisualizationAction::DrillDown { label, team_ids } => {
+                self.set_status(&t!("图表筛选: {} ({} 支球队)", label, team_ids.len()));
+                self.team_list.set_drill_down(label, team_ids);
+            },
+            VisualizationAction::ClearDrillDown => self.team_list.clear_drill_down(),
+        }
+    }
+
+    fn export_chart(&mut self) {
+        let chart = match self.visualization.current_chart() {
+            Some(chart) => chart,
+            None => {
+                self.warn(t!("没有可导出的图表"));
+                return;
+            }
+        };
+        
+        let dialog = FileDialog::new()
+            .add_filter(t!("PNG图片"), &["png"])
+            .add_filter(t!("SVG矢量图"), &["svg"])
+            .show_save_single_file();
+        
+        if let Ok(Some(mut path)) = dialog {
+            // 未填写扩展名时默认保存为PNG
+            if path.extension().is_none() {
+                path.set_extension("png");
+            }
+            
+            match chart.save(&path, chart::EXPORT_WIDTH, chart::EXPORT_HEIGHT) {
+                Ok(_) => {
+                    self.notifications.success(&t!("已导出图表: {}", path.display()));
+                    self.set_status(&t!("已导出图表: {}", path.display()));
+                },
+                Err(e) => {
+                    self.show_error(t!("导出图表"), e);
+                }
+            }
+        }
+    }
+
+    fn export_report(&mut self) {
+        if self.visualization.teams.is_empty() {
+            self.warn(t!("没有可导出的数据"));
+            return;
+        }
+        
+        let dialog = FileDialog::new()
+            .add_filter(t!("HTML文件"), &["html"])
+            .show_save_single_file();
+        
+        if let Ok(Some(mut path)) = dialog {
+            if path.extension().is_none() {
+                path.set_extension("html");
+            }
+            
+            let db_name = self.database.get_db_path()
+                .and_then(|p| p.file_name())
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let html = report::build_html_report(
+                &db_name,
+                &self.visualization.teams,
+                &self.team_details.leagues,
+                &self.visualization.report_sections(),
+                self.visualization.show_data_table,
+                self.visualization.show_percentage,
+            );
+            
+            match report::save_html_report(&path, &html) {
+                Ok(_) => {
+                    self.notifications.success(&t!("已生成报告: {}", path.display()));
+                    self.set_status(&t!("已生成报告: {}", path.display()));
+                },
+                Err(e) => {
+                    self.show_error(t!("生成报告"), e);
+                }
+            }
+        }
+    }
+
+    // 在球队列表中选中球队，球队被筛选隐藏时清除筛选条件
+    fn reveal_team(&mut self, team_id: i64, ctx: &Context) {
+        self.team_list.select_team_by_id(team_id);
+        if self.team_list.get_selected_team_id() != Some(team_id) {
+            self.team_list.clear_filters();
+            self.team_list.select_team_by_id(team_id);
+        }
+        
+        self.select_team(team_id, ctx);
+    }
+
+    fn open_team_from_gallery(&mut self, team_id: i64, ctx: &Context) {
+        self.reveal_team(team_id, ctx);
+        self.active_tab = ActiveTab::TeamDetails;
+    }
+
+    fn reload_logo(&mut self, ctx: &Context, team_id: i64) {
+        if let Some(db_dir) = self.database.get_db_directory() {
+            if let Err(e) = self.team_details.load_logo(ctx, &db_dir, team_id) {
+                self.show_error(t!("加载Logo"), e);
+            }
+        }
+        self.compare_view.invalidate_logos();
+        self.logo_gallery.invalidate_team(team_id);
+    }
 }
 
 impl App for TeamEditorApp {
-    fn update(&mut self, ctx: &Context, _frame: &mut Frame) {
+    fn update(&mut self, ctx: &Context, frame: &mut Frame) {
+        self.update_theme(ctx, frame);
+        
+        // 打开命令行指定的数据库
+        if let Some(path) = self.pending_open.take() {
+            self.open_database(path, ctx);
+        }
+        
+        // 处理拖放的数据库文件
+        self.handle_dropped_files(ctx);
+        
+        // 处理后台数据库线程的结果
+        self.process_db_events(ctx);
+        self.process_reference_events();
+        self.check_external_changes();
+        
         // 处理对话框
         self.message_dialog.show(ctx);
+        self.error_dialog.show(ctx);
         
         if self.confirm_dialog.show(ctx) {
-            self.handle_confirm_save(ctx);
-            self.handle_confirm_export();
+            self.handle_confirm(ctx);
+        } else if !self.confirm_dialog.open {
+            self.pending_confirm = None;
         }
         
         if self.staff_edit_dialog.show(ctx) {
@@ -598,6 +1674,38 @@ impl App for TeamEditorApp {
             self.handle_bulk_edit(ctx);
         }
         
+        if self.balance_dialog.show(ctx) {
+            self.handle_balance();
+        }
+        
+        if self.generator_dialog.show(ctx) {
+            self.handle_generator();
+        }
+        
+        if self.audit_dialog.show(ctx) {
+            self.export_audit_log();
+        }
+        
+        if self.conflict_dialog.show(ctx) {
+            self.handle_conflict();
+        }
+        
+        if self.logo_editor_dialog.show(ctx) {
+            self.handle_logo_editor(ctx);
+        }
+        
+        if self.logo_import_dialog.show(ctx) {
+            self.handle_logo_import(ctx);
+        }
+        
+        if self.font_dialog.show(ctx) {
+            self.handle_font_settings(ctx);
+        }
+        
+        if self.theme_dialog.show(ctx) {
+            self.handle_theme_settings();
+        }
+        
         // 自动保存
         self.update_auto_save_timer();
         if self.auto_save_countdown == 0 {
@@ -623,6 +1731,11 @@ impl App for TeamEditorApp {
                 if let Some(team_id) = self.team_list.ui(ui) {
                     self.select_team(team_id, ctx);
                 }
+                
+                // 在球队列表中取消图表筛选后，图表也回到全部球队
+                if self.team_list.drill_down_label.is_none() && !self.visualization.drill_path.is_empty() {
+                    self.visualization.clear_drill_down();
+                }
             });
         
         // 右侧面板 - 员工列表
@@ -640,9 +1753,13 @@ impl App for TeamEditorApp {
         CentralPanel::default().show(ctx, |ui| {
             // 选项卡
                 ui.horizontal(|ui| {
-                    ui.selectable_value(&mut self.active_tab, ActiveTab::TeamDetails, "球队详情");
-                    ui.selectable_value(&mut self.active_tab, ActiveTab::Visualization, "数据可视化");
-                ui.selectable_value(&mut self.active_tab, ActiveTab::SponsorEditor, "杂项编辑器");
+                    ui.selectable_value(&mut self.active_tab, ActiveTab::TeamDetails, t!("球队详情"));
+                    ui.selectable_value(&mut self.active_tab, ActiveTab::Visualization, t!("数据可视化"));
+                    ui.selectable_value(&mut self.active_tab, ActiveTab::Statistics, t!("统计分析"));
+                    ui.selectable_value(&mut self.active_tab, ActiveTab::Compare, t!("数据库对比"));
+                    ui.selectable_value(&mut self.active_tab, ActiveTab::Gallery, t!("Logo总览"));
+                    ui.selectable_value(&mut self.active_tab, ActiveTab::Changes, t!("打开后的修改"));
+                ui.selectable_value(&mut self.active_tab, ActiveTab::SponsorEditor, t!("杂项编辑器"));
                 });
                 
             ui.separator();
@@ -652,9 +1769,9 @@ impl App for TeamEditorApp {
                     ActiveTab::TeamDetails => {
                         widgets::rounded_frame(ui, |ui| {
                             // 球队详情
-                            let (logo_clicked, field_changed) = self.team_details.ui(ui);
-                            if logo_clicked {
-                                self.replace_logo(ctx);
+                            let (logo_action, field_changed) = self.team_details.ui(ui);
+                            if let Some(action) = logo_action {
+                                self.handle_logo_action(action, ctx);
                             }
                             
                             // 如果有字段被修改，设置未保存更改标志
@@ -665,24 +1782,90 @@ impl App for TeamEditorApp {
                         });
                     },
                     ActiveTab::Visualization => {
+                        let selected_team_id = self.team_list.get_selected_team_id();
+                        let mut visualization_action = None;
+                        
                         widgets::rounded_frame(ui, |ui| {
                             // 数据可视化
-                            self.visualization.ui(ui);
+                            visualization_action = self.visualization.ui(ui, selected_team_id);
+                        });
+                        
+                        if let Some(action) = visualization_action {
+                            self.handle_visualization_action(action, ctx);
+                        }
+                },
+                ActiveTab::Statistics => {
+                    widgets::rounded_frame(ui, |ui| {
+                        ScrollArea::vertical().show(ui, |ui| {
+                            self.statistics.ui(ui);
+                        });
+                    });
+                },
+                ActiveTab::Compare => {
+                    let working_dir = self.database.get_db_directory();
+                    let ref_dir = self.reference_directory();
+                    let mut compare_action = None;
+                    
+                    widgets::rounded_frame(ui, |ui| {
+                        ScrollArea::vertical().show(ui, |ui| {
+                            compare_action = self.compare_view.ui(
+                                ui,
+                                self.team_details.team.as_ref(),
+                                &self.staff_list.team_staff,
+                                working_dir.as_deref(),
+                                ref_dir.as_deref(),
+                            );
                         });
+                    });
+                    
+                    if let Some(action) = compare_action {
+                        self.handle_compare_action(action, ctx);
+                    }
+                },
+                ActiveTab::Gallery => {
+                    let mut gallery_action = None;
+                    
+                    widgets::rounded_frame(ui, |ui| {
+                        gallery_action = self.logo_gallery.ui(ui);
+                    });
+                    
+                    if let Some(GalleryAction::OpenTeam(team_id)) = gallery_action {
+                        self.open_team_from_gallery(team_id, ctx);
+                    }
+                },
+                ActiveTab::Changes => {
+                    let mut changes_action = None;
+                    
+                    widgets::rounded_frame(ui, |ui| {
+                        changes_action = self.changes_view.ui(ui);
+                    });
+                    
+                    if let Some(action) = changes_action {
+                        self.handle_changes_action(action);
+                    }
                 },
                 ActiveTab::SponsorEditor => {
                     widgets::rounded_frame(ui, |ui| {
                         // 显示提示信息，而不是实际的赞助商编辑器
                         ui.vertical_centered(|ui| {
                             ui.add_space(50.0);
-                            ui.heading("杂项编辑器功能暂时不可用");
+                            ui.heading(t!("杂项编辑器功能暂时不可用"));
                             ui.add_space(20.0);
-                            ui.label("该功能正在维护中，请稍后再试。");
+                            ui.label(t!("该功能正在维护中，请稍后再试。"));
                             ui.add_space(50.0);
                         });
                     });
                 }
                 }
             });
+        
+        // 加载进度
+        self.show_progress_overlay(ctx);
+        
+        // 非阻塞的警告通知
+        self.notifications.show(ctx);
+        
+        // 拖放提示
+        self.show_drop_overlay(ctx);
     }
 } 
\ No newline at end of file
@@ -0,0 +1,251 @@
+// 联赛经济平衡
+//
+// 按选定的分布曲线把一个联赛的总财富重新分配给各个球队。每个球队先得到最低财富，
+// 剩余部分按权重分配，取整产生的误差补给排名第一的球队，保证总额与目标一致。
+
+use crate::data::team::Team;
+
+/// 财富的目标分布
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum WealthCurve {
+    /// 按当前财富排名，排名越靠前分得越多
+    TopHeavy,
+    /// 所有球队相同
+    Flat,
+    /// 按支持者数量的比例分配
+    SupporterScaled,
+}
+
+impl WealthCurve {
+    pub const ALL: [WealthCurve; 3] = [WealthCurve::TopHeavy, WealthCurve::Flat, WealthCurve::SupporterScaled];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WealthCurve::TopHeavy => t!("头部集中"),
+            WealthCurve::Flat => t!("平均分配"),
+            WealthCurve::SupporterScaled => t!("按支持者数量"),
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            WealthCurve::TopHeavy => t!("按当前财富排名，第n名的份额与 1/n^陡峭度 成正比"),
+            WealthCurve::Flat => t!("扣除最低财富后，剩余财富平均分给每个球队"),
+            WealthCurve::SupporterScaled => t!("份额与 支持者数量^陡峭度 成正比，陡峭度为1时严格按比例"),
+        }
+    }
+
+    /// 是否使用陡峭度参数
+    pub fn uses_steepness(&self) -> bool {
+        !matches!(self, WealthCurve::Flat)
+    }
+}
+
+/// 重新分配的参数
+#[derive(Debug, Clone, Copy)]
+pub struct BalanceParams {
+    pub curve: WealthCurve,
+    /// 联赛的目标总财富
+    pub total_wealth: i64,
+    /// 每个球队至少分得的财富
+    pub min_wealth: i64,
+    pub steepness: f64,
+}
+
+/// 单个球队的调整方案
+#[derive(Debug, Clone)]
+pub struct Proposal {
+    pub team_id: i64,
+    pub name: String,
+    pub current: i64,
+    pub proposed: i64,
+}
+
+impl Proposal {
+    pub fn change(&self) -> i64 {
+        self.proposed - self.current
+    }
+}
+
+/// 计算每个球队的新财富，结果按新财富从高到低排列
+///
+/// 目标总额不足以满足最低财富时，每个球队都只分得平均值。
+pub fn propose(teams: &[&Team], params: &BalanceParams) -> Vec<Proposal> {
+    if teams.is_empty() {
+        return Vec::new();
+    }
+
+    // 按当前财富从高到低排列，财富相同时按ID保证结果稳定
+    let mut ranked: Vec<&Team> = teams.to_vec();
+    ranked.sort_by(|a, b| b.wealth.cmp(&a.wealth).then_with(|| a.id.cmp(&b.id)));
+
+    let count = ranked.len() as i64;
+    let total = params.total_wealth.max(0);
+    let floor = params.min_wealth.max(0).min(total / count);
+    let remaining = total - floor * count;
+
+    let weights = curve_weights(&ranked, params);
+    let weight_sum: f64 = weights.iter().sum();
+
+    let mut shares: Vec<i64> = weights.iter()
+        .map(|weight| {
+            if weight_sum > 0.0 {
+                (remaining as f64 * weight / weight_sum).floor() as i64
+            } else {
+                remaining / count
+            }
+        })
+        .collect();
+
+    // 取整误差补给份额最大的球队
+    let distributed: i64 = shares.iter().sum();
+    if let Some(top) = (0..shares.len()).max_by(|&a, &b| weights[a].total_cmp(&weights[b]).then(b.cmp(&a))) {
+        shares[top] += remaining - distributed;
+    }
+
+    let mut proposals: Vec<Proposal> = ranked.iter()
+        .zip(shares)
+        .map(|(team, share)| Proposal {
+            team_id: team.id,
+            name: team.name.clone(),
+            current: team.wealth,
+            proposed: floor + share,
+        })
+        .collect();
+
+    proposals.sort_by(|a, b| b.proposed.cmp(&a.proposed).then_with(|| a.team_id.cmp(&b.team_id)));
+    proposals
+}
+
+// 按排名顺序计算每个球队的权重
+fn curve_weights(ranked: &[&Team], params: &BalanceParams) -> Vec<f64> {
+    let steepness = params.steepness.max(0.0);
+    match params.curve {
+        WealthCurve::TopHeavy => (0..ranked.len())
+            .map(|rank| 1.0 / ((rank + 1) as f64).powf(steepness))
+            .collect(),
+        WealthCurve::Flat => vec![1.0; ranked.len()],
+        WealthCurve::SupporterScaled => ranked.iter()
+            .map(|team| (team.supporter_count.max(0) as f64).powf(steepness))
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn team(id: i64, wealth: i64, supporter_count: i64) -> Team {
+        Team {
+            id,
+            name: format!("球队{}", id),
+            wealth,
+            found_year: 1900,
+            location: String::new(),
+            supporter_count,
+            stadium_name: String::new(),
+            nickname: String::new(),
+            league_id: 1,
+        }
+    }
+
+    fn sample_teams() -> Vec<Team> {
+        vec![
+            team(1, 5_000_000, 120_000),
+            team(2, 80_000, 3_000),
+            team(3, 1_200_000, 45_000),
+            team(4, 1_200_000, 0),
+            team(5, 17, 999),
+            team(6, 640_000, 20_000),
+            team(7, 3_300_000, 77_777),
+        ]
+    }
+
+    fn params(curve: WealthCurve, total_wealth: i64, min_wealth: i64, steepness: f64) -> BalanceParams {
+        BalanceParams { curve, total_wealth, min_wealth, steepness }
+    }
+
+    fn all_params() -> Vec<BalanceParams> {
+        let mut result = Vec::new();
+        for curve in WealthCurve::ALL {
+            for (total, min) in [(10_000_000, 100_000), (1_000_003, 0), (999, 7), (500, 1_000), (0, 0), (-50, 10)] {
+                for steepness in [0.0, 0.5, 1.0, 2.5] {
+                    result.push(params(curve, total, min, steepness));
+                }
+            }
+        }
+        result
+    }
+
+    #[test]
+    fn empty_league_has_no_proposals() {
+        assert!(propose(&[], &params(WealthCurve::Flat, 1000, 10, 1.0)).is_empty());
+    }
+
+    #[test]
+    fn proposals_sum_to_target_and_stay_in_range() {
+        let teams = sample_teams();
+        let refs: Vec<&Team> = teams.iter().collect();
+
+        for params in all_params() {
+            let proposals = propose(&refs, &params);
+            assert_eq!(proposals.len(), teams.len());
+
+            let total = params.total_wealth.max(0);
+            let floor = params.min_wealth.max(0).min(total / teams.len() as i64);
+            assert_eq!(proposals.iter().map(|p| p.proposed).sum::<i64>(), total, "{:?}", params);
+            for proposal in &proposals {
+                assert!(proposal.proposed >= floor && proposal.proposed <= total, "{:?} {:?}", params, proposal);
+            }
+
+            // 结果按新财富从高到低排列
+            assert!(proposals.windows(2).all(|pair| pair[0].proposed >= pair[1].proposed), "{:?}", params);
+        }
+    }
+
+    #[test]
+    fn insufficient_total_gives_average() {
+        let teams = sample_teams();
+        let refs: Vec<&Team> = teams.iter().collect();
+        let proposals = propose(&refs, &params(WealthCurve::TopHeavy, 700, 1_000, 1.0));
+        assert!(proposals.iter().all(|p| p.proposed == 100));
+    }
+
+    #[test]
+    fn flat_curve_differs_by_at_most_rounding() {
+        let teams = sample_teams();
+        let refs: Vec<&Team> = teams.iter().collect();
+        let proposals = propose(&refs, &params(WealthCurve::Flat, 1_000_003, 0, 1.0));
+        let max = proposals.iter().map(|p| p.proposed).max().unwrap();
+        let min = proposals.iter().map(|p| p.proposed).min().unwrap();
+        assert!(max - min < teams.len() as i64);
+    }
+
+    #[test]
+    fn top_heavy_keeps_the_current_ranking() {
+        let teams = sample_teams();
+        let refs: Vec<&Team> = teams.iter().collect();
+        let proposals = propose(&refs, &params(WealthCurve::TopHeavy, 10_000_000, 100_000, 1.5));
+        let order: Vec<i64> = proposals.iter().map(|p| p.team_id).collect();
+        assert_eq!(order, vec![1, 7, 3, 4, 6, 2, 5]);
+    }
+
+    #[test]
+    fn applying_a_proposal_again_is_stable() {
+        let mut teams = sample_teams();
+
+        for params in all_params() {
+            let refs: Vec<&Team> = teams.iter().collect();
+            let first = propose(&refs, &params);
+            for proposal in &first {
+                let team = teams.iter_mut().find(|t| t.id == proposal.team_id).unwrap();
+                team.wealth = proposal.proposed;
+            }
+
+            // 已经达到目标分布时不再产生任何调整
+            let refs: Vec<&Team> = teams.iter().collect();
+            let second = propose(&refs, &params);
+            assert!(second.iter().all(|p| p.change() == 0), "{:?}", params);
+        }
+    }
+}
@@ -0,0 +1,599 @@
+// 图表导出
+//
+// 图表先布局为一组简单图形（矩形、线段、圆、扇形和文字），再分别输出为SVG文本
+// 或直接光栅化为PNG图片，不依赖窗口和GPU，因此可以在界面之外生成。
+
+use std::f32::consts::{PI, TAU};
+use std::path::Path;
+
+use ab_glyph::{Font, FontVec, PxScale, ScaleFont};
+use image::{Rgba, RgbaImage};
+use log::{info, warn};
+
+use crate::error::{AppError, Result};
+use crate::fonts;
+use crate::i18n::{self, Language};
+
+pub type Rgb = [u8; 3];
+
+/// 图表默认导出尺寸
+pub const EXPORT_WIDTH: u32 = 1200;
+pub const EXPORT_HEIGHT: u32 = 800;
+
+/// 条形图和饼图使用的颜色，与界面中的图表一致
+pub const CHART_COLORS: [Rgb; 8] = [
+    [100, 150, 250],
+    [250, 150, 100],
+    [100, 250, 150],
+    [250, 100, 150],
+    [150, 100, 250],
+    [150, 250, 100],
+    [100, 200, 200],
+    [200, 200, 100],
+];
+
+const BACKGROUND: Rgb = [255, 255, 255];
+const GRID_COLOR: Rgb = [220, 220, 220];
+const AXIS_COLOR: Rgb = [150, 150, 160];
+const TEXT_COLOR: Rgb = [60, 60, 80];
+const TICK_COUNT: usize = 5;
+
+/// 散点图中的一个数据点
+#[derive(Debug, Clone)]
+pub struct ScatterDatum {
+    pub x: f64,
+    pub y: f64,
+    pub color: Rgb,
+}
+
+#[derive(Debug, Clone)]
+pub enum ChartData {
+    Bar(Vec<(String, i64)>),
+    Pie(Vec<(String, i64)>),
+    Scatter {
+        x_label: String,
+        y_label: String,
+        points: Vec<ScatterDatum>,
+        legend: Vec<(String, Rgb)>,
+    },
+}
+
+/// 可以导出的图表
+#[derive(Debug, Clone)]
+pub struct Chart {
+    pub title: String,
+    pub data: ChartData,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Anchor {
+    Start,
+    Middle,
+    End,
+}
+
+// 布局后的图形，坐标以像素为单位，原点在左上角
+#[derive(Debug, Clone)]
+enum Shape {
+    Rect { x: f32, y: f32, w: f32, h: f32, fill: Rgb },
+    Line { x1: f32, y1: f32, x2: f32, y2: f32, width: f32, color: Rgb },
+    Circle { cx: f32, cy: f32, r: f32, fill: Rgb },
+    /// 扇形，角度从正上方开始顺时针计算
+    Wedge { cx: f32, cy: f32, r: f32, start: f32, end: f32, fill: Rgb },
+    /// y为文字的垂直中心
+    Text { x: f32, y: f32, text: String, size: f32, color: Rgb, anchor: Anchor },
+}
+
+impl Chart {
+    pub fn new(title: &str, data: ChartData) -> Self {
+        Chart { title: title.to_string(), data }
+    }
+
+    /// 生成SVG文本
+    pub fn to_svg(&self, width: u32, height: u32) -> String {
+        let mut svg = format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}" font-family="Microsoft YaHei, PingFang SC, sans-serif">"#,
+            w = width, h = height
+        );
+        svg.push('\n');
+
+        for shape in self.layout(width as f32, height as f32) {
+            match shape {
+                Shape::Rect { x, y, w, h, fill } => svg.push_str(&format!(
+                    r#"<rect x="{:.1}" y="{:.1}" width="{:.1}" height="{:.1}" fill="{}"/>"#,
+                    x, y, w, h, hex(fill)
+                )),
+                Shape::Line { x1, y1, x2, y2, width, color } => svg.push_str(&format!(
+                    r#"<line x1="{:.1}" y1="{:.1}" x2="{:.1}" y2="{:.1}" stroke="{}" stroke-width="{:.1}"/>"#,
+                    x1, y1, x2, y2, hex(color), width
+                )),
+                Shape::Circle { cx, cy, r, fill } => svg.push_str(&format!(
+                    r#"<circle cx="{:.1}" cy="{:.1}" r="{:.1}" fill="{}"/>"#,
+                    cx, cy, r, hex(fill)
+                )),
+                Shape::Wedge { cx, cy, r, start, end, fill } => {
+                    if end - start >= TAU - 0.0001 {
+                        svg.push_str(&format!(
+                            r#"<circle cx="{:.1}" cy="{:.1}" r="{:.1}" fill="{}"/>"#,
+                            cx, cy, r, hex(fill)
+                        ));
+                    } else {
+                        let (x1, y1) = polar(cx, cy, r, start);
+                        let (x2, y2) = polar(cx, cy, r, end);
+                        let large_arc = if end - start > PI { 1 } else { 0 };
+                        svg.push_str(&format!(
+                            r#"<path d="M{:.1},{:.1} L{:.1},{:.1} A{:.1},{:.1} 0 {} 1 {:.1},{:.1} Z" fill="{}" stroke="white" stroke-width="1"/>"#,
+                            cx, cy, x1, y1, r, r, large_arc, x2, y2, hex(fill)
+                        ));
+                    }
+                },
+                Shape::Text { x, y, text, size, color, anchor } => {
+                    let anchor = match anchor {
+                        Anchor::Start => "start",
+                        Anchor::Middle => "middle",
+                        Anchor::End => "end",
+                    };
+                    svg.push_str(&format!(
+                        r#"<text x="{:.1}" y="{:.1}" font-size="{:.0}" fill="{}" text-anchor="{}" dominant-baseline="central">{}</text>"#,
+                        x, y, size, hex(color), anchor, escape_xml(&text)
+                    ));
+                },
+            }
+            svg.push('\n');
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// 光栅化为PNG图片，找不到可用字体时不绘制文字
+    pub fn render_png(&self, width: u32, height: u32) -> RgbaImage {
+        let mut canvas = Canvas::new(width, height, load_font());
+        for shape in self.layout(width as f32, height as f32) {
+            canvas.draw(&shape);
+        }
+        canvas.image
+    }
+
+    /// 按文件扩展名保存为SVG或PNG
+    pub fn save(&self, path: &Path, width: u32, height: u32) -> Result<()> {
+        let extension = path.extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_lowercase())
+            .unwrap_or_default();
+
+        match extension.as_str() {
+            "svg" => std::fs::write(path, self.to_svg(width, height))?,
+            "png" => self.render_png(width, height).save(path)?,
+            _ => return Err(AppError::InvalidInput(t!("不支持的图表格式: {}", path.display()))),
+        }
+
+        info!("已导出图表 {} 到 {}", self.title, path.display());
+        Ok(())
+    }
+
+    fn layout(&self, width: f32, height: f32) -> Vec<Shape> {
+        let mut shapes = vec![
+            Shape::Rect { x: 0.0, y: 0.0, w: width, h: height, fill: BACKGROUND },
+            Shape::Text {
+                x: width / 2.0,
+                y: 28.0,
+                text: self.title.clone(),
+                size: 22.0,
+                color: TEXT_COLOR,
+                anchor: Anchor::Middle,
+            },
+        ];
+
+        match &self.data {
+            ChartData::Bar(entries) => layout_bar(&mut shapes, entries, width, height),
+            ChartData::Pie(entries) => layout_pie(&mut shapes, entries, width, height),
+            ChartData::Scatter { x_label, y_label, points, legend } => {
+                layout_scatter(&mut shapes, x_label, y_label, points, legend, width, height)
+            },
+        }
+
+        shapes
+    }
+}
+
+fn layout_bar(shapes: &mut Vec<Shape>, entries: &[(String, i64)], width: f32, height: f32) {
+    let (left, right, top, bottom) = (70.0, width - 30.0, 70.0, height - 80.0);
+    let max_value = entries.iter().map(|(_, v)| *v).max().unwrap_or(0).max(1);
+    let max_display = nice_ceiling(max_value as f64);
+
+    // Y轴刻度和水平辅助线
+    for i in 0..=TICK_COUNT {
+        let t = i as f32 / TICK_COUNT as f32;
+        let y = bottom - (bottom - top) * t;
+        shapes.push(Shape::Line { x1: left, y1: y, x2: right, y2: y, width: 1.0, color: GRID_COLOR });
+        shapes.push(Shape::Text {
+            x: left - 8.0,
+            y,
+            text: format_value(max_display * t as f64),
+            size: 13.0,
+            color: TEXT_COLOR,
+            anchor: Anchor::End,
+        });
+    }
+    shapes.push(Shape::Line { x1: left, y1: bottom, x2: right, y2: bottom, width: 1.5, color: AXIS_COLOR });
+
+    if entries.is_empty() {
+        return;
+    }
+
+    let slot = (right - left) / entries.len() as f32;
+    let bar_width = slot * 0.7;
+    let label_size = if entries.len() > 10 { 10.0 } else { 13.0 };
+
+    for (i, (label, value)) in entries.iter().enumerate() {
+        let bar_height = (bottom - top) * (*value as f64 / max_display) as f32;
+        let x = left + slot * i as f32 + (slot - bar_width) / 2.0;
+        let center = x + bar_width / 2.0;
+
+        shapes.push(Shape::Rect {
+            x,
+            y: bottom - bar_height,
+            w: bar_width,
+            h: bar_height,
+            fill: CHART_COLORS[i % CHART_COLORS.len()],
+        });
+        shapes.push(Shape::Text {
+            x: center,
+            y: bottom - bar_height - 12.0,
+            text: value.to_string(),
+            size: 13.0,
+            color: TEXT_COLOR,
+            anchor: Anchor::Middle,
+        });
+        // 条形较多时标签错开两行，避免重叠
+        let label_y = if entries.len() > 8 && i % 2 == 1 { bottom + 38.0 } else { bottom + 18.0 };
+        shapes.push(Shape::Text {
+            x: center,
+            y: label_y,
+            text: label.clone(),
+            size: label_size,
+            color: TEXT_COLOR,
+            anchor: Anchor::Middle,
+        });
+    }
+}
+
+fn layout_pie(shapes: &mut Vec<Shape>, entries: &[(String, i64)], width: f32, height: f32) {
+    let total: i64 = entries.iter().map(|(_, v)| *v).sum();
+    if total <= 0 {
+        shapes.push(Shape::Text {
+            x: width / 2.0,
+            y: height / 2.0,
+            text: t!("没有数据可显示").to_string(),
+            size: 16.0,
+            color: TEXT_COLOR,
+            anchor: Anchor::Middle,
+        });
+        return;
+    }
+
+    let radius = ((height - 120.0) / 2.0).min(width * 0.3);
+    let (cx, cy) = (40.0 + radius, 60.0 + (height - 60.0) / 2.0);
+
+    let mut angle = 0.0;
+    for (i, (_, value)) in entries.iter().enumerate() {
+        let sweep = *value as f32 / total as f32 * TAU;
+        if sweep > 0.0 {
+            shapes.push(Shape::Wedge {
+                cx,
+                cy,
+                r: radius,
+                start: angle,
+                end: angle + sweep,
+                fill: CHART_COLORS[i % CHART_COLORS.len()],
+            });
+        }
+        angle += sweep;
+    }
+
+    // 图例
+    let legend_x = cx + radius + 60.0;
+    let row_height = ((height - 120.0) / entries.len().max(1) as f32).min(32.0);
+    let legend_top = cy - row_height * entries.len() as f32 / 2.0;
+    for (i, (label, value)) in entries.iter().enumerate() {
+        let y = legend_top + row_height * (i as f32 + 0.5);
+        shapes.push(Shape::Rect {
+            x: legend_x,
+            y: y - 8.0,
+            w: 16.0,
+            h: 16.0,
+            fill: CHART_COLORS[i % CHART_COLORS.len()],
+        });
+        shapes.push(Shape::Text {
+            x: legend_x + 26.0,
+            y,
+            text: format!("{}: {} ({:.1}%)", label, value, *value as f64 / total as f64 * 100.0),
+            size: 14.0,
+            color: TEXT_COLOR,
+            anchor: Anchor::Start,
+        });
+    }
+}
+
+fn layout_scatter(
+    shapes: &mut Vec<Shape>,
+    x_label: &str,
+    y_label: &str,
+    points: &[ScatterDatum],
+    legend: &[(String, Rgb)],
+    width: f32,
+    height: f32,
+) {
+    let (left, right, top, bottom) = (90.0, width - 180.0, 60.0, height - 70.0);
+
+    let (mut x_min, mut x_max, mut y_min, mut y_max) = (f64::MAX, f64::MIN, f64::MAX, f64::MIN);
+    for point in points {
+        x_min = x_min.min(point.x);
+        x_max = x_max.max(point.x);
+        y_min = y_min.min(point.y);
+        y_max = y_max.max(point.y);
+    }
+    if points.is_empty() {
+        (x_min, x_max, y_min, y_max) = (0.0, 1.0, 0.0, 1.0);
+    }
+    let x_pad = ((x_max - x_min) * 0.05).max(0.5);
+    let y_pad = ((y_max - y_min) * 0.05).max(0.5);
+    let (x_min, x_max, y_min, y_max) = (x_min - x_pad, x_max + x_pad, y_min - y_pad, y_max + y_pad);
+
+    let to_x = |x: f64| left + ((x - x_min) / (x_max - x_min)) as f32 * (right - left);
+    let to_y = |y: f64| bottom - ((y - y_min) / (y_max - y_min)) as f32 * (bottom - top);
+
+    for i in 0..=TICK_COUNT {
+        let t = i as f64 / TICK_COUNT as f64;
+        let x = to_x(x_min + (x_max - x_min) * t);
+        let y = to_y(y_min + (y_max - y_min) * t);
+        shapes.push(Shape::Line { x1: x, y1: top, x2: x, y2: bottom, width: 1.0, color: GRID_COLOR });
+        shapes.push(Shape::Line { x1: left, y1: y, x2: right, y2: y, width: 1.0, color: GRID_COLOR });
+        shapes.push(Shape::Text {
+            x,
+            y: bottom + 16.0,
+            text: format_value(x_min + (x_max - x_min) * t),
+            size: 12.0,
+            color: TEXT_COLOR,
+            anchor: Anchor::Middle,
+        });
+        shapes.push(Shape::Text {
+            x: left - 8.0,
+            y,
+            text: format_value(y_min + (y_max - y_min) * t),
+            size: 12.0,
+            color: TEXT_COLOR,
+            anchor: Anchor::End,
+        });
+    }
+    shapes.push(Shape::Line { x1: left, y1: bottom, x2: right, y2: bottom, width: 1.5, color: AXIS_COLOR });
+    shapes.push(Shape::Line { x1: left, y1: top, x2: left, y2: bottom, width: 1.5, color: AXIS_COLOR });
+
+    shapes.push(Shape::Text {
+        x: (left + right) / 2.0,
+        y: bottom + 45.0,
+        text: x_label.to_string(),
+        size: 15.0,
+        color: TEXT_COLOR,
+        anchor: Anchor::Middle,
+    });
+    shapes.push(Shape::Text {
+        x: left,
+        y: top - 16.0,
+        text: y_label.to_string(),
+        size: 15.0,
+        color: TEXT_COLOR,
+        anchor: Anchor::Middle,
+    });
+
+    for point in points {
+        shapes.push(Shape::Circle { cx: to_x(point.x), cy: to_y(point.y), r: 4.0, fill: point.color });
+    }
+
+    for (i, (label, color)) in legend.iter().enumerate() {
+        let y = top + 12.0 + i as f32 * 24.0;
+        if y > bottom {
+            break;
+        }
+        shapes.push(Shape::Circle { cx: right + 30.0, cy: y, r: 6.0, fill: *color });
+        shapes.push(Shape::Text {
+            x: right + 44.0,
+            y,
+            text: label.clone(),
+            size: 13.0,
+            color: TEXT_COLOR,
+            anchor: Anchor::Start,
+        });
+    }
+}
+
+// 从正上方开始顺时针的角度对应的坐标
+fn polar(cx: f32, cy: f32, r: f32, angle: f32) -> (f32, f32) {
+    (cx + r * angle.sin(), cy - r * angle.cos())
+}
+
+// 向上取整到1、2、5乘以10的幂
+fn nice_ceiling(value: f64) -> f64 {
+    if value <= 0.0 {
+        return 1.0;
+    }
+    let magnitude = 10f64.powf(value.log10().floor());
+    [1.0, 2.0, 5.0, 10.0].iter()
+        .map(|step| step * magnitude)
+        .find(|&candidate| candidate >= value)
+        .unwrap_or(10.0 * magnitude)
+}
+
+/// 坐标轴刻度的缩写，中文按万和亿缩写，英文按K和M缩写
+pub fn format_value(value: f64) -> String {
+    let abs = value.abs();
+    match i18n::language() {
+        Language::Chinese if abs >= 100_000_000.0 => format!("{:.1}亿", value / 100_000_000.0),
+        Language::Chinese if abs >= 10_000.0 => format!("{:.1}万", value / 10_000.0),
+        Language::English if abs >= 1_000_000.0 => format!("{:.1}M", value / 1_000_000.0),
+        Language::English if abs >= 10_000.0 => format!("{:.1}K", value / 1_000.0),
+        _ => format!("{:.0}", value),
+    }
+}
+
+fn hex(color: Rgb) -> String {
+    format!("#{:02x}{:02x}{:02x}", color[0], color[1], color[2])
+}
+
+/// 转义XML和HTML中的特殊字符
+pub fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// 优先使用界面当前的中文字体，找不到时使用egui内置字体
+fn load_font() -> Option<FontVec> {
+    if let Some((data, index)) = fonts::cjk_font_data() {
+        if let Ok(font) = FontVec::try_from_vec_and_index(data, index) {
+            return Some(font);
+        }
+    }
+
+    let fonts = egui::FontDefinitions::default();
+    let fallback = fonts.font_data.get("Ubuntu-Light")
+        .and_then(|data| FontVec::try_from_vec(data.font.to_vec()).ok());
+    if fallback.is_none() {
+        warn!("未找到可用字体，导出的PNG图表将不包含文字");
+    }
+    fallback
+}
+
+// 简单的软件光栅化，使用覆盖率实现抗锯齿
+struct Canvas {
+    image: RgbaImage,
+    font: Option<FontVec>,
+}
+
+impl Canvas {
+    fn new(width: u32, height: u32, font: Option<FontVec>) -> Self {
+        Canvas {
+            image: RgbaImage::from_pixel(width, height, Rgba([255, 255, 255, 255])),
+            font,
+        }
+    }
+
+    fn blend(&mut self, x: i64, y: i64, color: Rgb, coverage: f32) {
+        if x < 0 || y < 0 || x >= self.image.width() as i64 || y >= self.image.height() as i64 {
+            return;
+        }
+        let coverage = coverage.clamp(0.0, 1.0);
+        if coverage <= 0.0 {
+            return;
+        }
+        let pixel = self.image.get_pixel_mut(x as u32, y as u32);
+        for (channel, &target) in pixel.0.iter_mut().zip(color.iter()) {
+            let old = *channel as f32;
+            *channel = (old + (target as f32 - old) * coverage).round() as u8;
+        }
+    }
+
+    // 对包围盒内的每个像素计算覆盖率
+    fn fill_region(&mut self, min: (f32, f32), max: (f32, f32), color: Rgb, coverage: impl Fn(f32, f32) -> f32) {
+        let x0 = min.0.floor().max(0.0) as i64;
+        let y0 = min.1.floor().max(0.0) as i64;
+        let x1 = max.0.ceil().min(self.image.width() as f32) as i64;
+        let y1 = max.1.ceil().min(self.image.height() as f32) as i64;
+        for y in y0..y1 {
+            for x in x0..x1 {
+                let c = coverage(x as f32 + 0.5, y as f32 + 0.5);
+                self.blend(x, y, color, c);
+            }
+        }
+    }
+
+    fn draw(&mut self, shape: &Shape) {
+        match shape {
+            Shape::Rect { x, y, w, h, fill } => {
+                let (x, y, w, h) = (*x, *y, *w, *h);
+                self.fill_region((x, y), (x + w, y + h), *fill, |px, py| {
+                    let cx = (px + 0.5).min(x + w) - (px - 0.5).max(x);
+                    let cy = (py + 0.5).min(y + h) - (py - 0.5).max(y);
+                    cx.max(0.0) * cy.max(0.0)
+                });
+            },
+            Shape::Line { x1, y1, x2, y2, width, color } => {
+                let (x1, y1, x2, y2, half) = (*x1, *y1, *x2, *y2, width / 2.0);
+                let min = (x1.min(x2) - half - 1.0, y1.min(y2) - half - 1.0);
+                let max = (x1.max(x2) + half + 1.0, y1.max(y2) + half + 1.0);
+                self.fill_region(min, max, *color, |px, py| {
+                    half + 0.5 - distance_to_segment(px, py, x1, y1, x2, y2)
+                });
+            },
+            Shape::Circle { cx, cy, r, fill } => {
+                let (cx, cy, r) = (*cx, *cy, *r);
+                self.fill_region((cx - r - 1.0, cy - r - 1.0), (cx + r + 1.0, cy + r + 1.0), *fill, |px, py| {
+                    r + 0.5 - ((px - cx).powi(2) + (py - cy).powi(2)).sqrt()
+                });
+            },
+            Shape::Wedge { cx, cy, r, start, end, fill } => {
+                let (cx, cy, r, start, end) = (*cx, *cy, *r, *start, *end);
+                self.fill_region((cx - r - 1.0, cy - r - 1.0), (cx + r + 1.0, cy + r + 1.0), *fill, |px, py| {
+                    let angle = (px - cx).atan2(cy - py).rem_euclid(TAU);
+                    if angle < start || angle > end {
+                        return 0.0;
+                    }
+                    r + 0.5 - ((px - cx).powi(2) + (py - cy).powi(2)).sqrt()
+                });
+            },
+            Shape::Text { x, y, text, size, color, anchor } => self.draw_text(*x, *y, text, *size, *color, *anchor),
+        }
+    }
+
+    fn draw_text(&mut self, x: f32, y: f32, text: &str, size: f32, color: Rgb, anchor: Anchor) {
+        let Some(font) = self.font.take() else {
+            return;
+        };
+
+        let scaled = font.as_scaled(PxScale::from(size));
+        let glyphs: Vec<_> = text.chars().map(|c| scaled.scaled_glyph(c)).collect();
+        let text_width: f32 = glyphs.iter().map(|g| scaled.h_advance(g.id)).sum();
+
+        let mut caret = match anchor {
+            Anchor::Start => x,
+            Anchor::Middle => x - text_width / 2.0,
+            Anchor::End => x - text_width,
+        };
+        let baseline = y + (scaled.ascent() + scaled.descent()) / 2.0;
+
+        for mut glyph in glyphs {
+            let advance = scaled.h_advance(glyph.id);
+            glyph.position = ab_glyph::point(caret, baseline);
+            if let Some(outlined) = font.outline_glyph(glyph) {
+                let bounds = outlined.px_bounds();
+                outlined.draw(|gx, gy, coverage| {
+                    self.blend(bounds.min.x as i64 + gx as i64, bounds.min.y as i64 + gy as i64, color, coverage);
+                });
+            }
+            caret += advance;
+        }
+
+        self.font = Some(font);
+    }
+}
+
+fn distance_to_segment(px: f32, py: f32, x1: f32, y1: f32, x2: f32, y2: f32) -> f32 {
+    let (dx, dy) = (x2 - x1, y2 - y1);
+    let length_sq = dx * dx + dy * dy;
+    let t = if length_sq == 0.0 {
+        0.0
+    } else {
+        (((px - x1) * dx + (py - y1) * dy) / length_sq).clamp(0.0, 1.0)
+    };
+    ((px - x1 - t * dx).powi(2) + (py - y1 - t * dy).powi(2)).sqrt()
+}
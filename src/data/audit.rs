@@ -0,0 +1,155 @@
+// 审计日志
+//
+// 编辑器对数据库的每次修改都按字段追加到数据库旁边的JSONL文件中（数据库文件名加上
+// .audit.jsonl），每行一条记录，包括时间、表、主键、字段以及修改前后的值。
+// 日志只追加不改写，写入日志失败只记录错误，不影响对数据库本身的修改。
+
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::Local;
+use log::error;
+use rusqlite::types::Value;
+use serde::{Deserialize, Serialize};
+
+use crate::error::Result;
+
+/// 一个字段的一次修改
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub timestamp: String,
+    /// 表名，如Teams、Staff
+    pub entity: String,
+    pub id: String,
+    pub field: String,
+    pub old_value: serde_json::Value,
+    pub new_value: serde_json::Value,
+}
+
+impl AuditEntry {
+    pub fn new(entity: &str, id: &Value, field: &str, old_value: &Value, new_value: &Value) -> Self {
+        AuditEntry {
+            timestamp: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+            entity: entity.to_string(),
+            id: value_text(&json_value(id)),
+            field: field.to_string(),
+            old_value: json_value(old_value),
+            new_value: json_value(new_value),
+        }
+    }
+
+    pub fn old_text(&self) -> String {
+        value_text(&self.old_value)
+    }
+
+    pub fn new_text(&self) -> String {
+        value_text(&self.new_value)
+    }
+}
+
+/// 数据库对应的审计日志路径
+pub fn log_path(db_path: &Path) -> PathBuf {
+    let mut name = db_path.as_os_str().to_owned();
+    name.push(".audit.jsonl");
+    PathBuf::from(name)
+}
+
+/// 判断新值与旧值是否不同，按显示文本比较，避免同一数值以文本和整数两种类型存储时被当作修改
+pub fn is_changed(old_value: &Value, new_value: &Value) -> bool {
+    value_text(&json_value(old_value)) != value_text(&json_value(new_value))
+}
+
+/// 在日志末尾追加记录
+pub fn append(path: &Path, entries: &[AuditEntry]) -> Result<()> {
+    if entries.is_empty() {
+        return Ok(());
+    }
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    let mut content = String::new();
+    for entry in entries {
+        content.push_str(&serde_json::to_string(entry)?);
+        content.push('\n');
+    }
+    file.write_all(content.as_bytes())?;
+    Ok(())
+}
+
+/// 读取全部记录，日志不存在时返回空列表，无法解析的行会被跳过
+pub fn load(path: &Path) -> Result<Vec<AuditEntry>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let reader = BufReader::new(File::open(path)?);
+    let mut entries = Vec::new();
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str(&line) {
+            Ok(entry) => entries.push(entry),
+            Err(e) => error!("审计日志第 {} 行无法解析: {}", index + 1, e),
+        }
+    }
+    Ok(entries)
+}
+
+/// 导出为CSV文本
+pub fn to_csv(entries: &[AuditEntry]) -> String {
+    let mut content = String::from(t!("时间,表,主键,字段,原值,新值\n"));
+    for entry in entries {
+        let fields = [
+            entry.timestamp.clone(),
+            entry.entity.clone(),
+            entry.id.clone(),
+            entry.field.clone(),
+            entry.old_text(),
+            entry.new_text(),
+        ];
+        let line: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+        content.push_str(&line.join(","));
+        content.push('\n');
+    }
+    content
+}
+
+/// 表名的中文名称
+pub fn entity_label(entity: &str) -> &str {
+    match entity {
+        "Teams" => t!("球队"),
+        "Staff" => t!("员工"),
+        "Sponsor" => t!("赞助商"),
+        "FA" => t!("足协"),
+        other => other,
+    }
+}
+
+fn json_value(value: &Value) -> serde_json::Value {
+    match value {
+        Value::Null => serde_json::Value::Null,
+        Value::Integer(i) => (*i).into(),
+        Value::Real(f) => (*f).into(),
+        Value::Text(text) => text.clone().into(),
+        Value::Blob(bytes) => t!("<{} 字节>", bytes.len()).into(),
+    }
+}
+
+fn value_text(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Null => t!("(空)").to_string(),
+        serde_json::Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+// 包含逗号、引号或换行的字段加上引号
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
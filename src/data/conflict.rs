@@ -0,0 +1,243 @@
+// 外部修改冲突检测
+//
+// 后台线程记录每个球队和员工最近一次由编辑器读取或写入的值（基准值）。保存前把数据库
+// 中的当前值与基准值比较，不一致说明这一行在编辑器之外被修改过（例如游戏正在运行），
+// 此时不直接写入，而是把冲突交给用户选择重新加载、覆盖或按字段合并。
+// 另外记录数据库的 data_version，用于判断文件变化是否来自其他程序。
+
+use std::collections::HashMap;
+
+use crate::data::database::Database;
+use crate::data::staff::Staff;
+use crate::data::team::{Team, TeamField};
+use crate::data::worker::DbCommand;
+use crate::error::Result;
+
+/// 编辑器最近一次看到的数据
+pub struct KnownState {
+    teams: HashMap<i64, Team>,
+    staff: HashMap<i64, Staff>,
+    /// 最近一次加载时数据库的修改版本号
+    pub data_version: i64,
+}
+
+impl KnownState {
+    pub fn new() -> Self {
+        KnownState {
+            teams: HashMap::new(),
+            staff: HashMap::new(),
+            data_version: 0,
+        }
+    }
+
+    /// 完整加载后替换所有基准值
+    pub fn reset(&mut self, teams: &[Team], staff: &[Staff], data_version: i64) {
+        self.teams = teams.iter().map(|t| (t.id, t.clone())).collect();
+        self.staff = staff.iter().map(|s| (s.id, s.clone())).collect();
+        self.data_version = data_version;
+    }
+
+    /// 写入成功后更新基准值
+    pub fn update_teams(&mut self, teams: &[Team]) {
+        for team in teams {
+            self.teams.insert(team.id, team.clone());
+        }
+    }
+
+    pub fn update_staff(&mut self, staff: &[Staff]) {
+        for member in staff {
+            self.staff.insert(member.id, member.clone());
+        }
+    }
+
+    /// 找出要保存的球队中在编辑器之外被修改过的
+    pub fn check_teams(&self, database: &Database, teams: &[Team]) -> Result<Vec<SaveConflict>> {
+        if teams.iter().all(|t| !self.teams.contains_key(&t.id)) {
+            return Ok(Vec::new());
+        }
+
+        let mut current: HashMap<i64, Team> = database.load_teams()?
+            .into_iter()
+            .map(|t| (t.id, t))
+            .collect();
+
+        Ok(teams.iter()
+            .filter_map(|ours| {
+                let base = self.teams.get(&ours.id)?;
+                let theirs = current.remove(&ours.id);
+                (theirs.as_ref() != Some(base)).then(|| SaveConflict::Team {
+                    base: base.clone(),
+                    ours: ours.clone(),
+                    theirs,
+                })
+            })
+            .collect())
+    }
+
+    pub fn check_staff(&self, database: &Database, staff: &[Staff]) -> Result<Vec<SaveConflict>> {
+        if staff.iter().all(|s| !self.staff.contains_key(&s.id)) {
+            return Ok(Vec::new());
+        }
+
+        let mut current: HashMap<i64, Staff> = database.load_staff()?
+            .into_iter()
+            .map(|s| (s.id, s))
+            .collect();
+
+        Ok(staff.iter()
+            .filter_map(|ours| {
+                let base = self.staff.get(&ours.id)?;
+                let theirs = current.remove(&ours.id);
+                (theirs.as_ref() != Some(base)).then(|| SaveConflict::Staff {
+                    base: base.clone(),
+                    ours: ours.clone(),
+                    theirs,
+                })
+            })
+            .collect())
+    }
+}
+
+/// 一个字段在基准、数据库当前值和本次修改中的取值
+pub struct FieldConflict {
+    pub field: &'static str,
+    pub base: String,
+    pub theirs: String,
+    pub ours: String,
+}
+
+impl FieldConflict {
+    /// 编辑器和外部程序都修改了这个字段且结果不同
+    pub fn is_both_changed(&self) -> bool {
+        self.ours != self.base && self.theirs != self.base && self.ours != self.theirs
+    }
+}
+
+/// 保存时发现的一行冲突，theirs为None表示该行已被外部删除
+#[derive(Debug, Clone)]
+pub enum SaveConflict {
+    Team { base: Team, ours: Team, theirs: Option<Team> },
+    Staff { base: Staff, ours: Staff, theirs: Option<Staff> },
+}
+
+impl SaveConflict {
+    pub fn label(&self) -> String {
+        match self {
+            SaveConflict::Team { ours, .. } => t!("球队 {}", ours),
+            SaveConflict::Staff { ours, .. } => t!("员工 {}", ours),
+        }
+    }
+
+    pub fn is_deleted(&self) -> bool {
+        match self {
+            SaveConflict::Team { theirs, .. } => theirs.is_none(),
+            SaveConflict::Staff { theirs, .. } => theirs.is_none(),
+        }
+    }
+
+    /// 编辑器或外部程序修改过的字段
+    pub fn fields(&self) -> Vec<FieldConflict> {
+        let fields = match self {
+            SaveConflict::Team { base, ours, theirs } => TeamField::ALL.iter()
+                .map(|field| FieldConflict {
+                    field: field.as_str(),
+                    base: field.value_string(base),
+                    theirs: theirs.as_ref().map(|t| field.value_string(t)).unwrap_or_default(),
+                    ours: field.value_string(ours),
+                })
+                .collect::<Vec<_>>(),
+            SaveConflict::Staff { base, ours, theirs } => staff_fields(base, ours, theirs.as_ref()),
+        };
+
+        fields.into_iter()
+            .filter(|f| f.ours != f.base || (!self.is_deleted() && f.theirs != f.base))
+            .collect()
+    }
+}
+
+// 读取员工某个字段的显示值
+type StaffValue = fn(&Staff) -> String;
+
+fn staff_fields(base: &Staff, ours: &Staff, theirs: Option<&Staff>) -> Vec<FieldConflict> {
+    let values: [(&'static str, StaffValue); 4] = [
+        (t!("姓名"), |s| s.name.clone()),
+        (t!("能力"), |s| s.ability_json.clone()),
+        (t!("声望"), |s| s.fame.to_string()),
+        (t!("所属球队ID"), |s| s.team_id.to_string()),
+    ];
+
+    values.iter()
+        .map(|(field, value)| FieldConflict {
+            field,
+            base: value(base),
+            theirs: theirs.map(value).unwrap_or_default(),
+            ours: value(ours),
+        })
+        .collect()
+}
+
+/// 按字段合并：编辑器修改过的字段使用编辑器的值，其余字段保留数据库中的当前值
+fn merge_team(base: &Team, ours: &Team, theirs: Option<&Team>) -> Team {
+    let Some(theirs) = theirs else {
+        return ours.clone();
+    };
+
+    let mut merged = theirs.clone();
+    for field in TeamField::ALL {
+        if field.value_string(ours) != field.value_string(base) {
+            field.copy_value(&mut merged, ours);
+        }
+    }
+    merged
+}
+
+fn merge_staff(base: &Staff, ours: &Staff, theirs: Option<&Staff>) -> Staff {
+    let Some(theirs) = theirs else {
+        return ours.clone();
+    };
+
+    let mut merged = theirs.clone();
+    if ours.name != base.name {
+        merged.name = ours.name.clone();
+    }
+    if ours.ability_json != base.ability_json {
+        merged.ability_json = ours.ability_json.clone();
+    }
+    if ours.fame != base.fame {
+        merged.fame = ours.fame;
+    }
+    merged
+}
+
+/// 用合并后的值替换命令中发生冲突的行
+pub fn merge_command(command: DbCommand, conflicts: &[SaveConflict]) -> DbCommand {
+    match command {
+        DbCommand::SaveTeams(teams, origin) => {
+            let teams = teams.into_iter()
+                .map(|team| {
+                    conflicts.iter()
+                        .find_map(|conflict| match conflict {
+                            SaveConflict::Team { base, ours, theirs } if ours.id == team.id => {
+                                Some(merge_team(base, ours, theirs.as_ref()))
+                            },
+                            _ => None,
+                        })
+                        .unwrap_or(team)
+                })
+                .collect();
+            DbCommand::SaveTeams(teams, origin)
+        },
+        DbCommand::SaveStaff(staff) => {
+            let staff = conflicts.iter()
+                .find_map(|conflict| match conflict {
+                    SaveConflict::Staff { base, ours, theirs } if ours.id == staff.id => {
+                        Some(merge_staff(base, ours, theirs.as_ref()))
+                    },
+                    _ => None,
+                })
+                .unwrap_or(staff);
+            DbCommand::SaveStaff(staff)
+        },
+        other => other,
+    }
+}
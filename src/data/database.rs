@@ -1,18 +1,29 @@
-use std::collections::HashMap;
+use std::cell::RefCell;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use log::{error, info};
-use rusqlite::{Connection, Result as SqlResult, Transaction};
+use rusqlite::{params_from_iter, Connection, OpenFlags, OptionalExtension, Result as SqlResult, Transaction};
+use rusqlite::types::Value;
 
+use crate::data::audit::{self, AuditEntry};
+use crate::data::snapshot::format_value;
 use crate::data::staff::Staff;
-use crate::data::team::{League, Team};
+use crate::data::team::{League, Leagues, Team};
 use crate::data::sponsor::{Sponsor, FA};
-use crate::error::{AppError, Result};
+use crate::error::{AppError, ErrorContext, Result, ResultExt};
+use crate::generator::GeneratedData;
+
+// 数据库被其他连接锁定时，单条语句最多等待的时间
+const BUSY_TIMEOUT: Duration = Duration::from_secs(2);
 
 pub struct Database {
     conn: Option<Connection>,
     db_path: Option<PathBuf>,
+    read_only: bool,
+    // 在transaction中执行时暂存的审计日志，提交成功后才写入
+    pending_audit: RefCell<Option<Vec<AuditEntry>>>,
 }
 
 impl Database {
@@ -20,6 +31,8 @@ impl Database {
         Database {
             conn: None,
             db_path: None,
+            read_only: false,
+            pending_audit: RefCell::new(None),
         }
     }
 
@@ -27,68 +40,108 @@ impl Database {
         self.conn.is_some()
     }
 
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    pub fn get_db_path(&self) -> Option<&Path> {
+        self.db_path.as_deref()
+    }
+
     pub fn get_db_directory(&self) -> Option<PathBuf> {
         self.db_path.as_ref().map(|p| p.parent().unwrap().to_path_buf())
     }
 
     pub fn connect(&mut self, path: &Path) -> Result<()> {
-        let conn = Connection::open(path)?;
+        let conn = Connection::open(path)
+            .and_then(|conn| conn.busy_timeout(BUSY_TIMEOUT).map(|_| conn))
+            .context(|| ErrorContext::new(t!("打开数据库 {}", path.display())))?;
         self.db_path = Some(path.to_path_buf());
         self.conn = Some(conn);
+        self.read_only = false;
         info!("数据库连接成功: {}", path.display());
         Ok(())
     }
 
+    pub fn connect_read_only(&mut self, path: &Path) -> Result<()> {
+        let conn = Connection::open_with_flags(
+            path,
+            OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+        )
+            .and_then(|conn| conn.busy_timeout(BUSY_TIMEOUT).map(|_| conn))
+            .context(|| ErrorContext::new(t!("以只读方式打开数据库 {}", path.display())))?;
+        self.db_path = Some(path.to_path_buf());
+        self.conn = Some(conn);
+        self.read_only = true;
+        info!("数据库以只读方式连接成功: {}", path.display());
+        Ok(())
+    }
+
     pub fn close(&mut self) -> Result<()> {
         if let Some(conn) = self.conn.take() {
             drop(conn);
             self.db_path = None;
+            self.read_only = false;
             info!("数据库连接已关闭");
         }
         Ok(())
     }
 
+    /// 数据库的修改版本号，只有其他连接（包括其他程序）提交修改后才会变化
+    pub fn data_version(&self) -> Result<i64> {
+        let conn = self.conn.as_ref().ok_or(AppError::DatabaseNotLoaded)?;
+        Ok(conn.query_row("PRAGMA data_version", [], |row| row.get(0))?)
+    }
+
     pub fn load_teams(&self) -> Result<Vec<Team>> {
         let conn = self.conn.as_ref().ok_or(AppError::DatabaseNotLoaded)?;
         
-        let mut stmt = conn.prepare(
-            "SELECT ID, TeamName, TeamWealth, TeamFoundYear, TeamLocation, 
+        let sql = "SELECT ID, TeamName, TeamWealth, TeamFoundYear, TeamLocation, 
             SupporterCount, StadiumName, Nickname, BelongingLeague 
-            FROM Teams ORDER BY TeamName"
-        )?;
+            FROM Teams ORDER BY TeamName";
+        let context = || ErrorContext::new(t!("读取球队")).table("Teams").sql(sql);
         
+        let mut stmt = conn.prepare(sql).context(context)?;
         let teams = stmt
-            .query_map([], |row| Team::from_row(&row))?
-            .collect::<SqlResult<Vec<_>>>()?;
+            .query_map([], |row| Team::from_row(&row))
+            .and_then(|rows| rows.collect::<SqlResult<Vec<_>>>())
+            .context(context)?;
         
         Ok(teams)
     }
 
-    pub fn load_leagues(&self) -> Result<HashMap<i64, String>> {
+    pub fn load_leagues(&self) -> Result<Leagues> {
         let conn = self.conn.as_ref().ok_or(AppError::DatabaseNotLoaded)?;
         
-        let mut stmt = conn.prepare("SELECT ID, LeagueName FROM League")?;
+        let sql = "SELECT ID, LeagueName FROM League";
+        let context = || ErrorContext::new(t!("读取联赛")).table("League").sql(sql);
         
+        let mut stmt = conn.prepare(sql).context(context)?;
         let leagues = stmt
             .query_map([], |row| {
-                Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
-            })?
-            .collect::<SqlResult<HashMap<_, _>>>()?;
+                Ok(League {
+                    id: row.get(0)?,
+                    name: row.get(1)?,
+                })
+            })
+            .and_then(|rows| rows.collect::<SqlResult<Vec<_>>>())
+            .context(context)?;
         
-        Ok(leagues)
+        Ok(Leagues::new(leagues))
     }
 
     pub fn load_staff(&self) -> Result<Vec<Staff>> {
         let conn = self.conn.as_ref().ok_or(AppError::DatabaseNotLoaded)?;
         
-        let mut stmt = conn.prepare(
-            "SELECT ID, Name, AbilityJSON, Fame, EmployedTeamID 
-            FROM Staff ORDER BY Name"
-        )?;
+        let sql = "SELECT ID, Name, AbilityJSON, Fame, EmployedTeamID 
+            FROM Staff ORDER BY Name";
+        let context = || ErrorContext::new(t!("读取员工")).table("Staff").sql(sql);
         
+        let mut stmt = conn.prepare(sql).context(context)?;
         let staff = stmt
-            .query_map([], |row| Staff::from_row(&row))?
-            .collect::<SqlResult<Vec<_>>>()?;
+            .query_map([], |row| Staff::from_row(&row))
+            .and_then(|rows| rows.collect::<SqlResult<Vec<_>>>())
+            .context(context)?;
         
         Ok(staff)
     }
@@ -153,8 +206,7 @@ impl Database {
         let sponsors = sponsors.into_iter()
             .map(|mut sponsor| {
                 if let Some(db_dir) = self.get_db_directory() {
-                    let logo_dir = db_dir.join("SponsorLogos");
-                    let logo_path = logo_dir.join(format!("{}.png", sponsor.sponsor_name));
+                    let logo_path = crate::logo::sponsor_logo_path(&db_dir, &sponsor.sponsor_name);
                     if logo_path.exists() {
                         sponsor.logo_path = Some(logo_path);
                     }
@@ -234,32 +286,7 @@ impl Database {
     }
 
     pub fn update_team(&self, team: &Team) -> Result<()> {
-        let conn = self.conn.as_ref().ok_or(AppError::DatabaseNotLoaded)?;
-        
-        conn.execute(
-            "UPDATE Teams SET 
-            TeamName = ?1, 
-            TeamWealth = ?2, 
-            TeamFoundYear = ?3, 
-            TeamLocation = ?4, 
-            SupporterCount = ?5, 
-            StadiumName = ?6, 
-            Nickname = ?7,
-            BelongingLeague = ?8
-            WHERE ID = ?9",
-            (
-                &team.name,
-                &team.wealth,
-                &team.found_year,
-                &team.location,
-                &team.supporter_count,
-                &team.stadium_name,
-                &team.nickname,
-                &team.league_id,
-                &team.id,
-            ),
-        )?;
-        
+        self.update_row_values("Teams", "ID", &Value::Integer(team.id), &team_values(team))?;
         Ok(())
     }
     
@@ -268,23 +295,102 @@ impl Database {
             return Ok(0);
         }
         
+        // 全部写入成功才提交，审计日志在提交后写入
+        let updated_count = self.transaction(|| {
+            let mut entries = Vec::new();
+            for team in teams {
+                self.update_row_audited("Teams", "ID", &Value::Integer(team.id), &team_values(team), &mut entries)?;
+            }
+            self.write_audit(&entries);
+            Ok(teams.len())
+        })?;
+        info!("批量更新了 {} 个球队", updated_count);
+        
+        Ok(updated_count)
+    }
+
+    pub fn update_staff(&self, staff: &Staff) -> Result<()> {
+        self.update_row_values("Staff", "ID", &Value::Integer(staff.id), &staff_values(staff))?;
+        Ok(())
+    }
+    
+    pub fn update_staff_batch(&self, staff_list: &[Staff]) -> Result<usize> {
+        if staff_list.is_empty() {
+            return Ok(0);
+        }
+        
+        // 全部写入成功才提交，审计日志在提交后写入
+        let updated_count = self.transaction(|| {
+            let mut entries = Vec::new();
+            for staff in staff_list {
+                self.update_row_audited("Staff", "ID", &Value::Integer(staff.id), &staff_values(staff), &mut entries)?;
+            }
+            self.write_audit(&entries);
+            Ok(staff_list.len())
+        })?;
+        info!("批量更新了 {} 个员工", updated_count);
+        
+        Ok(updated_count)
+    }
+
+    /// 把员工作为新员工插入到指定球队，返回使用新ID写入的员工
+    pub fn copy_staff_to_team(&self, staff_list: &[Staff], team_id: i64) -> Result<Vec<Staff>> {
         let conn = self.conn.as_ref().ok_or(AppError::DatabaseNotLoaded)?;
+        let tx = conn.unchecked_transaction()?;
+        
+        // 参考数据库的员工ID在工作数据库中指向无关的员工，复制时按新员工插入
+        let first_id: i64 = tx.query_row("SELECT COALESCE(MAX(ID), 0) + 1 FROM Staff", [], |row| row.get(0))?;
+        let mut entries = Vec::new();
+        let mut copied = Vec::with_capacity(staff_list.len());
+        
+        for (staff, id) in staff_list.iter().zip(first_id..) {
+            let mut staff = staff.clone();
+            staff.id = id;
+            staff.team_id = team_id;
+            
+            tx.execute(
+                "INSERT INTO Staff (ID, Name, AbilityJSON, Fame, EmployedTeamID)
+                VALUES (?1, ?2, ?3, ?4, ?5)",
+                (&staff.id, &staff.name, &staff.ability_json, &staff.fame, &staff.team_id),
+            ).context(|| ErrorContext::new(t!("复制员工")).table("Staff").record(staff.id))?;
+            
+            let key = Value::Integer(staff.id);
+            let mut values = staff_values(&staff);
+            values.push(("EmployedTeamID".to_string(), Value::Integer(team_id)));
+            entries.extend(inserted_entries("Staff", &key, &values));
+            copied.push(staff);
+        }
+        
+        tx.commit()?;
+        // 提交成功后才写入日志，回滚的修改不会留下记录
+        self.write_audit(&entries);
+        info!("已复制 {} 个员工到球队 {}", copied.len(), team_id);
         
-        let mut updated_count = 0;
-        
-        for team in teams {
-            conn.execute(
-                "UPDATE Teams SET 
-                TeamName = ?1, 
-                TeamWealth = ?2, 
-                TeamFoundYear = ?3, 
-                TeamLocation = ?4, 
-                SupporterCount = ?5, 
-                StadiumName = ?6, 
-                Nickname = ?7,
-                BelongingLeague = ?8
-                WHERE ID = ?9",
+        Ok(copied)
+    }
+
+    /// 写入生成的球队和员工
+    ///
+    /// 生成数据的ID从1开始，写入前平移到数据库中现有的最大ID之后，员工的球队ID
+    /// 随之平移。全部写入成功才提交，返回写入后的数据。
+    pub fn insert_generated(&self, data: &GeneratedData) -> Result<GeneratedData> {
+        let conn = self.conn.as_ref().ok_or(AppError::DatabaseNotLoaded)?;
+        let tx = conn.unchecked_transaction()?;
+        
+        let team_offset: i64 = tx.query_row("SELECT COALESCE(MAX(ID), 0) FROM Teams", [], |row| row.get(0))?;
+        let staff_offset: i64 = tx.query_row("SELECT COALESCE(MAX(ID), 0) FROM Staff", [], |row| row.get(0))?;
+        
+        let mut inserted = data.clone();
+        let mut entries = Vec::new();
+        for team in &mut inserted.teams {
+            team.id += team_offset;
+            tx.execute(
+                "INSERT INTO Teams (
+                ID, TeamName, TeamWealth, TeamFoundYear, TeamLocation, 
+                SupporterCount, StadiumName, Nickname, BelongingLeague)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
                 (
+                    &team.id,
                     &team.name,
                     &team.wealth,
                     &team.found_year,
@@ -293,58 +399,197 @@ impl Database {
                     &team.stadium_name,
                     &team.nickname,
                     &team.league_id,
-                    &team.id,
                 ),
-            )?;
-            
-            updated_count += 1;
+            ).context(|| ErrorContext::new(t!("写入生成的球队")).table("Teams").record(team.id))?;
+            entries.extend(inserted_entries("Teams", &Value::Integer(team.id), &team_values(team)));
         }
         
-        info!("批量更新了 {} 个球队", updated_count);
+        for staff in &mut inserted.staff {
+            staff.id += staff_offset;
+            staff.team_id += team_offset;
+            tx.execute(
+                "INSERT INTO Staff (ID, Name, AbilityJSON, Fame, EmployedTeamID)
+                VALUES (?1, ?2, ?3, ?4, ?5)",
+                (&staff.id, &staff.name, &staff.ability_json, &staff.fame, &staff.team_id),
+            ).context(|| ErrorContext::new(t!("写入生成的员工")).table("Staff").record(staff.id))?;
+            let mut values = staff_values(staff);
+            values.push(("EmployedTeamID".to_string(), Value::Integer(staff.team_id)));
+            entries.extend(inserted_entries("Staff", &Value::Integer(staff.id), &values));
+        }
         
-        Ok(updated_count)
+        tx.commit()?;
+        // 提交成功后才写入日志，回滚的修改不会留下记录
+        self.write_audit(&entries);
+        info!("已生成 {} 个球队和 {} 个员工", inserted.teams.len(), inserted.staff.len());
+        
+        Ok(inserted)
     }
 
-    pub fn update_staff(&self, staff: &Staff) -> Result<()> {
+    /// 读取整张表，返回列名和每一行的原始值
+    pub fn load_table_rows(&self, table: &str) -> Result<(Vec<String>, Vec<Vec<Value>>)> {
         let conn = self.conn.as_ref().ok_or(AppError::DatabaseNotLoaded)?;
         
-        conn.execute(
-            "UPDATE Staff SET 
-            Name = ?1, 
-            AbilityJSON = ?2, 
-            Fame = ?3 
-            WHERE ID = ?4",
-            (&staff.name, &staff.ability_json, &staff.fame, &staff.id),
-        )?;
+        let mut stmt = conn.prepare(&format!("SELECT * FROM {}", quote_identifier(table)))?;
+        let columns: Vec<String> = stmt.column_names().into_iter().map(str::to_string).collect();
+        let column_count = columns.len();
         
-        Ok(())
+        let rows = stmt
+            .query_map([], |row| {
+                (0..column_count).map(|i| row.get::<_, Value>(i)).collect::<SqlResult<Vec<_>>>()
+            })?
+            .collect::<SqlResult<Vec<_>>>()?;
+        
+        Ok((columns, rows))
     }
-    
-    pub fn update_staff_batch(&self, staff_list: &[Staff]) -> Result<usize> {
-        if staff_list.is_empty() {
+
+    /// 按主键修改一行中的若干列并写入审计日志，返回修改的行数
+    pub fn update_row_values(&self, table: &str, key_column: &str, key: &Value, values: &[(String, Value)]) -> Result<usize> {
+        let mut entries = Vec::new();
+        let updated = self.update_row_audited(table, key_column, key, values, &mut entries)?;
+        self.write_audit(&entries);
+        Ok(updated)
+    }
+
+    // 读取修改前的值，执行修改，并把有变化的字段加入entries
+    fn update_row_audited(
+        &self,
+        table: &str,
+        key_column: &str,
+        key: &Value,
+        values: &[(String, Value)],
+        entries: &mut Vec<AuditEntry>,
+    ) -> Result<usize> {
+        let conn = self.conn.as_ref().ok_or(AppError::DatabaseNotLoaded)?;
+        if values.is_empty() {
             return Ok(0);
         }
         
-        let conn = self.conn.as_ref().ok_or(AppError::DatabaseNotLoaded)?;
+        let column_list: Vec<String> = values.iter().map(|(column, _)| quote_identifier(column)).collect();
+        let select_sql = format!(
+            "SELECT {} FROM {} WHERE {} = ?1",
+            column_list.join(", "),
+            quote_identifier(table),
+            quote_identifier(key_column)
+        );
+        let context = |sql: &str| ErrorContext::new(t!("修改记录")).table(table).record(format_value(key)).sql(sql);
+        let old_values: Option<Vec<Value>> = conn
+            .query_row(&select_sql, [key], |row| {
+                (0..values.len()).map(|i| row.get::<_, Value>(i)).collect::<SqlResult<Vec<_>>>()
+            })
+            .optional()
+            .context(|| context(&select_sql))?;
         
-        let mut updated_count = 0;
-        
-        for staff in staff_list {
-            conn.execute(
-                "UPDATE Staff SET 
-                Name = ?1, 
-                AbilityJSON = ?2, 
-                Fame = ?3 
-                WHERE ID = ?4",
-                (&staff.name, &staff.ability_json, &staff.fame, &staff.id),
-            )?;
-            
-            updated_count += 1;
+        let assignments: Vec<String> = column_list.iter()
+            .enumerate()
+            .map(|(i, column)| format!("{} = ?{}", column, i + 1))
+            .collect();
+        let sql = format!(
+            "UPDATE {} SET {} WHERE {} = ?{}",
+            quote_identifier(table),
+            assignments.join(", "),
+            quote_identifier(key_column),
+            values.len() + 1
+        );
+        
+        let params = values.iter().map(|(_, value)| value).chain(std::iter::once(key));
+        let updated = conn.execute(&sql, params_from_iter(params)).context(|| context(&sql))?;
+        
+        if let Some(old_values) = old_values {
+            for ((column, new_value), old_value) in values.iter().zip(&old_values) {
+                if audit::is_changed(old_value, new_value) {
+                    entries.push(AuditEntry::new(table, key, column, old_value, new_value));
+                }
+            }
         }
         
-        info!("批量更新了 {} 个员工", updated_count);
+        Ok(updated)
+    }
+
+    // 追加审计日志，失败时只记录错误
+    fn write_audit(&self, entries: &[AuditEntry]) {
+        if let Some(pending) = self.pending_audit.borrow_mut().as_mut() {
+            pending.extend_from_slice(entries);
+            return;
+        }
+        let Some(db_path) = &self.db_path else {
+            return;
+        };
+        if let Err(e) = audit::append(&audit::log_path(db_path), entries) {
+            error!("写入审计日志失败: {}", e);
+        }
+    }
+
+    /// 在一个事务中执行多个修改，任何一步失败都会回滚全部修改
+    ///
+    /// operation中只能使用不自行开启事务的方法，如update_row_values、insert_row_values和delete_row。
+    pub fn transaction<T>(&self, operation: impl FnOnce() -> Result<T>) -> Result<T> {
+        let conn = self.conn.as_ref().ok_or(AppError::DatabaseNotLoaded)?;
+        let tx = conn.unchecked_transaction()?;
         
-        Ok(updated_count)
+        self.pending_audit.replace(Some(Vec::new()));
+        let result = operation();
+        let entries = self.pending_audit.replace(None).unwrap_or_default();
+        
+        // 出错时tx在返回前被丢弃，事务自动回滚
+        let value = result?;
+        tx.commit()?;
+        self.write_audit(&entries);
+        Ok(value)
+    }
+
+    /// 插入一行完整的数据并写入审计日志，key_column用于在日志中记录这一行的主键
+    pub fn insert_row_values(&self, table: &str, key_column: &str, columns: &[String], values: &[Value]) -> Result<()> {
+        let conn = self.conn.as_ref().ok_or(AppError::DatabaseNotLoaded)?;
+        
+        let column_list: Vec<String> = columns.iter().map(|c| quote_identifier(c)).collect();
+        let placeholders: Vec<String> = (1..=values.len()).map(|i| format!("?{}", i)).collect();
+        let sql = format!(
+            "INSERT INTO {} ({}) VALUES ({})",
+            quote_identifier(table),
+            column_list.join(", "),
+            placeholders.join(", ")
+        );
+        
+        conn.execute(&sql, params_from_iter(values))
+            .context(|| ErrorContext::new(t!("插入记录")).table(table).sql(&sql))?;
+        
+        let key = columns.iter()
+            .position(|column| column == key_column)
+            .and_then(|i| values.get(i))
+            .cloned()
+            .unwrap_or(Value::Null);
+        let values: Vec<(String, Value)> = columns.iter().cloned().zip(values.iter().cloned()).collect();
+        self.write_audit(&inserted_entries(table, &key, &values));
+        Ok(())
+    }
+
+    /// 按主键删除一行并写入审计日志，返回删除的行数
+    pub fn delete_row(&self, table: &str, key_column: &str, key: &Value) -> Result<usize> {
+        let conn = self.conn.as_ref().ok_or(AppError::DatabaseNotLoaded)?;
+        let context = |sql: &str| ErrorContext::new(t!("删除记录")).table(table).record(format_value(key)).sql(sql);
+        
+        // 删除前读取整行，日志中记录每个字段被删除前的值
+        let select_sql = format!("SELECT * FROM {} WHERE {} = ?1", quote_identifier(table), quote_identifier(key_column));
+        let mut stmt = conn.prepare(&select_sql).context(|| context(&select_sql))?;
+        let columns: Vec<String> = stmt.column_names().into_iter().map(str::to_string).collect();
+        let old_values: Option<Vec<Value>> = stmt
+            .query_row([key], |row| {
+                (0..columns.len()).map(|i| row.get::<_, Value>(i)).collect::<SqlResult<Vec<_>>>()
+            })
+            .optional()
+            .context(|| context(&select_sql))?;
+        
+        let sql = format!("DELETE FROM {} WHERE {} = ?1", quote_identifier(table), quote_identifier(key_column));
+        let deleted = conn.execute(&sql, [key]).context(|| context(&sql))?;
+        
+        if let (true, Some(old_values)) = (deleted > 0, old_values) {
+            let entries: Vec<AuditEntry> = columns.iter()
+                .zip(&old_values)
+                .map(|(column, old_value)| AuditEntry::new(table, key, column, old_value, &Value::Null))
+                .collect();
+            self.write_audit(&entries);
+        }
+        Ok(deleted)
     }
 
     pub fn update_sponsor(&self, sponsor: &Sponsor) -> Result<()> {
@@ -484,4 +729,42 @@ impl Database {
         
         Ok(())
     }
-} 
\ No newline at end of file
+}
+
+// 表名和列名来自代码或数据库本身，加上双引号以支持特殊字符
+fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+// 编辑器修改的球队列及其新值
+fn team_values(team: &Team) -> Vec<(String, Value)> {
+    column_values(vec![
+        ("TeamName", Value::Text(team.name.clone())),
+        ("TeamWealth", Value::Integer(team.wealth)),
+        ("TeamFoundYear", Value::Integer(team.found_year)),
+        ("TeamLocation", Value::Text(team.location.clone())),
+        ("SupporterCount", Value::Integer(team.supporter_count)),
+        ("StadiumName", Value::Text(team.stadium_name.clone())),
+        ("Nickname", Value::Text(team.nickname.clone())),
+        ("BelongingLeague", Value::Integer(team.league_id)),
+    ])
+}
+
+// 新插入一行时每个字段的审计记录，修改前的值为空
+fn inserted_entries(table: &str, key: &Value, values: &[(String, Value)]) -> Vec<AuditEntry> {
+    values.iter()
+        .map(|(column, value)| AuditEntry::new(table, key, column, &Value::Null, value))
+        .collect()
+}
+
+fn staff_values(staff: &Staff) -> Vec<(String, Value)> {
+    column_values(vec![
+        ("Name", Value::Text(staff.name.clone())),
+        ("AbilityJSON", Value::Text(staff.ability_json.clone())),
+        ("Fame", Value::Integer(staff.fame)),
+    ])
+}
+
+fn column_values(values: Vec<(&str, Value)>) -> Vec<(String, Value)> {
+    values.into_iter().map(|(column, value)| (column.to_string(), value)).collect()
+}
@@ -1,4 +1,9 @@
 pub mod team;
 pub mod staff;
-pub mod database; 
-pub mod sponsor; 
\ No newline at end of file
+pub mod database;
+pub mod worker;
+pub mod team_filter;
+pub mod sponsor;
+pub mod snapshot;
+pub mod audit;
+pub mod conflict; 
\ No newline at end of file
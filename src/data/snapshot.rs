@@ -0,0 +1,296 @@
+// 打开数据库时的快照
+//
+// 打开数据库后立即把球队、员工、赞助商和足协表的全部行读入内存，包括界面中没有
+// 显示的列。之后可以把当前数据与快照逐行、逐列对比，并把单行或单个字段还原为
+// 打开时的值。行按主键匹配，新增的行还原时删除，删除的行还原时重新插入。
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Local};
+use log::{error, info};
+use rusqlite::types::Value;
+
+use crate::data::database::Database;
+use crate::error::Result;
+
+/// 参与快照的表
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SnapshotTable {
+    Teams,
+    Staff,
+    Sponsor,
+    FA,
+}
+
+impl SnapshotTable {
+    pub const ALL: [SnapshotTable; 4] = [SnapshotTable::Teams, SnapshotTable::Staff, SnapshotTable::Sponsor, SnapshotTable::FA];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SnapshotTable::Teams => t!("球队"),
+            SnapshotTable::Staff => t!("员工"),
+            SnapshotTable::Sponsor => t!("赞助商"),
+            SnapshotTable::FA => t!("足协"),
+        }
+    }
+
+    pub fn table_name(&self) -> &'static str {
+        match self {
+            SnapshotTable::Teams => "Teams",
+            SnapshotTable::Staff => "Staff",
+            SnapshotTable::Sponsor => "Sponsor",
+            SnapshotTable::FA => "FA",
+        }
+    }
+
+    /// 用于匹配行的主键列
+    pub fn key_column(&self) -> &'static str {
+        match self {
+            SnapshotTable::Sponsor => "SponsorName",
+            _ => "ID",
+        }
+    }
+
+    /// 显示行名称的列
+    fn label_column(&self) -> &'static str {
+        match self {
+            SnapshotTable::Teams => "TeamName",
+            SnapshotTable::Staff => "Name",
+            SnapshotTable::Sponsor => "SponsorName",
+            SnapshotTable::FA => "Title",
+        }
+    }
+}
+
+// 一张表在某个时刻的全部数据
+struct TableData {
+    columns: Vec<String>,
+    // 以主键的显示文本为键，保证比较结果的顺序稳定
+    rows: BTreeMap<String, Vec<Value>>,
+}
+
+impl TableData {
+    fn load(database: &Database, table: SnapshotTable) -> Result<Self> {
+        let (columns, rows) = database.load_table_rows(table.table_name())?;
+        let key_index = columns.iter().position(|c| c == table.key_column()).unwrap_or(0);
+        let rows = rows.into_iter()
+            .map(|row| (key_text(&row[key_index]), row))
+            .collect();
+        Ok(TableData { columns, rows })
+    }
+
+    fn column_index(&self, column: &str) -> Option<usize> {
+        self.columns.iter().position(|c| c == column)
+    }
+}
+
+/// 行的变化类型
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Modified,
+}
+
+impl ChangeKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChangeKind::Added => t!("新增"),
+            ChangeKind::Removed => t!("删除"),
+            ChangeKind::Modified => t!("修改"),
+        }
+    }
+}
+
+/// 单个字段的变化，新增或删除的行中不存在的一方为Null
+#[derive(Debug, Clone)]
+pub struct FieldChange {
+    pub column: String,
+    pub original: Value,
+    pub current: Value,
+}
+
+/// 一行数据的变化
+#[derive(Debug, Clone)]
+pub struct RowChange {
+    pub table: SnapshotTable,
+    pub key: Value,
+    pub label: String,
+    pub kind: ChangeKind,
+    pub fields: Vec<FieldChange>,
+}
+
+/// 还原操作
+#[derive(Debug, Clone)]
+pub enum Revert {
+    Row { table: SnapshotTable, key: Value },
+    Field { table: SnapshotTable, key: Value, column: String },
+}
+
+pub struct Snapshot {
+    pub taken_at: DateTime<Local>,
+    tables: Vec<(SnapshotTable, TableData)>,
+}
+
+impl Snapshot {
+    /// 读取所有表的当前数据，某张表读取失败时跳过该表
+    pub fn capture(database: &Database) -> Self {
+        let tables = SnapshotTable::ALL.iter()
+            .filter_map(|&table| match TableData::load(database, table) {
+                Ok(data) => Some((table, data)),
+                Err(e) => {
+                    error!("创建{}表快照失败: {}", table.table_name(), e);
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let row_count: usize = tables.iter().map(|(_, data)| data.rows.len()).sum();
+        info!("已创建数据库快照: {} 张表, {} 行", tables.len(), row_count);
+
+        Snapshot { taken_at: Local::now(), tables }
+    }
+
+    /// 不包含任何表的快照，用于不需要对比修改的参考数据库
+    pub fn empty() -> Self {
+        Snapshot { taken_at: Local::now(), tables: Vec::new() }
+    }
+
+    /// 对比当前数据与快照
+    pub fn diff(&self, database: &Database) -> Result<Vec<RowChange>> {
+        let mut changes = Vec::new();
+
+        for (table, original) in &self.tables {
+            let current = TableData::load(database, *table)?;
+            let label_index = original.column_index(table.label_column());
+
+            for (key, original_row) in &original.rows {
+                match current.rows.get(key) {
+                    Some(current_row) => {
+                        let fields = diff_fields(original, original_row, &current, current_row);
+                        if !fields.is_empty() {
+                            changes.push(row_change(*table, current_row, &current, label_index, ChangeKind::Modified, fields));
+                        }
+                    },
+                    None => {
+                        let fields = original.columns.iter()
+                            .zip(original_row)
+                            .map(|(column, value)| FieldChange {
+                                column: column.clone(),
+                                original: value.clone(),
+                                current: Value::Null,
+                            })
+                            .collect();
+                        changes.push(row_change(*table, original_row, original, label_index, ChangeKind::Removed, fields));
+                    },
+                }
+            }
+
+            for (key, current_row) in &current.rows {
+                if !original.rows.contains_key(key) {
+                    let fields = current.columns.iter()
+                        .zip(current_row)
+                        .map(|(column, value)| FieldChange {
+                            column: column.clone(),
+                            original: Value::Null,
+                            current: value.clone(),
+                        })
+                        .collect();
+                    let label_index = current.column_index(table.label_column());
+                    changes.push(row_change(*table, current_row, &current, label_index, ChangeKind::Added, fields));
+                }
+            }
+        }
+
+        Ok(changes)
+    }
+
+    /// 把一行或一个字段还原为快照中的值，返回修改的行数
+    pub fn revert(&self, database: &Database, revert: &Revert) -> Result<usize> {
+        let (table, key) = match revert {
+            Revert::Row { table, key } | Revert::Field { table, key, .. } => (*table, key),
+        };
+        let Some(original) = self.table(table) else {
+            return Ok(0);
+        };
+        let original_row = original.rows.get(&key_text(key));
+
+        match (revert, original_row) {
+            // 快照中不存在的行是新增的，还原即删除
+            (Revert::Row { .. }, None) => database.delete_row(table.table_name(), table.key_column(), key),
+            (Revert::Row { .. }, Some(row)) => {
+                let values: Vec<(String, Value)> = original.columns.iter().cloned().zip(row.iter().cloned()).collect();
+                let updated = database.update_row_values(table.table_name(), table.key_column(), key, &values)?;
+                if updated > 0 {
+                    return Ok(updated);
+                }
+                // 当前数据中已经没有这一行，重新插入
+                database.insert_row_values(table.table_name(), table.key_column(), &original.columns, row)?;
+                Ok(1)
+            },
+            (Revert::Field { column, .. }, Some(row)) => {
+                let Some(index) = original.column_index(column) else {
+                    return Ok(0);
+                };
+                let values = [(column.clone(), row[index].clone())];
+                database.update_row_values(table.table_name(), table.key_column(), key, &values)
+            },
+            (Revert::Field { .. }, None) => Ok(0),
+        }
+    }
+
+    fn table(&self, table: SnapshotTable) -> Option<&TableData> {
+        self.tables.iter().find(|(t, _)| *t == table).map(|(_, data)| data)
+    }
+}
+
+fn diff_fields(original: &TableData, original_row: &[Value], current: &TableData, current_row: &[Value]) -> Vec<FieldChange> {
+    original.columns.iter()
+        .enumerate()
+        .filter_map(|(i, column)| {
+            let current_value = current.column_index(column).map_or(Value::Null, |j| current_row[j].clone());
+            (original_row[i] != current_value).then(|| FieldChange {
+                column: column.clone(),
+                original: original_row[i].clone(),
+                current: current_value,
+            })
+        })
+        .collect()
+}
+
+fn row_change(
+    table: SnapshotTable,
+    row: &[Value],
+    data: &TableData,
+    label_index: Option<usize>,
+    kind: ChangeKind,
+    fields: Vec<FieldChange>,
+) -> RowChange {
+    let key_index = data.column_index(table.key_column()).unwrap_or(0);
+    RowChange {
+        table,
+        key: row[key_index].clone(),
+        label: label_index.and_then(|i| row.get(i)).map(format_value).unwrap_or_default(),
+        kind,
+        fields,
+    }
+}
+
+// 主键的比较文本，整数和文本主键都能唯一表示
+fn key_text(value: &Value) -> String {
+    match value {
+        Value::Integer(i) => format!("{:020}", i),
+        other => format_value(other),
+    }
+}
+
+/// 字段值的显示文本
+pub fn format_value(value: &Value) -> String {
+    match value {
+        Value::Null => t!("(空)").to_string(),
+        Value::Integer(i) => i.to_string(),
+        Value::Real(f) => f.to_string(),
+        Value::Text(text) => text.clone(),
+        Value::Blob(bytes) => t!("<{} 字节>", bytes.len()),
+    }
+}
@@ -5,7 +5,7 @@ use std::fmt;
 
 use crate::error::{AppError, Result};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Staff {
     pub id: i64,
     pub name: String,
@@ -1,8 +1,9 @@
 use rusqlite::Row;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fmt;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Team {
     pub id: i64,
     pub name: String,
@@ -46,6 +47,73 @@ impl Team {
     }
 }
 
+/// 球队的可编辑字段（不含ID）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TeamField {
+    Name,
+    Wealth,
+    FoundYear,
+    Location,
+    SupporterCount,
+    StadiumName,
+    Nickname,
+    LeagueId,
+}
+
+impl TeamField {
+    pub const ALL: [TeamField; 8] = [
+        TeamField::Name,
+        TeamField::Wealth,
+        TeamField::FoundYear,
+        TeamField::Location,
+        TeamField::SupporterCount,
+        TeamField::StadiumName,
+        TeamField::Nickname,
+        TeamField::LeagueId,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TeamField::Name => t!("球队名称"),
+            TeamField::Wealth => t!("球队财富"),
+            TeamField::FoundYear => t!("成立年份"),
+            TeamField::Location => t!("所在地区"),
+            TeamField::SupporterCount => t!("支持者数量"),
+            TeamField::StadiumName => t!("主场名称"),
+            TeamField::Nickname => t!("球队昵称"),
+            TeamField::LeagueId => t!("联赛ID"),
+        }
+    }
+
+    /// 获取字段值的显示文本
+    pub fn value_string(&self, team: &Team) -> String {
+        match self {
+            TeamField::Name => team.name.clone(),
+            TeamField::Wealth => team.wealth.to_string(),
+            TeamField::FoundYear => team.found_year.to_string(),
+            TeamField::Location => team.location.clone(),
+            TeamField::SupporterCount => team.supporter_count.to_string(),
+            TeamField::StadiumName => team.stadium_name.clone(),
+            TeamField::Nickname => team.nickname.clone(),
+            TeamField::LeagueId => team.league_id.to_string(),
+        }
+    }
+
+    /// 将另一个球队的字段值复制到目标球队
+    pub fn copy_value(&self, target: &mut Team, source: &Team) {
+        match self {
+            TeamField::Name => target.name = source.name.clone(),
+            TeamField::Wealth => target.wealth = source.wealth,
+            TeamField::FoundYear => target.found_year = source.found_year,
+            TeamField::Location => target.location = source.location.clone(),
+            TeamField::SupporterCount => target.supporter_count = source.supporter_count,
+            TeamField::StadiumName => target.stadium_name = source.stadium_name.clone(),
+            TeamField::Nickname => target.nickname = source.nickname.clone(),
+            TeamField::LeagueId => target.league_id = source.league_id,
+        }
+    }
+}
+
 impl fmt::Display for Team {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{} (ID: {})", self.name, self.id)
@@ -56,4 +124,46 @@ impl fmt::Display for Team {
 pub struct League {
     pub id: i64,
     pub name: String,
-} 
\ No newline at end of file
+}
+
+/// 数据库中的全部联赛，按ID查找名称
+///
+/// 加载数据后分发给各个视图，筛选、图表和导出都通过它显示联赛名称。
+#[derive(Debug, Clone, Default)]
+pub struct Leagues {
+    names: HashMap<i64, String>,
+}
+
+impl Leagues {
+    pub fn new(leagues: Vec<League>) -> Self {
+        Leagues {
+            names: leagues.into_iter().map(|league| (league.id, league.name)).collect(),
+        }
+    }
+
+    pub fn get(&self, league_id: i64) -> Option<&str> {
+        self.names.get(&league_id).map(String::as_str)
+    }
+
+    /// 联赛名称，数据库中没有该联赛时显示为"联赛 ID"
+    pub fn name(&self, league_id: i64) -> String {
+        self.get(league_id)
+            .map(str::to_string)
+            .unwrap_or_else(|| t!("联赛 {}", league_id))
+    }
+
+    /// 鼠标悬停时显示的联赛ID
+    pub fn hover_text(&self, league_id: i64) -> String {
+        t!("联赛ID: {}", league_id)
+    }
+
+    /// 全部联赛ID
+    pub fn ids(&self) -> Vec<i64> {
+        self.names.keys().copied().collect()
+    }
+
+    /// 按联赛名称排序，名称相同时按ID排序
+    pub fn sort_by_name(&self, league_ids: &mut [i64]) {
+        league_ids.sort_by_cached_key(|&id| (self.name(id), id));
+    }
+}
@@ -0,0 +1,340 @@
+// 球队筛选
+//
+// 筛选结果只保存球队在列表中的下标，不复制球队数据。每个球队的小写搜索键在
+// 加载时预先计算，输入搜索词时不再为每个球队分配字符串。搜索词在上一次的基础上
+// 追加字符时，只需要在上一次的结果中继续筛选。
+
+use crate::data::team::{Leagues, Team};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum FilterField {
+    Name,
+    Location,
+    League,
+    All,
+}
+
+impl FilterField {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FilterField::Name => t!("球队名称"),
+            FilterField::Location => t!("地区"),
+            FilterField::League => t!("联赛"),
+            FilterField::All => t!("全部字段"),
+        }
+    }
+}
+
+// 单个球队预先计算的小写搜索键
+struct SearchKeys {
+    name: String,
+    location: String,
+    league: String,
+    all: String,
+}
+
+impl SearchKeys {
+    fn new(team: &Team, leagues: &Leagues) -> Self {
+        let league = leagues.name(team.league_id).to_lowercase();
+        SearchKeys {
+            name: team.name.to_lowercase(),
+            location: team.location.to_lowercase(),
+            all: format!("{}{}", team.search_string().to_lowercase(), league),
+            league,
+        }
+    }
+
+    fn get(&self, field: FilterField) -> &str {
+        match field {
+            FilterField::Name => &self.name,
+            FilterField::Location => &self.location,
+            FilterField::League => &self.league,
+            FilterField::All => &self.all,
+        }
+    }
+}
+
+/// 与球队列表一一对应的搜索键
+pub struct TeamSearchIndex {
+    keys: Vec<SearchKeys>,
+}
+
+impl TeamSearchIndex {
+    pub fn new(teams: &[Team], leagues: &Leagues) -> Self {
+        TeamSearchIndex {
+            keys: teams.iter().map(|team| SearchKeys::new(team, leagues)).collect(),
+        }
+    }
+
+    /// 球队数据修改后更新对应的搜索键
+    pub fn update(&mut self, index: usize, team: &Team, leagues: &Leagues) {
+        if let Some(keys) = self.keys.get_mut(index) {
+            *keys = SearchKeys::new(team, leagues);
+        }
+    }
+
+    /// 下标为index的球队是否包含搜索词，search_term需为小写
+    pub fn contains(&self, index: usize, field: FilterField, search_term: &str) -> bool {
+        self.keys
+            .get(index)
+            .is_some_and(|keys| keys.get(field).contains(search_term))
+    }
+}
+
+/// 筛选条件
+#[derive(Debug, PartialEq, Clone)]
+pub struct TeamFilter {
+    /// 小写的搜索词
+    pub search_term: String,
+    pub field: FilterField,
+    pub location: Option<String>,
+    pub league_id: Option<i64>,
+    pub min_wealth: Option<i64>,
+    pub max_wealth: Option<i64>,
+    pub min_year: Option<i64>,
+    pub max_year: Option<i64>,
+    /// 只保留这些球队（已排序的球队ID），用于从图表下钻
+    pub team_ids: Option<Vec<i64>>,
+}
+
+impl TeamFilter {
+    pub fn new() -> Self {
+        TeamFilter {
+            search_term: String::new(),
+            field: FilterField::All,
+            location: None,
+            league_id: None,
+            min_wealth: None,
+            max_wealth: None,
+            min_year: None,
+            max_year: None,
+            team_ids: None,
+        }
+    }
+
+    /// 设置搜索词，会转换为小写
+    pub fn set_search_text(&mut self, text: &str) {
+        self.search_term = text.trim().to_lowercase();
+    }
+
+    fn matches(&self, index: usize, team: &Team, search_index: &TeamSearchIndex) -> bool {
+        (self.search_term.is_empty() || search_index.contains(index, self.field, &self.search_term))
+            && self.location.as_ref().is_none_or(|location| &team.location == location)
+            && self.league_id.is_none_or(|league_id| team.league_id == league_id)
+            && self.min_wealth.is_none_or(|min| team.wealth >= min)
+            && self.max_wealth.is_none_or(|max| team.wealth <= max)
+            && self.min_year.is_none_or(|min| team.found_year >= min)
+            && self.max_year.is_none_or(|max| team.found_year <= max)
+            && self.team_ids.as_ref().is_none_or(|ids| ids.binary_search(&team.id).is_ok())
+    }
+
+    // 只有搜索词变长、其他条件不变时，新结果一定是旧结果的子集
+    fn narrows(&self, previous: &TeamFilter) -> bool {
+        self.search_term.starts_with(&previous.search_term)
+            && self.field == previous.field
+            && self.location == previous.location
+            && self.league_id == previous.league_id
+            && self.min_wealth == previous.min_wealth
+            && self.max_wealth == previous.max_wealth
+            && self.min_year == previous.min_year
+            && self.max_year == previous.max_year
+            && self.team_ids == previous.team_ids
+    }
+}
+
+impl Default for TeamFilter {
+    fn default() -> Self {
+        TeamFilter::new()
+    }
+}
+
+/// 保存筛选结果（球队下标），并在可能时增量筛选
+pub struct TeamFilterState {
+    index: TeamSearchIndex,
+    indices: Vec<usize>,
+    last_filter: Option<TeamFilter>,
+}
+
+impl TeamFilterState {
+    pub fn new() -> Self {
+        TeamFilterState {
+            index: TeamSearchIndex::new(&[], &Leagues::default()),
+            indices: Vec::new(),
+            last_filter: None,
+        }
+    }
+
+    /// 球队列表或联赛名称变化后重建搜索键，需要重新调用apply
+    pub fn rebuild(&mut self, teams: &[Team], leagues: &Leagues) {
+        self.index = TeamSearchIndex::new(teams, leagues);
+        self.indices.clear();
+        self.last_filter = None;
+    }
+
+    /// 单个球队修改后更新搜索键，已有的筛选结果保持不变
+    pub fn update_team(&mut self, index: usize, team: &Team, leagues: &Leagues) {
+        self.index.update(index, team, leagues);
+        // 修改后的数据可能不再满足旧条件，下次筛选时完整重新计算
+        self.last_filter = None;
+    }
+
+    /// 应用筛选条件，返回符合条件的球队下标
+    pub fn apply(&mut self, teams: &[Team], filter: &TeamFilter) -> &[usize] {
+        if self.last_filter.as_ref() == Some(filter) {
+            return &self.indices;
+        }
+
+        let incremental = self.last_filter.as_ref().is_some_and(|last| filter.narrows(last));
+        let index = &self.index;
+
+        if incremental {
+            self.indices.retain(|&i| filter.matches(i, &teams[i], index));
+        } else {
+            self.indices.clear();
+            self.indices.extend(
+                teams.iter()
+                    .enumerate()
+                    .filter(|(i, team)| filter.matches(*i, team, index))
+                    .map(|(i, _)| i)
+            );
+        }
+
+        self.last_filter = Some(filter.clone());
+        &self.indices
+    }
+
+    pub fn indices(&self) -> &[usize] {
+        &self.indices
+    }
+}
+
+impl Default for TeamFilterState {
+    fn default() -> Self {
+        TeamFilterState::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::team::League;
+
+    const NAMES: [&str; 6] = ["Arsenal", "Ardent Rovers", "北京国安", "Real Madrid", "Aston Villa", "北京人和"];
+    const LOCATIONS: [&str; 3] = ["London", "北京", "Madrid"];
+
+    fn teams() -> Vec<Team> {
+        (0..60)
+            .map(|i| Team {
+                id: i + 1,
+                name: format!("{} {}", NAMES[i as usize % NAMES.len()], i),
+                wealth: (i * 7919) % 1000,
+                found_year: 1880 + (i * 13) % 130,
+                location: LOCATIONS[i as usize % LOCATIONS.len()].to_string(),
+                supporter_count: i * 100,
+                stadium_name: String::new(),
+                nickname: String::new(),
+                league_id: i % 4,
+            })
+            .collect()
+    }
+
+    fn leagues() -> Leagues {
+        Leagues::new(vec![
+            League { id: 0, name: "Premier League".to_string() },
+            League { id: 1, name: "中超".to_string() },
+            League { id: 2, name: "La Liga".to_string() },
+        ])
+    }
+
+    // 不使用缓存，完整筛选一次
+    fn full_filter(teams: &[Team], leagues: &Leagues, filter: &TeamFilter) -> Vec<usize> {
+        let mut state = TeamFilterState::new();
+        state.rebuild(teams, leagues);
+        state.apply(teams, filter).to_vec()
+    }
+
+    fn search(field: FilterField, text: &str) -> TeamFilter {
+        let mut filter = TeamFilter::new();
+        filter.field = field;
+        filter.set_search_text(text);
+        filter
+    }
+
+    #[test]
+    fn incremental_filter_matches_full_filter() {
+        let teams = teams();
+        let leagues = leagues();
+        let mut state = TeamFilterState::new();
+        state.rebuild(&teams, &leagues);
+
+        let mut steps = Vec::new();
+        // 逐字输入，然后逐字删除
+        for text in ["a", "ar", "ars", "arse", "ars", "ar", "a", "", "北", "北京", "北京国", "北"] {
+            steps.push(search(FilterField::All, text));
+        }
+        // 切换搜索字段
+        for field in [FilterField::Name, FilterField::Location, FilterField::League, FilterField::All] {
+            steps.push(search(field, "a"));
+            steps.push(search(field, "ar"));
+        }
+        // 修改其他条件
+        let mut filter = search(FilterField::Name, "a");
+        filter.location = Some("London".to_string());
+        steps.push(filter.clone());
+        filter.set_search_text("ar");
+        steps.push(filter.clone());
+        filter.location = None;
+        filter.league_id = Some(1);
+        steps.push(filter.clone());
+        filter.min_wealth = Some(200);
+        filter.max_wealth = Some(800);
+        steps.push(filter.clone());
+        filter.set_search_text("a");
+        steps.push(filter.clone());
+        filter.team_ids = Some(vec![2, 3, 5, 8, 13, 21, 34, 55]);
+        steps.push(filter.clone());
+        filter.team_ids = None;
+        filter.league_id = None;
+        filter.min_year = Some(1950);
+        steps.push(filter.clone());
+
+        for filter in &steps {
+            let incremental = state.apply(&teams, filter).to_vec();
+            assert_eq!(incremental, full_filter(&teams, &leagues, filter), "{:?}", filter);
+        }
+    }
+
+    #[test]
+    fn update_team_invalidates_narrowing() {
+        let mut teams = teams();
+        let leagues = leagues();
+        let mut state = TeamFilterState::new();
+        state.rebuild(&teams, &leagues);
+
+        state.apply(&teams, &search(FilterField::Name, "ar"));
+
+        // 修改后的球队名称满足更长的搜索词，不能只在旧结果中继续筛选
+        let index = teams.iter().position(|t| t.name.starts_with("北京")).unwrap();
+        teams[index].name = "Arsenal Reserves".to_string();
+        state.update_team(index, &teams[index], &leagues);
+
+        let filter = search(FilterField::Name, "ars");
+        let result = state.apply(&teams, &filter).to_vec();
+        assert!(result.contains(&index));
+        assert_eq!(result, full_filter(&teams, &leagues, &filter));
+    }
+
+    #[test]
+    fn league_search_uses_league_names() {
+        let teams = teams();
+        let leagues = leagues();
+
+        let result = full_filter(&teams, &leagues, &search(FilterField::League, "中超"));
+        assert!(!result.is_empty());
+        assert!(result.iter().all(|&i| teams[i].league_id == 1));
+
+        let result = full_filter(&teams, &leagues, &search(FilterField::League, "premier"));
+        assert!(result.iter().all(|&i| teams[i].league_id == 0));
+        assert_eq!(result.len(), teams.iter().filter(|t| t.league_id == 0).count());
+    }
+}
@@ -0,0 +1,400 @@
+// 后台数据库线程
+//
+// 加载和写入在独立线程中执行，界面线程通过通道发送命令并在每帧接收结果，
+// 避免大数据库加载或保存时窗口卡住。后台线程使用自己的数据库连接，
+// 命令按发送顺序依次执行。
+
+use std::path::Path;
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use log::{error, info};
+
+use crate::data::conflict::{KnownState, SaveConflict};
+use crate::data::database::Database;
+use crate::data::snapshot::{Revert, RowChange, Snapshot};
+use crate::data::sponsor::Sponsor;
+use crate::data::staff::Staff;
+use crate::data::team::{Leagues, Team};
+use crate::error::{AppError, Result};
+use crate::generator::GeneratedData;
+
+// 数据库被占用时的最大尝试次数和每次重试前的等待时间（按次数递增）
+const BUSY_RETRIES: u32 = 4;
+const BUSY_RETRY_DELAY: Duration = Duration::from_millis(500);
+
+/// 触发球队保存的来源，用于在保存完成后显示对应的提示
+#[derive(Debug, Clone)]
+pub enum SaveOrigin {
+    Manual,
+    AutoSave,
+    BulkEdit,
+    /// 从参考数据库复制字段，附带字段名称
+    CopyFromReference(String),
+    /// 联赛经济平衡，附带联赛名称
+    Balance(String),
+}
+
+/// 发送给后台线程的命令
+pub enum DbCommand {
+    LoadAll,
+    SaveTeams(Vec<Team>, SaveOrigin),
+    SaveStaff(Staff),
+    CopyStaffToTeam(Vec<Staff>, i64),
+    InsertGenerated(GeneratedData),
+    /// 对比当前数据与打开数据库时的快照
+    DiffSnapshot,
+    RevertChanges(Vec<Revert>),
+    /// 忽略外部修改冲突，直接执行保存命令
+    Overwrite(Box<DbCommand>),
+    /// 检查数据库是否被其他程序修改过
+    CheckExternalChanges,
+}
+
+impl DbCommand {
+    /// 是否会修改数据库，只读模式下不允许发送
+    pub fn is_write(&self) -> bool {
+        !matches!(self, DbCommand::LoadAll | DbCommand::DiffSnapshot | DbCommand::CheckExternalChanges)
+    }
+}
+
+/// 一次完整加载的数据
+pub struct LoadedData {
+    pub teams: Vec<Team>,
+    pub leagues: Leagues,
+    pub staff: Vec<Staff>,
+    pub sponsors: Vec<Sponsor>,
+}
+
+/// 后台线程返回给界面的事件
+pub enum DbEvent {
+    Progress(String, f32),
+    Loaded(LoadedData),
+    TeamsSaved(Vec<Team>, SaveOrigin),
+    StaffSaved(Staff),
+    /// 复制员工完成，附带写入的新员工
+    StaffCopied(Vec<Staff>),
+    /// 生成的数据已写入，附带平移后的ID
+    Generated(GeneratedData),
+    /// 与快照对比的结果，附带快照的创建时间
+    SnapshotDiff(Vec<RowChange>, String),
+    /// 还原完成，附带修改的行数
+    Reverted(usize),
+    /// 保存的数据在编辑器之外被修改过，附带未执行的命令
+    Conflict(Vec<SaveConflict>, DbCommand),
+    /// 自上次加载以来数据库是否被其他程序修改过
+    ExternalChanges(bool),
+    Failed(String, AppError),
+}
+
+/// 后台线程在切换只读/编辑模式时需要保留的状态
+pub struct WorkerState {
+    /// 打开数据库时的原始数据
+    snapshot: Snapshot,
+    known: KnownState,
+}
+
+pub struct DbWorker {
+    sender: Option<Sender<DbCommand>>,
+    receiver: Receiver<DbEvent>,
+    handle: Option<JoinHandle<WorkerState>>,
+    pending: usize,
+    progress: Option<(String, f32)>,
+}
+
+impl DbWorker {
+    /// 为指定数据库启动后台线程，连接失败时直接返回错误
+    ///
+    /// read_only为true时使用SQLite的只读标志打开，任何写入都会失败。
+    /// previous为同一数据库上一个后台线程留下的状态，为None时重新创建快照。
+    pub fn start(path: &Path, read_only: bool, previous: Option<WorkerState>, ctx: egui::Context) -> Result<Self> {
+        let mut database = Database::new();
+        if read_only {
+            database.connect_read_only(path)?;
+        } else {
+            database.connect(path)?;
+        }
+
+        Self::spawn(database, ctx, move |database| match previous {
+            Some(mut state) => {
+                // data_version只在同一个连接内可比较，换用新连接的当前值
+                if let Ok(version) = database.data_version() {
+                    state.known.data_version = version;
+                }
+                state
+            },
+            // 在执行任何命令之前保存数据库的原始状态
+            None => WorkerState {
+                snapshot: Snapshot::capture(database),
+                known: KnownState::new(),
+            },
+        })
+    }
+
+    /// 为参考数据库启动只读后台线程，只用于加载数据，不创建快照
+    pub fn start_reference(path: &Path, ctx: egui::Context) -> Result<Self> {
+        let mut database = Database::new();
+        database.connect_read_only(path)?;
+
+        Self::spawn(database, ctx, |_| WorkerState {
+            snapshot: Snapshot::empty(),
+            known: KnownState::new(),
+        })
+    }
+
+    fn spawn(
+        database: Database,
+        ctx: egui::Context,
+        initial_state: impl FnOnce(&Database) -> WorkerState + Send + 'static,
+    ) -> Result<Self> {
+        let (command_tx, command_rx) = mpsc::channel::<DbCommand>();
+        let (event_tx, event_rx) = mpsc::channel::<DbEvent>();
+
+        let handle = thread::Builder::new()
+            .name("db-worker".to_string())
+            .spawn(move || {
+                let send = |event: DbEvent| {
+                    // 界面已关闭时忽略发送失败
+                    let _ = event_tx.send(event);
+                    ctx.request_repaint();
+                };
+
+                let mut state = initial_state(&database);
+
+                for command in command_rx {
+                    run_command(&database, &state.snapshot, &mut state.known, command, &send);
+                }
+
+                info!("数据库后台线程已退出");
+                state
+            })?;
+
+        Ok(DbWorker {
+            sender: Some(command_tx),
+            receiver: event_rx,
+            handle: Some(handle),
+            pending: 0,
+            progress: None,
+        })
+    }
+
+    pub fn send(&mut self, command: DbCommand) {
+        if let Some(sender) = &self.sender {
+            if sender.send(command).is_ok() {
+                self.pending += 1;
+            } else {
+                error!("数据库后台线程已停止，无法发送命令");
+            }
+        }
+    }
+
+    /// 取出一个已完成的事件，没有事件时返回None
+    pub fn try_recv(&mut self) -> Option<DbEvent> {
+        match self.receiver.try_recv() {
+            Ok(event) => {
+                match &event {
+                    DbEvent::Progress(message, fraction) => {
+                        self.progress = Some((message.clone(), *fraction));
+                    },
+                    // 其余事件都表示一个命令执行结束
+                    _ => {
+                        self.pending = self.pending.saturating_sub(1);
+                        if self.pending == 0 {
+                            self.progress = None;
+                        }
+                    },
+                }
+                Some(event)
+            },
+            Err(TryRecvError::Empty) | Err(TryRecvError::Disconnected) => None,
+        }
+    }
+
+    /// 是否还有未完成的命令
+    pub fn is_busy(&self) -> bool {
+        self.pending > 0
+    }
+
+    /// 当前命令的进度说明和完成比例
+    pub fn progress(&self) -> Option<(&str, f32)> {
+        self.progress.as_ref().map(|(message, fraction)| (message.as_str(), *fraction))
+    }
+
+    /// 等待剩余命令执行完毕后停止线程，返回可交给下一个线程的状态
+    pub fn stop(mut self) -> Option<WorkerState> {
+        self.join()
+    }
+
+    fn join(&mut self) -> Option<WorkerState> {
+        // 关闭通道后线程会在处理完剩余命令后退出
+        self.sender = None;
+        let handle = self.handle.take()?;
+        match handle.join() {
+            Ok(state) => Some(state),
+            Err(_) => {
+                error!("数据库后台线程异常退出");
+                None
+            },
+        }
+    }
+}
+
+impl Drop for DbWorker {
+    fn drop(&mut self) {
+        self.join();
+    }
+}
+
+fn run_command(database: &Database, snapshot: &Snapshot, known: &mut KnownState, command: DbCommand, send: &impl Fn(DbEvent)) {
+    match command {
+        DbCommand::LoadAll => match retry_busy(send, || load_all(database, send)) {
+            Ok(data) => {
+                let data_version = database.data_version().unwrap_or(known.data_version);
+                known.reset(&data.teams, &data.staff, data_version);
+                send(DbEvent::Loaded(data));
+            },
+            Err(e) => send(DbEvent::Failed(t!("加载数据").to_string(), e)),
+        },
+        command @ (DbCommand::SaveTeams(..) | DbCommand::SaveStaff(..)) => save(database, known, command, true, send),
+        DbCommand::Overwrite(command) => save(database, known, *command, false, send),
+        DbCommand::CopyStaffToTeam(staff_list, team_id) => {
+            send(DbEvent::Progress(t!("正在复制员工...").to_string(), 0.0));
+            match retry_busy(send, || database.copy_staff_to_team(&staff_list, team_id)) {
+                Ok(copied) => {
+                    known.update_staff(&copied);
+                    send(DbEvent::StaffCopied(copied));
+                },
+                Err(e) => send(DbEvent::Failed(t!("复制员工").to_string(), e)),
+            }
+        },
+        DbCommand::InsertGenerated(data) => {
+            send(DbEvent::Progress(t!("正在写入 {} 个球队...", data.teams.len()), 0.0));
+            match retry_busy(send, || database.insert_generated(&data)) {
+                Ok(inserted) => send(DbEvent::Generated(inserted)),
+                Err(e) => send(DbEvent::Failed(t!("写入生成数据").to_string(), e)),
+            }
+        },
+        DbCommand::DiffSnapshot => {
+            send(DbEvent::Progress(t!("正在对比修改...").to_string(), 0.0));
+            match snapshot.diff(database) {
+                Ok(changes) => {
+                    let taken_at = snapshot.taken_at.format("%Y-%m-%d %H:%M:%S").to_string();
+                    send(DbEvent::SnapshotDiff(changes, taken_at));
+                },
+                Err(e) => send(DbEvent::Failed(t!("对比修改").to_string(), e)),
+            }
+        },
+        DbCommand::RevertChanges(reverts) => {
+            send(DbEvent::Progress(t!("正在还原修改...").to_string(), 0.0));
+            // 全部还原成功才提交，避免中途出错留下只还原了一部分的数据库
+            let result = retry_busy(send, || {
+                database.transaction(|| {
+                    reverts.iter()
+                        .map(|revert| snapshot.revert(database, revert))
+                        .sum::<Result<usize>>()
+                })
+            });
+            match result {
+                Ok(count) => send(DbEvent::Reverted(count)),
+                Err(e) => send(DbEvent::Failed(t!("还原修改").to_string(), e)),
+            }
+        },
+        DbCommand::CheckExternalChanges => {
+            // 编辑器自己的写入不会改变本连接看到的版本号
+            let changed = database.data_version().is_ok_and(|version| version != known.data_version);
+            send(DbEvent::ExternalChanges(changed));
+        },
+    }
+}
+
+// 保存球队或员工，check为true时先检查是否与外部修改冲突
+fn save(database: &Database, known: &mut KnownState, command: DbCommand, check: bool, send: &impl Fn(DbEvent)) {
+    let conflicts = if !check {
+        Ok(Vec::new())
+    } else {
+        match &command {
+            DbCommand::SaveTeams(teams, _) => known.check_teams(database, teams),
+            DbCommand::SaveStaff(staff) => known.check_staff(database, std::slice::from_ref(staff)),
+            _ => Ok(Vec::new()),
+        }
+    };
+    match conflicts {
+        Ok(conflicts) if !conflicts.is_empty() => {
+            info!("保存时发现 {} 处外部修改冲突", conflicts.len());
+            send(DbEvent::Conflict(conflicts, command));
+            return;
+        },
+        Ok(_) => {},
+        Err(e) => {
+            send(DbEvent::Failed(t!("检查外部修改").to_string(), e));
+            return;
+        },
+    }
+
+    match command {
+        DbCommand::SaveTeams(teams, origin) => {
+            send(DbEvent::Progress(t!("正在保存 {} 个球队...", teams.len()), 0.0));
+            let result = retry_busy(send, || {
+                if teams.len() == 1 {
+                    database.update_team(&teams[0])
+                } else {
+                    database.update_teams_batch(&teams).map(|_| ())
+                }
+            });
+            match result {
+                Ok(_) => {
+                    known.update_teams(&teams);
+                    send(DbEvent::TeamsSaved(teams, origin));
+                },
+                Err(e) => send(DbEvent::Failed(t!("保存球队").to_string(), e)),
+            }
+        },
+        DbCommand::SaveStaff(staff) => {
+            send(DbEvent::Progress(t!("正在保存员工...").to_string(), 0.0));
+            match retry_busy(send, || database.update_staff(&staff)) {
+                Ok(_) => {
+                    known.update_staff(std::slice::from_ref(&staff));
+                    send(DbEvent::StaffSaved(staff));
+                },
+                Err(e) => send(DbEvent::Failed(t!("保存员工").to_string(), e)),
+            }
+        },
+        _ => send(DbEvent::Failed(t!("保存").to_string(), AppError::InvalidInput(t!("该命令不能覆盖保存").to_string()))),
+    }
+}
+
+// 数据库被其他程序锁定时等待后重试，仍然失败时返回DatabaseBusy
+fn retry_busy<T>(send: &impl Fn(DbEvent), mut operation: impl FnMut() -> Result<T>) -> Result<T> {
+    let mut attempt = 1;
+    loop {
+        match operation() {
+            Err(e) if e.is_busy() && attempt < BUSY_RETRIES => {
+                send(DbEvent::Progress(t!("数据库被占用，正在重试 ({}/{})...", attempt, BUSY_RETRIES - 1), 0.0));
+                thread::sleep(BUSY_RETRY_DELAY * attempt);
+                attempt += 1;
+            },
+            result => return result,
+        }
+    }
+}
+
+fn load_all(database: &Database, send: &impl Fn(DbEvent)) -> Result<LoadedData> {
+    send(DbEvent::Progress(t!("正在加载球队...").to_string(), 0.0));
+    let teams = database.load_teams()?;
+
+    send(DbEvent::Progress(t!("正在加载联赛...").to_string(), 0.4));
+    let leagues = database.load_leagues()?;
+
+    send(DbEvent::Progress(t!("正在加载员工...").to_string(), 0.5));
+    let staff = database.load_staff()?;
+
+    send(DbEvent::Progress(t!("正在加载赞助商...").to_string(), 0.9));
+    let sponsors = database.load_sponsors().unwrap_or_else(|e| {
+        error!("加载赞助商失败: {}", e);
+        Vec::new()
+    });
+
+    info!("后台加载完成: {} 个球队, {} 个员工", teams.len(), staff.len());
+    Ok(LoadedData { teams, leagues, staff, sponsors })
+}
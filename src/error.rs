@@ -1,36 +1,354 @@
-use thiserror::Error;
+use std::fmt;
+use std::io::ErrorKind;
 
-#[derive(Debug, thiserror::Error)]
+use rusqlite::ErrorCode;
+
+#[derive(Debug)]
 pub enum AppError {
-    #[error("数据库错误: {0}")]
     DatabaseError(String),
+    JsonError(serde_json::Error),
+    IoError(std::io::Error),
+    ImageError(image::ImageError),
+    InvalidInput(String),
+    NotFound(String),
+    DatabaseNotLoaded,
+    TeamNotSelected,
+    Unknown(String),
+    DatabaseBusy,
+    SqliteError(rusqlite::Error),
+    /// 附带出错时的操作、表、记录和SQL
+    WithContext {
+        context: ErrorContext,
+        source: Box<AppError>,
+    },
+}
 
-    #[error("JSON解析错误: {0}")]
-    JsonError(#[from] serde_json::Error),
+// 错误消息随界面语言变化，因此不使用thiserror生成
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            AppError::DatabaseError(message) => t!("数据库错误: {}", message),
+            AppError::JsonError(e) => t!("JSON解析错误: {}", e),
+            AppError::IoError(e) => t!("IO错误: {}", e),
+            AppError::ImageError(e) => t!("图片处理错误: {}", e),
+            AppError::InvalidInput(message) => t!("无效输入: {}", message),
+            AppError::NotFound(message) => t!("未找到记录: {}", message),
+            AppError::DatabaseNotLoaded => t!("未加载数据库").to_string(),
+            AppError::TeamNotSelected => t!("未选择球队").to_string(),
+            AppError::Unknown(message) => t!("未知错误: {}", message),
+            AppError::DatabaseBusy => t!("数据库被其他程序占用（游戏或其他工具可能正在写入），请稍后重试").to_string(),
+            AppError::SqliteError(e) => t!("SQLite错误: {}", e),
+            AppError::WithContext { context, source } => format!("{}: {}", context, source),
+        };
+        f.write_str(&message)
+    }
+}
 
-    #[error("IO错误: {0}")]
-    IoError(#[from] std::io::Error),
+impl std::error::Error for AppError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            AppError::JsonError(e) => Some(e),
+            AppError::IoError(e) => Some(e),
+            AppError::ImageError(e) => Some(e),
+            AppError::SqliteError(e) => Some(e),
+            AppError::WithContext { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
 
-    #[error("图片处理错误: {0}")]
-    ImageError(#[from] image::ImageError),
+impl From<serde_json::Error> for AppError {
+    fn from(err: serde_json::Error) -> Self {
+        AppError::JsonError(err)
+    }
+}
 
-    #[error("无效输入: {0}")]
-    InvalidInput(String),
+impl From<std::io::Error> for AppError {
+    fn from(err: std::io::Error) -> Self {
+        AppError::IoError(err)
+    }
+}
 
-    #[error("未找到记录: {0}")]
-    NotFound(String),
+impl From<image::ImageError> for AppError {
+    fn from(err: image::ImageError) -> Self {
+        AppError::ImageError(err)
+    }
+}
 
-    #[error("未加载数据库")]
-    DatabaseNotLoaded,
+impl From<rusqlite::Error> for AppError {
+    fn from(err: rusqlite::Error) -> Self {
+        // 数据库被锁定单独区分，便于重试和给出明确的提示
+        match err.sqlite_error_code() {
+            Some(ErrorCode::DatabaseBusy) | Some(ErrorCode::DatabaseLocked) => AppError::DatabaseBusy,
+            _ => AppError::SqliteError(err),
+        }
+    }
+}
 
-    #[error("未选择球队")]
-    TeamNotSelected,
+pub type Result<T> = std::result::Result<T, AppError>;
 
-    #[error("未知错误: {0}")]
-    Unknown(String),
+/// 出错时正在执行的操作，表、记录和SQL只在与数据库相关时填写
+#[derive(Debug, Clone, Default)]
+pub struct ErrorContext {
+    pub operation: String,
+    pub table: Option<String>,
+    pub record_id: Option<String>,
+    pub sql: Option<String>,
+}
+
+impl ErrorContext {
+    pub fn new(operation: impl Into<String>) -> Self {
+        ErrorContext {
+            operation: operation.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn table(mut self, table: impl Into<String>) -> Self {
+        self.table = Some(table.into());
+        self
+    }
+
+    pub fn record(mut self, record_id: impl fmt::Display) -> Self {
+        self.record_id = Some(record_id.to_string());
+        self
+    }
+
+    pub fn sql(mut self, sql: impl Into<String>) -> Self {
+        self.sql = Some(sql.into());
+        self
+    }
+}
+
+impl fmt::Display for ErrorContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.operation)?;
+        match (&self.table, &self.record_id) {
+            (Some(table), Some(record_id)) => f.write_str(&t!("（表 {}，记录 {}）", table, record_id)),
+            (Some(table), None) => f.write_str(&t!("（表 {}）", table)),
+            (None, Some(record_id)) => f.write_str(&t!("（记录 {}）", record_id)),
+            (None, None) => Ok(()),
+        }
+    }
+}
 
-    #[error("SQLite错误: {0}")]
-    SqliteError(#[from] rusqlite::Error),
+/// 为Result附加错误上下文
+pub trait ResultExt<T> {
+    fn context(self, context: impl FnOnce() -> ErrorContext) -> Result<T>;
 }
 
-pub type Result<T> = std::result::Result<T, AppError>; 
\ No newline at end of file
+impl<T, E: Into<AppError>> ResultExt<T> for std::result::Result<T, E> {
+    fn context(self, context: impl FnOnce() -> ErrorContext) -> Result<T> {
+        self.map_err(|e| e.into().with_context(context()))
+    }
+}
+
+impl AppError {
+    pub fn with_context(self, context: ErrorContext) -> AppError {
+        AppError::WithContext {
+            context,
+            source: Box::new(self),
+        }
+    }
+
+    /// 去掉所有上下文后的原始错误
+    pub fn root(&self) -> &AppError {
+        match self {
+            AppError::WithContext { source, .. } => source.root(),
+            other => other,
+        }
+    }
+
+    /// 由外到内的所有上下文
+    pub fn contexts(&self) -> Vec<&ErrorContext> {
+        let mut contexts = Vec::new();
+        let mut current = self;
+        while let AppError::WithContext { context, source } = current {
+            contexts.push(context);
+            current = source;
+        }
+        contexts
+    }
+
+    pub fn is_busy(&self) -> bool {
+        matches!(self.root(), AppError::DatabaseBusy)
+    }
+
+    /// 生成面向用户的错误报告：原因说明、修复建议和可复制的详细信息
+    pub fn report(&self) -> ErrorReport {
+        let (summary, suggestions) = explain(self.root());
+        let contexts = self.contexts();
+
+        let operation = contexts.iter()
+            .map(|c| c.operation.as_str())
+            .filter(|op| !op.is_empty())
+            .collect::<Vec<_>>()
+            .join(" → ");
+        // 表、记录和SQL取最内层（最接近出错位置）的值
+        let table = contexts.iter().rev().find_map(|c| c.table.clone());
+        let record_id = contexts.iter().rev().find_map(|c| c.record_id.clone());
+        let sql = contexts.iter().rev().find_map(|c| c.sql.clone());
+
+        let mut details = String::new();
+        details.push_str(&t!("时间: {}\n", chrono::Local::now().format("%Y-%m-%d %H:%M:%S")));
+        details.push_str(&t!("版本: {}\n", env!("CARGO_PKG_VERSION")));
+        if !operation.is_empty() {
+            details.push_str(&t!("操作: {}\n", operation));
+        }
+        if let Some(table) = &table {
+            details.push_str(&t!("表: {}\n", table));
+        }
+        if let Some(record_id) = &record_id {
+            details.push_str(&t!("记录: {}\n", record_id));
+        }
+        if let Some(sql) = &sql {
+            details.push_str(&format!("SQL: {}\n", sql));
+        }
+        details.push_str(&t!("错误: {}\n", self.root()));
+        details.push_str(&t!("原始错误: {:?}\n", self.root()));
+
+        ErrorReport {
+            operation,
+            summary,
+            suggestions: suggestions.into_iter().map(String::from).collect(),
+            details,
+        }
+    }
+}
+
+/// 展示给用户的错误报告
+#[derive(Debug, Clone)]
+pub struct ErrorReport {
+    pub operation: String,
+    pub summary: String,
+    pub suggestions: Vec<String>,
+    /// 完整的技术信息，可复制后反馈问题
+    pub details: String,
+}
+
+// 把常见的SQLite和IO错误翻译为原因说明和修复建议
+fn explain(err: &AppError) -> (String, Vec<&'static str>) {
+    match err {
+        AppError::DatabaseBusy => (
+            t!("数据库正被其他程序占用").to_string(),
+            vec![t!("关闭游戏或其他正在使用该数据库的工具后重试"), t!("稍等片刻后重试")],
+        ),
+        AppError::SqliteError(e) => explain_sqlite(e),
+        AppError::IoError(e) => explain_io(e),
+        AppError::JsonError(e) => (
+            t!("数据中的JSON字段格式不正确: {}", e),
+            vec![t!("该记录可能被其他工具写入了错误的数据，可在游戏中重置后再编辑")],
+        ),
+        AppError::ImageError(e) => (
+            t!("无法处理图片: {}", e),
+            vec![t!("确认图片是有效的PNG或JPEG文件"), t!("用其他图片软件重新另存后再导入")],
+        ),
+        AppError::DatabaseNotLoaded => (
+            t!("尚未加载数据库").to_string(),
+            vec![t!("通过“文件 → 加载数据库”打开游戏的数据库文件")],
+        ),
+        AppError::TeamNotSelected => (
+            t!("尚未选择球队").to_string(),
+            vec![t!("在左侧列表中选择一个球队")],
+        ),
+        AppError::NotFound(message) => (
+            t!("未找到记录: {}", message),
+            vec![t!("记录可能已被其他程序删除，请重新加载数据库")],
+        ),
+        AppError::InvalidInput(message) => (
+            message.clone(),
+            vec![t!("检查输入的内容后重试")],
+        ),
+        AppError::DatabaseError(message) | AppError::Unknown(message) => (
+            message.clone(),
+            vec![t!("复制下方的错误报告并反馈给开发者")],
+        ),
+        AppError::WithContext { source, .. } => explain(source.root()),
+    }
+}
+
+fn explain_sqlite(err: &rusqlite::Error) -> (String, Vec<&'static str>) {
+    if let rusqlite::Error::QueryReturnedNoRows = err {
+        return (
+            t!("数据库中没有对应的记录").to_string(),
+            vec![t!("记录可能已被其他程序删除，请重新加载数据库")],
+        );
+    }
+
+    // 表或列不存在时SQLite只返回通用错误码，需要根据消息判断
+    let message = err.to_string();
+    if message.contains("no such table") || message.contains("no such column") {
+        return (
+            t!("数据库结构与编辑器不匹配").to_string(),
+            vec![t!("确认打开的是CFS游戏的数据库文件"), t!("游戏版本更新后数据库结构可能已变化，请更新编辑器")],
+        );
+    }
+
+    match err.sqlite_error_code() {
+        Some(ErrorCode::ReadOnly) => (
+            t!("数据库文件不可写").to_string(),
+            vec![t!("检查文件是否被设为只读"), t!("确认对数据库所在文件夹有写入权限")],
+        ),
+        Some(ErrorCode::CannotOpen) => (
+            t!("无法打开数据库文件").to_string(),
+            vec![t!("检查文件路径是否正确"), t!("确认文件没有被移动或删除")],
+        ),
+        Some(ErrorCode::NotADatabase) => (
+            t!("文件不是有效的SQLite数据库").to_string(),
+            vec![t!("确认选择的是游戏的数据库文件，而不是其他文件")],
+        ),
+        Some(ErrorCode::DatabaseCorrupt) => (
+            t!("数据库文件已损坏").to_string(),
+            vec![t!("从 DatabaseBackup 文件夹中的备份恢复数据库"), t!("在游戏中重新生成存档")],
+        ),
+        Some(ErrorCode::DiskFull) => (
+            t!("磁盘空间不足").to_string(),
+            vec![t!("清理磁盘空间后重试")],
+        ),
+        Some(ErrorCode::ConstraintViolation) => (
+            t!("修改违反了数据库约束（例如ID重复或必填字段为空）").to_string(),
+            vec![t!("检查输入的值是否与其他记录重复"), t!("确认必填字段都已填写")],
+        ),
+        Some(ErrorCode::PermissionDenied) | Some(ErrorCode::AuthorizationForStatementDenied) => (
+            t!("没有访问数据库的权限").to_string(),
+            vec![t!("确认对数据库文件有读写权限"), t!("以管理员身份运行编辑器")],
+        ),
+        Some(ErrorCode::SystemIoFailure) => (
+            t!("读写数据库文件时发生磁盘错误").to_string(),
+            vec![t!("确认磁盘或移动存储设备连接正常"), t!("把数据库复制到本地磁盘后再编辑")],
+        ),
+        _ => (
+            t!("SQLite错误: {}", err),
+            vec![t!("复制下方的错误报告并反馈给开发者")],
+        ),
+    }
+}
+
+fn explain_io(err: &std::io::Error) -> (String, Vec<&'static str>) {
+    match err.kind() {
+        ErrorKind::NotFound => (
+            t!("找不到文件或文件夹").to_string(),
+            vec![t!("检查路径是否正确"), t!("确认文件没有被移动或删除")],
+        ),
+        ErrorKind::PermissionDenied => (
+            t!("没有访问文件的权限").to_string(),
+            vec![t!("确认对该文件和所在文件夹有读写权限"), t!("关闭可能正在使用该文件的程序")],
+        ),
+        ErrorKind::AlreadyExists => (
+            t!("文件已存在").to_string(),
+            vec![t!("选择其他文件名，或先删除已有文件")],
+        ),
+        ErrorKind::InvalidData | ErrorKind::UnexpectedEof => (
+            t!("文件内容不完整或格式不正确").to_string(),
+            vec![t!("确认文件没有损坏"), t!("重新导出或下载该文件")],
+        ),
+        ErrorKind::StorageFull => (
+            t!("磁盘空间不足").to_string(),
+            vec![t!("清理磁盘空间后重试")],
+        ),
+        _ => (
+            t!("读写文件失败: {}", err),
+            vec![t!("确认磁盘连接正常后重试")],
+        ),
+    }
+}
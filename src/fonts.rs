@@ -0,0 +1,392 @@
+// 界面字体
+//
+// 界面字体按以下顺序组成回退链：用户选择的字体文件、系统中文字体、编译时内置的子集字体。
+// egui按顺序查找字形，前面的字体缺少的字符由后面的字体补上。系统中文字体先检查常见位置，
+// 找不到时像fontconfig一样扫描系统和用户的字体目录，只采用确实包含中文字形的字体；
+// 扫描时只读取每个字体的 cmap 表，选中后才读取整个文件。
+
+use std::borrow::Cow;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use ab_glyph::{Font, FontRef};
+use egui::{Context, FontData, FontDefinitions, FontFamily};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AppError, Result};
+use crate::ui::theme;
+
+/// 默认正文字号
+pub const DEFAULT_FONT_SIZE: f32 = 16.0;
+
+/// 可选的正文字号范围
+pub const FONT_SIZE_RANGE: RangeInclusive<f32> = 10.0..=28.0;
+
+/// 可选的界面缩放范围
+pub const UI_SCALE_RANGE: RangeInclusive<f32> = 0.5..=2.5;
+
+/// 字体文件扩展名
+pub const FONT_EXTENSIONS: [&str; 4] = ["ttf", "otf", "ttc", "otc"];
+
+// 常见的系统中文字体，存在时不需要扫描字体目录
+const KNOWN_CJK_FONTS: [&str; 10] = [
+    // Windows 系统字体
+    "C:/Windows/Fonts/msyh.ttc",      // 微软雅黑
+    "C:/Windows/Fonts/simhei.ttf",    // 黑体
+    "C:/Windows/Fonts/simsun.ttc",    // 宋体
+    // Linux 系统字体
+    "/usr/share/fonts/opentype/noto/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/noto-cjk/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/google-noto-cjk/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/truetype/wqy/wqy-microhei.ttc",
+    "/usr/share/fonts/truetype/droid/DroidSansFallbackFull.ttf",
+    // macOS 系统字体
+    "/System/Library/Fonts/PingFang.ttc",
+    "/System/Library/Fonts/STHeiti Light.ttc",
+];
+
+// 扫描字体目录时，文件名包含这些词的字体优先检查，越靠前越优先
+const CJK_NAME_HINTS: [&str; 16] = [
+    "notosanscjk", "notosanssc", "sourcehansans", "wqy-microhei", "wqy-zenhei",
+    "droidsansfallback", "msyh", "pingfang", "hiragino", "heiti", "simhei",
+    "notoserifcjk", "sourcehanserif", "simsun", "uming", "cjk",
+];
+
+// 扫描字体目录的最大深度
+const MAX_SCAN_DEPTH: usize = 6;
+
+// 扫描时最多解析的字体文件数量，避免字体很多的系统启动过慢
+const MAX_PROBED_FONTS: usize = 200;
+
+// 扫描时读取的 cmap 表大小上限，超过时视为无效字体
+const MAX_CMAP_SIZE: u32 = 16 * 1024 * 1024;
+
+// 判断字体是否包含中文时检查的字符
+const CJK_PROBE: &str = "中文球队";
+
+// 内置的子集字体，使用 embedded-font 特性编译时由 build.rs 复制到 OUT_DIR；
+// 编译时没有提供字体文件时为空文件，按未内置处理
+#[cfg(feature = "embedded-font")]
+const EMBEDDED_FONT: Option<&[u8]> = {
+    let data: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/embedded-cjk-subset.otf"));
+    if data.is_empty() { None } else { Some(data) }
+};
+#[cfg(not(feature = "embedded-font"))]
+const EMBEDDED_FONT: Option<&[u8]> = None;
+
+// 当前回退链中第一个包含中文的字体，导出PNG图表时也使用这个字体
+static CJK_FONT: Mutex<Option<(Cow<'static, [u8]>, u32)>> = Mutex::new(None);
+
+/// 字体和缩放设置
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FontSettings {
+    /// 用户选择的字体文件，排在回退链最前面
+    pub custom_font: Option<PathBuf>,
+    /// 查找系统中已安装的中文字体
+    pub use_system_fonts: bool,
+    /// 使用内置的子集字体作为最后的回退
+    pub use_embedded_font: bool,
+    /// 正文字号，标题和小字按比例调整
+    pub font_size: f32,
+    /// 界面缩放比例
+    pub ui_scale: f32,
+}
+
+impl Default for FontSettings {
+    fn default() -> Self {
+        FontSettings {
+            custom_font: None,
+            use_system_fonts: true,
+            use_embedded_font: true,
+            font_size: DEFAULT_FONT_SIZE,
+            ui_scale: 1.0,
+        }
+    }
+}
+
+/// 字体的来源
+#[derive(Debug, Clone, PartialEq)]
+pub enum FontSource {
+    Custom(PathBuf),
+    System(PathBuf),
+    Embedded,
+}
+
+impl FontSource {
+    pub fn describe(&self) -> String {
+        match self {
+            FontSource::Custom(path) => t!("自定义字体 {}", path.display()),
+            FontSource::System(path) => t!("系统字体 {}", path.display()),
+            FontSource::Embedded => t!("内置子集字体").to_string(),
+        }
+    }
+}
+
+/// 回退链中已加载的字体
+pub struct ActiveFont {
+    pub source: FontSource,
+    /// 字体是否包含中文字形
+    pub covers_cjk: bool,
+}
+
+// 加载后的字体数据
+struct LoadedFont {
+    source: FontSource,
+    data: Cow<'static, [u8]>,
+    index: u32,
+    covers_cjk: bool,
+}
+
+impl LoadedFont {
+    fn parse(source: FontSource, data: Cow<'static, [u8]>) -> Option<Self> {
+        // 字体集合（.ttc）只使用第一个字体
+        let covers_cjk = {
+            let font = FontRef::try_from_slice_and_index(&data, 0).ok()?;
+            CJK_PROBE.chars().all(|c| font.glyph_id(c).0 != 0)
+        };
+        Some(LoadedFont {
+            source,
+            data,
+            index: 0,
+            covers_cjk,
+        })
+    }
+}
+
+/// 是否编译了内置字体
+pub fn has_embedded_font() -> bool {
+    EMBEDDED_FONT.is_some()
+}
+
+/// 检查字体文件能否使用，返回是否包含中文字形
+pub fn check_font_file(path: &Path) -> Result<bool> {
+    let data = std::fs::read(path)?;
+    LoadedFont::parse(FontSource::Custom(path.to_path_buf()), Cow::Owned(data))
+        .map(|font| font.covers_cjk)
+        .ok_or_else(|| AppError::InvalidInput(t!("不是有效的字体文件: {}", path.display())))
+}
+
+/// 按设置加载字体回退链，并应用字号和界面缩放，返回实际使用的字体
+pub fn apply(ctx: &Context, settings: &FontSettings) -> Vec<ActiveFont> {
+    let chain = resolve(settings);
+
+    let mut fonts = FontDefinitions::default();
+    for (i, font) in chain.iter().enumerate() {
+        let name = format!("font_chain_{}", i);
+        fonts.font_data.insert(name.clone(), FontData {
+            font: font.data.clone(),
+            index: font.index,
+            tweak: Default::default(),
+        });
+
+        // 比例字体优先使用回退链中的字体，等宽字体只用它们补充缺少的字形
+        fonts.families.entry(FontFamily::Proportional).or_default().insert(i, name.clone());
+        fonts.families.entry(FontFamily::Monospace).or_default().push(name);
+
+        info!("已加载字体: {}", font.source.describe());
+    }
+    ctx.set_fonts(fonts);
+
+    theme::set_font_size(ctx, settings.font_size);
+    ctx.set_zoom_factor(settings.ui_scale);
+
+    let cjk_font = chain.iter()
+        .find(|font| font.covers_cjk)
+        .map(|font| (font.data.clone(), font.index));
+    if cjk_font.is_none() {
+        warn!("未找到包含中文的字体，中文可能无法正常显示");
+    }
+    if let Ok(mut current) = CJK_FONT.lock() {
+        *current = cjk_font;
+    }
+
+    chain.into_iter()
+        .map(|font| ActiveFont {
+            source: font.source,
+            covers_cjk: font.covers_cjk,
+        })
+        .collect()
+}
+
+/// 当前使用的中文字体数据和字体索引
+pub fn cjk_font_data() -> Option<(Vec<u8>, u32)> {
+    let current = CJK_FONT.lock().ok()?;
+    current.as_ref().map(|(data, index)| (data.to_vec(), *index))
+}
+
+fn resolve(settings: &FontSettings) -> Vec<LoadedFont> {
+    let mut chain = Vec::new();
+
+    if let Some(path) = &settings.custom_font {
+        match std::fs::read(path) {
+            Ok(data) => match LoadedFont::parse(FontSource::Custom(path.clone()), Cow::Owned(data)) {
+                Some(font) => chain.push(font),
+                None => warn!("不是有效的字体文件: {}", path.display()),
+            },
+            Err(e) => warn!("无法读取字体文件 {}: {}", path.display(), e),
+        }
+    }
+
+    // 自定义字体不包含中文时继续查找系统中文字体
+    if settings.use_system_fonts && !chain.iter().any(|font| font.covers_cjk) {
+        if let Some(font) = find_system_cjk_font() {
+            chain.push(font);
+        }
+    }
+
+    if settings.use_embedded_font {
+        if let Some(data) = EMBEDDED_FONT {
+            if let Some(font) = LoadedFont::parse(FontSource::Embedded, Cow::Borrowed(data)) {
+                chain.push(font);
+            }
+        }
+    }
+
+    chain
+}
+
+fn find_system_cjk_font() -> Option<LoadedFont> {
+    let known = KNOWN_CJK_FONTS.iter().map(PathBuf::from).filter(|path| path.exists());
+    if let Some(font) = known.filter_map(probe_cjk_font).next() {
+        return Some(font);
+    }
+
+    let mut files = Vec::new();
+    for dir in font_directories() {
+        collect_font_files(&dir, 0, &mut files);
+    }
+    files.sort_by_cached_key(|path| (name_rank(path), path.clone()));
+    files.dedup();
+
+    let font = files.into_iter()
+        .take(MAX_PROBED_FONTS)
+        .filter_map(probe_cjk_font)
+        .next();
+    if font.is_none() {
+        warn!("在系统字体目录中没有找到中文字体");
+    }
+    font
+}
+
+// 先只读取 cmap 表确认包含中文字形，再读取整个字体文件
+fn probe_cjk_font(path: PathBuf) -> Option<LoadedFont> {
+    if !cmap_covers_cjk(&path)? {
+        return None;
+    }
+    let data = std::fs::read(&path).ok()?;
+    LoadedFont::parse(FontSource::System(path), Cow::Owned(data)).filter(|font| font.covers_cjk)
+}
+
+// 按表目录定位 cmap 表，只读取这一个表判断是否包含中文字形。
+// 字体集合（.ttc）与加载时一样只检查第一个字体
+fn cmap_covers_cjk(path: &Path) -> Option<bool> {
+    let mut file = File::open(path).ok()?;
+
+    let mut font_offset = 0;
+    let mut header = read_at(&mut file, 0, 16)?;
+    if &header[..4] == b"ttcf" {
+        font_offset = be_u32(&header[12..16]);
+        header = read_at(&mut file, font_offset, 12)?;
+    }
+
+    let num_tables = u16::from_be_bytes([header[4], header[5]]) as usize;
+    let records = read_at(&mut file, font_offset + 12, num_tables * 16)?;
+    let record = records.chunks_exact(16).find(|record| &record[..4] == b"cmap")?;
+    let length = be_u32(&record[12..16]);
+    if length > MAX_CMAP_SIZE {
+        return None;
+    }
+    let data = read_at(&mut file, be_u32(&record[8..12]), length as usize)?;
+
+    let table = ttf_parser::cmap::Table::parse(&data)?;
+    let has_glyph = |c: char| table.subtables.into_iter()
+        .filter(|subtable| subtable.is_unicode())
+        .any(|subtable| subtable.glyph_index(c as u32).is_some_and(|id| id.0 != 0));
+    Some(CJK_PROBE.chars().all(has_glyph))
+}
+
+fn read_at(file: &mut File, offset: u32, len: usize) -> Option<Vec<u8>> {
+    let mut buf = vec![0; len];
+    file.seek(SeekFrom::Start(offset as u64)).ok()?;
+    file.read_exact(&mut buf).ok()?;
+    Some(buf)
+}
+
+fn be_u32(bytes: &[u8]) -> u32 {
+    u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
+
+// 文件名看起来像中文字体的排在前面
+fn name_rank(path: &Path) -> usize {
+    let name = path.file_name()
+        .map(|name| name.to_string_lossy().to_lowercase().replace([' ', '_'], ""))
+        .unwrap_or_default();
+    CJK_NAME_HINTS.iter()
+        .position(|hint| name.contains(hint))
+        .unwrap_or(CJK_NAME_HINTS.len())
+}
+
+// 系统和当前用户的字体目录
+fn font_directories() -> Vec<PathBuf> {
+    let home = std::env::var_os("HOME").map(PathBuf::from);
+    let mut dirs = Vec::new();
+
+    if cfg!(windows) {
+        let windir = std::env::var_os("WINDIR").map(PathBuf::from).unwrap_or_else(|| PathBuf::from("C:/Windows"));
+        dirs.push(windir.join("Fonts"));
+        if let Some(local) = std::env::var_os("LOCALAPPDATA") {
+            dirs.push(PathBuf::from(local).join("Microsoft/Windows/Fonts"));
+        }
+    } else if cfg!(target_os = "macos") {
+        dirs.push(PathBuf::from("/System/Library/Fonts"));
+        dirs.push(PathBuf::from("/Library/Fonts"));
+        if let Some(home) = &home {
+            dirs.push(home.join("Library/Fonts"));
+        }
+    } else {
+        // 与fontconfig的默认配置相同，使用XDG数据目录下的fonts目录
+        let data_home = std::env::var_os("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .or_else(|| home.as_ref().map(|home| home.join(".local/share")));
+        dirs.extend(data_home.map(|dir| dir.join("fonts")));
+        dirs.extend(home.as_ref().map(|home| home.join(".fonts")));
+
+        let data_dirs = std::env::var("XDG_DATA_DIRS")
+            .ok()
+            .filter(|dirs| !dirs.is_empty())
+            .unwrap_or_else(|| "/usr/local/share:/usr/share".to_string());
+        dirs.extend(data_dirs.split(':').filter(|dir| !dir.is_empty()).map(|dir| Path::new(dir).join("fonts")));
+    }
+
+    dirs
+}
+
+fn collect_font_files(dir: &Path, depth: usize, files: &mut Vec<PathBuf>) {
+    if depth > MAX_SCAN_DEPTH {
+        return;
+    }
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_font_files(&path, depth + 1, files);
+        } else if is_font_file(&path) {
+            files.push(path);
+        }
+    }
+}
+
+fn is_font_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| FONT_EXTENSIONS.iter().any(|e| e.eq_ignore_ascii_case(ext)))
+        .unwrap_or(false)
+}
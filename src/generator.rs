@@ -0,0 +1,310 @@
+// 测试数据生成器
+//
+// 按配置生成虚构的球队和员工，用于搭建测试联赛。名称、地区、主场和昵称从可编辑的
+// 词表中组合，财富、支持者数量、员工声望和能力按选定的分布抽样。使用固定的随机种子
+// 时每次生成的结果完全相同。生成的ID从1开始，写入数据库时再平移到现有ID之后。
+
+use std::collections::HashSet;
+
+use crate::data::staff::Staff;
+use crate::data::team::Team;
+
+/// 可复现的伪随机数生成器（SplitMix64），不依赖外部库的实现细节
+pub struct SeededRng {
+    state: u64,
+}
+
+impl SeededRng {
+    pub fn new(seed: u64) -> Self {
+        SeededRng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// [0, 1) 之间的均匀分布
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// [min, max] 之间的整数
+    pub fn range(&mut self, min: i64, max: i64) -> i64 {
+        if max <= min {
+            return min;
+        }
+        let span = (max as i128 - min as i128 + 1) as u128;
+        (min as i128 + (self.next_u64() as u128 % span) as i128) as i64
+    }
+
+    /// 标准正态分布（Box-Muller）
+    pub fn standard_normal(&mut self) -> f64 {
+        let u1 = 1.0 - self.next_f64();
+        let u2 = self.next_f64();
+        (-2.0 * u1.ln()).sqrt() * (std::f64::consts::TAU * u2).cos()
+    }
+
+    pub fn pick<'a>(&mut self, items: &'a [String]) -> Option<&'a str> {
+        if items.is_empty() {
+            None
+        } else {
+            Some(&items[self.range(0, items.len() as i64 - 1) as usize])
+        }
+    }
+}
+
+/// 数值的分布形状
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum DistributionKind {
+    /// 区间内均匀分布
+    Uniform,
+    /// 集中在区间中点附近，区间两端约为±3个标准差
+    Normal,
+    /// 大多数数值偏低、少数很高，适合财富和支持者数量
+    LogNormal,
+}
+
+impl DistributionKind {
+    pub const ALL: [DistributionKind; 3] = [DistributionKind::Uniform, DistributionKind::Normal, DistributionKind::LogNormal];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DistributionKind::Uniform => t!("均匀"),
+            DistributionKind::Normal => t!("正态"),
+            DistributionKind::LogNormal => t!("对数正态"),
+        }
+    }
+}
+
+/// 取值范围和分布形状，抽样结果总在[min, max]之内
+#[derive(Debug, Clone, Copy)]
+pub struct ValueDistribution {
+    pub kind: DistributionKind,
+    pub min: i64,
+    pub max: i64,
+}
+
+impl ValueDistribution {
+    pub fn new(kind: DistributionKind, min: i64, max: i64) -> Self {
+        ValueDistribution { kind, min, max }
+    }
+
+    pub fn sample(&self, rng: &mut SeededRng) -> i64 {
+        let (min, max) = (self.min.min(self.max), self.min.max(self.max));
+        let value = match self.kind {
+            DistributionKind::Uniform => return rng.range(min, max),
+            DistributionKind::Normal => {
+                let mean = (min as f64 + max as f64) / 2.0;
+                let std_dev = (max as f64 - min as f64) / 6.0;
+                mean + rng.standard_normal() * std_dev
+            },
+            DistributionKind::LogNormal => {
+                // 在对数空间中取正态分布，最小值不大于0时平移到从1开始
+                let shift = if min < 1 { 1 - min } else { 0 };
+                let log_min = ((min + shift) as f64).ln();
+                let log_max = ((max + shift) as f64).ln();
+                let mu = (log_min + log_max) / 2.0;
+                let sigma = (log_max - log_min) / 6.0;
+                (mu + rng.standard_normal() * sigma).exp() - shift as f64
+            },
+        };
+        (value.round() as i64).clamp(min, max)
+    }
+}
+
+/// 组合名称使用的词表
+#[derive(Debug, Clone)]
+pub struct WordLists {
+    pub locations: Vec<String>,
+    /// 球队名称中地区之后的部分，如"雄狮"
+    pub team_names: Vec<String>,
+    pub team_suffixes: Vec<String>,
+    pub stadium_suffixes: Vec<String>,
+    pub nicknames: Vec<String>,
+    pub surnames: Vec<String>,
+    pub given_names: Vec<String>,
+}
+
+impl Default for WordLists {
+    fn default() -> Self {
+        WordLists {
+            locations: split_words("北京 上海 广州 深圳 成都 武汉 杭州 南京 天津 重庆 西安 青岛 大连 沈阳 长沙 厦门"),
+            team_names: split_words("雄狮 猛虎 飞鹰 海港 铁骑 巨龙 星辰 烈火 神舟 蓝鲸 骑士 勇士 翼龙 凤凰 山河 雷霆"),
+            team_suffixes: split_words("足球俱乐部 FC 联队 竞技 体育"),
+            stadium_suffixes: split_words("体育场 体育中心 球场 竞技场"),
+            nicknames: split_words("狮子军团 蓝色风暴 红色铁骑 海港之王 北方之狼 南国雄鹰 橙色旋风 绿茵卫士"),
+            surnames: split_words("王 李 张 刘 陈 杨 赵 黄 周 吴 徐 孙 马 朱 胡 郭"),
+            given_names: split_words("伟 芳 强 磊 军 洋 勇 杰 涛 明 超 华 鹏 辉 斌 宇"),
+        }
+    }
+}
+
+impl WordLists {
+    /// 各个词表的名称和内容，用于在界面中编辑
+    pub fn lists_mut(&mut self) -> [(&'static str, &mut Vec<String>); 7] {
+        [
+            (t!("地区"), &mut self.locations),
+            (t!("球队名称"), &mut self.team_names),
+            (t!("球队后缀"), &mut self.team_suffixes),
+            (t!("主场后缀"), &mut self.stadium_suffixes),
+            (t!("昵称"), &mut self.nicknames),
+            (t!("员工姓氏"), &mut self.surnames),
+            (t!("员工名字"), &mut self.given_names),
+        ]
+    }
+}
+
+/// 把以空格、逗号或换行分隔的文本拆分为词表
+pub fn split_words(text: &str) -> Vec<String> {
+    text.split(|c: char| c.is_whitespace() || c == ',' || c == '，' || c == '、')
+        .filter(|word| !word.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// 生成参数
+#[derive(Debug, Clone)]
+pub struct GeneratorConfig {
+    pub seed: u64,
+    pub team_count: usize,
+    pub staff_per_team: usize,
+    /// 生成的球队所属的联赛
+    pub league_id: i64,
+    pub found_year_min: i64,
+    pub found_year_max: i64,
+    pub wealth: ValueDistribution,
+    pub supporters: ValueDistribution,
+    pub fame: ValueDistribution,
+    pub ability: ValueDistribution,
+    pub words: WordLists,
+}
+
+impl Default for GeneratorConfig {
+    fn default() -> Self {
+        GeneratorConfig {
+            seed: 1,
+            team_count: 16,
+            staff_per_team: 5,
+            league_id: 0,
+            found_year_min: 1880,
+            found_year_max: 2010,
+            wealth: ValueDistribution::new(DistributionKind::LogNormal, 1_000, 100_000),
+            supporters: ValueDistribution::new(DistributionKind::LogNormal, 500, 200_000),
+            fame: ValueDistribution::new(DistributionKind::Normal, 0, 100),
+            ability: ValueDistribution::new(DistributionKind::Normal, 20, 180),
+            words: WordLists::default(),
+        }
+    }
+}
+
+/// 一次生成的球队和员工，员工的球队ID对应生成的球队
+#[derive(Debug, Clone, Default)]
+pub struct GeneratedData {
+    pub teams: Vec<Team>,
+    pub staff: Vec<Staff>,
+}
+
+/// 按配置生成数据，种子和配置相同时结果相同
+pub fn generate(config: &GeneratorConfig) -> GeneratedData {
+    let mut rng = SeededRng::new(config.seed);
+    let words = &config.words;
+    let mut data = GeneratedData::default();
+    let mut used_names: HashSet<String> = HashSet::new();
+
+    for index in 0..config.team_count {
+        let team_id = index as i64 + 1;
+        let location = rng.pick(&words.locations).unwrap_or("").to_string();
+        let core = rng.pick(&words.team_names).unwrap_or("").to_string();
+
+        // 名称重复时换一个后缀，仍然重复时加上编号
+        let mut name = String::new();
+        for _ in 0..4 {
+            let suffix = rng.pick(&words.team_suffixes).unwrap_or("");
+            name = format!("{}{}{}", location, core, suffix);
+            if !used_names.contains(&name) {
+                break;
+            }
+        }
+        if name.is_empty() || used_names.contains(&name) {
+            name = format!("{}{}", name, team_id);
+        }
+        used_names.insert(name.clone());
+
+        let stadium_suffix = rng.pick(&words.stadium_suffixes).unwrap_or("");
+        let nickname = rng.pick(&words.nicknames).map_or_else(|| core.clone(), str::to_string);
+
+        data.teams.push(Team {
+            id: team_id,
+            name,
+            wealth: config.wealth.sample(&mut rng),
+            found_year: rng.range(config.found_year_min, config.found_year_max),
+            location: location.clone(),
+            supporter_count: config.supporters.sample(&mut rng),
+            stadium_name: format!("{}{}{}", location, core, stadium_suffix),
+            nickname,
+            league_id: config.league_id,
+        });
+
+        for _ in 0..config.staff_per_team {
+            let staff_id = data.staff.len() as i64 + 1;
+            let surname = rng.pick(&words.surnames).unwrap_or("");
+            let given_count = rng.range(1, 2);
+            let given: String = (0..given_count)
+                .map(|_| rng.pick(&words.given_names).unwrap_or(""))
+                .collect();
+
+            data.staff.push(Staff {
+                id: staff_id,
+                name: format!("{}{}", surname, given),
+                ability_json: format!(r#"{{"rawAbility":{}}}"#, config.ability.sample(&mut rng)),
+                fame: config.fame.sample(&mut rng),
+                team_id,
+            });
+        }
+    }
+
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(seed: u64) -> GeneratorConfig {
+        GeneratorConfig {
+            seed,
+            team_count: 40,
+            staff_per_team: 3,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn same_seed_gives_same_output() {
+        let first = generate(&config(20240601));
+        let second = generate(&config(20240601));
+        assert_eq!(first.teams, second.teams);
+        assert_eq!(first.staff, second.staff);
+    }
+
+    #[test]
+    fn different_seeds_give_different_output() {
+        let first = generate(&config(1));
+        let second = generate(&config(2));
+        assert_ne!(first.teams, second.teams);
+    }
+
+    #[test]
+    fn generated_staff_belong_to_generated_teams() {
+        let data = generate(&config(7));
+        assert_eq!(data.teams.len(), 40);
+        assert_eq!(data.staff.len(), 40 * 3);
+        for staff in &data.staff {
+            assert!(data.teams.iter().any(|team| team.id == staff.team_id), "{:?}", staff);
+        }
+    }
+}
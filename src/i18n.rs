@@ -0,0 +1,136 @@
+//! 界面文本的本地化
+//!
+//! 界面文本以简体中文编写，中文原文同时作为翻译表的键。英文翻译表位于
+//! `assets/i18n/en.json`，编译时嵌入程序；缺少翻译的文本直接显示中文原文。
+//!
+//! 带参数的文本使用 `t!("已导出 {} 个球队", count)`，占位符与 `format!` 相同，
+//! 支持 `{}`、`{0}` 和 `{:.1}` 这样的精度，翻译中可以用 `{1}` 调整参数顺序。
+
+use std::collections::HashMap;
+use std::fmt::{self, Write};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+
+/// 界面语言
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    Chinese,
+    English,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::Chinese, Language::English];
+
+    /// 语言自身的名称，不随界面语言变化
+    pub fn native_name(&self) -> &'static str {
+        match self {
+            Language::Chinese => "简体中文",
+            Language::English => "English",
+        }
+    }
+
+    /// HTML的lang属性值
+    pub fn html_lang(&self) -> &'static str {
+        match self {
+            Language::Chinese => "zh-CN",
+            Language::English => "en",
+        }
+    }
+
+    fn catalogue(&self) -> Option<&'static HashMap<String, String>> {
+        match self {
+            Language::Chinese => None,
+            Language::English => {
+                static ENGLISH: OnceLock<HashMap<String, String>> = OnceLock::new();
+                Some(ENGLISH.get_or_init(|| load_catalogue(include_str!("../assets/i18n/en.json"))))
+            },
+        }
+    }
+}
+
+// 当前语言，后台线程生成的进度和错误文本也会用到
+static CURRENT: AtomicU8 = AtomicU8::new(0);
+
+pub fn set_language(language: Language) {
+    CURRENT.store(language as u8, Ordering::Relaxed);
+}
+
+pub fn language() -> Language {
+    match CURRENT.load(Ordering::Relaxed) {
+        1 => Language::English,
+        _ => Language::Chinese,
+    }
+}
+
+fn load_catalogue(json: &str) -> HashMap<String, String> {
+    serde_json::from_str(json).unwrap_or_else(|e| {
+        log::error!("解析翻译表失败: {}", e);
+        HashMap::new()
+    })
+}
+
+/// 翻译文本，没有翻译时返回原文
+pub fn tr(text: &str) -> &str {
+    language()
+        .catalogue()
+        .and_then(|catalogue| catalogue.get(text))
+        .map_or(text, String::as_str)
+}
+
+/// 把参数填入模板中的占位符
+pub fn format(template: &str, args: &[&dyn fmt::Display]) -> String {
+    let mut out = String::with_capacity(template.len() + args.len() * 8);
+    let mut next_arg = 0;
+    let mut rest = template;
+
+    while let Some(pos) = rest.find(['{', '}']) {
+        out.push_str(&rest[..pos]);
+        let tail = &rest[pos..];
+
+        // 双写的花括号表示花括号本身
+        if tail.starts_with("{{") || tail.starts_with("}}") {
+            out.push_str(&tail[..1]);
+            rest = &tail[2..];
+            continue;
+        }
+        let Some(end) = tail.find('}').filter(|_| tail.starts_with('{')) else {
+            out.push_str(&tail[..1]);
+            rest = &tail[1..];
+            continue;
+        };
+
+        let spec = &tail[1..end];
+        let (index, precision) = match spec.split_once(':') {
+            Some((index, format)) => (index, format.strip_prefix('.').and_then(|p| p.parse::<usize>().ok())),
+            None => (spec, None),
+        };
+        let index = index.parse::<usize>().unwrap_or_else(|_| {
+            next_arg += 1;
+            next_arg - 1
+        });
+
+        if let Some(arg) = args.get(index) {
+            let _ = match precision {
+                Some(precision) => write!(out, "{:.*}", precision, arg),
+                None => write!(out, "{}", arg),
+            };
+        }
+        rest = &tail[end + 1..];
+    }
+
+    out.push_str(rest);
+    out
+}
+
+/// 翻译界面文本，带参数时按 `format!` 的占位符填入参数并返回 `String`
+macro_rules! t {
+    ($text:expr) => {
+        $crate::i18n::tr($text)
+    };
+    ($text:expr, $($arg:expr),+ $(,)?) => {
+        $crate::i18n::format($crate::i18n::tr($text), &[$(&$arg as &dyn ::std::fmt::Display),+])
+    };
+}
@@ -0,0 +1,533 @@
+// 球队Logo管理
+//
+// 游戏从数据库所在目录读取 `L{球队ID}.png`，格式为 256x256 的 RGBA PNG。
+// 替换或移除Logo前，原始文件会备份到 `LogoBackup` 目录，以便恢复。
+
+use std::path::{Path, PathBuf};
+
+use egui::{Color32, ColorImage, Context, TextureHandle, TextureOptions};
+use image::{imageops::FilterType, DynamicImage, GenericImageView, RgbaImage};
+use log::info;
+
+use crate::data::team::Team;
+use crate::error::Result;
+use crate::utils;
+
+/// 游戏使用的Logo尺寸（像素）
+pub const LOGO_SIZE: u32 = 256;
+
+// 原始Logo的备份目录
+const BACKUP_DIR_NAME: &str = "LogoBackup";
+
+// 赞助商Logo所在目录
+const SPONSOR_LOGO_DIR_NAME: &str = "SponsorLogos";
+
+/// 游戏中Logo的几种显示尺寸，用于保存前预览
+pub const PREVIEW_SIZES: [f32; 3] = [128.0, 64.0, 32.0];
+
+/// 可导入的图片文件扩展名
+pub const IMAGE_EXTENSIONS: [&str; 4] = ["png", "jpg", "jpeg", "bmp"];
+
+// 模糊匹配的最低相似度
+const FUZZY_MATCH_THRESHOLD: f32 = 0.6;
+
+/// 非正方形图片转换为Logo的方式
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum LogoFit {
+    /// 居中裁剪为正方形
+    Crop,
+    /// 等比缩放并用指定颜色填充为正方形
+    Pad,
+}
+
+impl LogoFit {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LogoFit::Crop => t!("居中裁剪"),
+            LogoFit::Pad => t!("填充为正方形"),
+        }
+    }
+}
+
+/// 裁剪区域，使用源图片的像素坐标
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct CropRect {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl CropRect {
+    /// 整张图片
+    pub fn full(width: u32, height: u32) -> Self {
+        CropRect { x: 0, y: 0, width, height }
+    }
+
+    /// 图片中央最大的正方形
+    pub fn centered_square(width: u32, height: u32) -> Self {
+        let side = width.min(height);
+        CropRect {
+            x: (width - side) / 2,
+            y: (height - side) / 2,
+            width: side,
+            height: side,
+        }
+    }
+
+    /// 限制在图片范围内，且宽高至少为1像素
+    pub fn clamped(self, width: u32, height: u32) -> Self {
+        let x = self.x.min(width.saturating_sub(1));
+        let y = self.y.min(height.saturating_sub(1));
+        CropRect {
+            x,
+            y,
+            width: self.width.clamp(1, width - x),
+            height: self.height.clamp(1, height - y),
+        }
+    }
+}
+
+/// 按颜色把背景变为透明
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ColorKey {
+    pub color: [u8; 3],
+    /// 每个颜色通道允许的最大差值
+    pub tolerance: u8,
+}
+
+/// Logo编辑参数，按裁剪、去背景、转为正方形的顺序处理
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct LogoEdit {
+    pub crop: CropRect,
+    pub color_key: Option<ColorKey>,
+    pub fit: LogoFit,
+    pub pad_color: [u8; 4],
+}
+
+impl LogoEdit {
+    pub fn new(width: u32, height: u32) -> Self {
+        LogoEdit {
+            crop: CropRect::full(width, height),
+            color_key: None,
+            fit: LogoFit::Pad,
+            pad_color: [0, 0, 0, 0],
+        }
+    }
+}
+
+/// Logo文件的状态
+#[derive(Debug, Clone)]
+pub struct LogoInfo {
+    pub dimensions: Option<(u32, u32)>,
+    pub has_backup: bool,
+}
+
+impl LogoInfo {
+    pub fn exists(&self) -> bool {
+        self.dimensions.is_some()
+    }
+
+    /// Logo存在但尺寸与游戏要求不一致
+    pub fn has_wrong_size(&self) -> bool {
+        matches!(self.dimensions, Some((w, h)) if w != LOGO_SIZE || h != LOGO_SIZE)
+    }
+}
+
+/// 球队Logo的路径
+pub fn logo_path(db_dir: &Path, team_id: i64) -> PathBuf {
+    utils::create_logo_path(db_dir, team_id)
+}
+
+/// 赞助商Logo目录
+pub fn sponsor_logo_dir(db_dir: &Path) -> PathBuf {
+    db_dir.join(SPONSOR_LOGO_DIR_NAME)
+}
+
+/// 赞助商Logo的路径
+pub fn sponsor_logo_path(db_dir: &Path, sponsor_name: &str) -> PathBuf {
+    sponsor_logo_dir(db_dir).join(format!("{}.png", sponsor_name))
+}
+
+fn backup_dir(db_dir: &Path) -> PathBuf {
+    db_dir.join(BACKUP_DIR_NAME)
+}
+
+fn backup_path(db_dir: &Path, team_id: i64) -> PathBuf {
+    backup_dir(db_dir).join(format!("L{}.png", team_id))
+}
+
+// 原本没有Logo时写入的标记文件，恢复时会删除Logo
+fn missing_marker_path(db_dir: &Path, team_id: i64) -> PathBuf {
+    backup_dir(db_dir).join(format!("L{}.none", team_id))
+}
+
+/// 读取Logo文件的状态
+pub fn logo_info(db_dir: &Path, team_id: i64) -> LogoInfo {
+    let path = logo_path(db_dir, team_id);
+    let dimensions = if utils::file_exists(&path) {
+        image::image_dimensions(&path).ok()
+    } else {
+        None
+    };
+
+    LogoInfo {
+        dimensions,
+        has_backup: has_original_backup(db_dir, team_id),
+    }
+}
+
+/// 是否存在可恢复的原始Logo
+pub fn has_original_backup(db_dir: &Path, team_id: i64) -> bool {
+    utils::file_exists(&backup_path(db_dir, team_id))
+        || utils::file_exists(&missing_marker_path(db_dir, team_id))
+}
+
+/// 首次修改前备份原始Logo，已有备份时不会覆盖
+fn backup_original(db_dir: &Path, team_id: i64) -> Result<()> {
+    if has_original_backup(db_dir, team_id) {
+        return Ok(());
+    }
+
+    std::fs::create_dir_all(backup_dir(db_dir))?;
+
+    let path = logo_path(db_dir, team_id);
+    if utils::file_exists(&path) {
+        std::fs::copy(&path, backup_path(db_dir, team_id))?;
+        info!("已备份原始Logo: {}", path.display());
+    } else {
+        std::fs::write(missing_marker_path(db_dir, team_id), b"")?;
+    }
+
+    Ok(())
+}
+
+/// 将任意图片转换为游戏要求的正方形Logo
+pub fn prepare_logo(img: &DynamicImage, fit: LogoFit) -> DynamicImage {
+    match fit {
+        LogoFit::Crop => {
+            let (width, height) = img.dimensions();
+            crop_image(img, CropRect::centered_square(width, height))
+                .resize_exact(LOGO_SIZE, LOGO_SIZE, FilterType::Lanczos3)
+        },
+        LogoFit::Pad => pad_to_square(img, LOGO_SIZE, [0, 0, 0, 0]),
+    }
+}
+
+/// 按编辑参数生成Logo
+pub fn apply_logo_edit(img: &DynamicImage, edit: &LogoEdit) -> DynamicImage {
+    let (width, height) = img.dimensions();
+    let mut edited = crop_image(img, edit.crop.clamped(width, height));
+
+    if let Some(key) = edit.color_key {
+        edited = remove_background(&edited, key);
+    }
+
+    match edit.fit {
+        LogoFit::Crop => prepare_logo(&edited, LogoFit::Crop),
+        LogoFit::Pad => pad_to_square(&edited, LOGO_SIZE, edit.pad_color),
+    }
+}
+
+/// 裁剪图片
+pub fn crop_image(img: &DynamicImage, rect: CropRect) -> DynamicImage {
+    img.crop_imm(rect.x, rect.y, rect.width, rect.height)
+}
+
+/// 等比缩放到指定尺寸内，并用指定颜色填充为正方形
+pub fn pad_to_square(img: &DynamicImage, size: u32, color: [u8; 4]) -> DynamicImage {
+    let resized = img.resize(size, size, FilterType::Lanczos3);
+    let mut canvas = RgbaImage::from_pixel(size, size, image::Rgba(color));
+    let x = (size - resized.width()) / 2;
+    let y = (size - resized.height()) / 2;
+    image::imageops::overlay(&mut canvas, &resized.to_rgba8(), x as i64, y as i64);
+    DynamicImage::ImageRgba8(canvas)
+}
+
+/// 将与指定颜色相近的像素变为透明
+pub fn remove_background(img: &DynamicImage, key: ColorKey) -> DynamicImage {
+    let mut rgba = img.to_rgba8();
+
+    for pixel in rgba.pixels_mut() {
+        let matches = pixel.0[..3]
+            .iter()
+            .zip(key.color.iter())
+            .all(|(&a, &b)| a.abs_diff(b) <= key.tolerance);
+        if matches {
+            pixel.0[3] = 0;
+        }
+    }
+
+    DynamicImage::ImageRgba8(rgba)
+}
+
+/// 保存新的Logo，保存前会备份原始Logo
+pub fn save_logo(db_dir: &Path, team_id: i64, img: &DynamicImage) -> Result<PathBuf> {
+    backup_original(db_dir, team_id)?;
+
+    let path = logo_path(db_dir, team_id);
+    DynamicImage::ImageRgba8(img.to_rgba8()).save_with_format(&path, image::ImageFormat::Png)?;
+    info!("已保存Logo: {}", path.display());
+    Ok(path)
+}
+
+/// 导入图片文件作为球队Logo，保存前会备份原始Logo
+pub fn import_logo_file(db_dir: &Path, team_id: i64, src_path: &Path) -> Result<PathBuf> {
+    let img = image::open(src_path)?;
+    save_logo(db_dir, team_id, &prepare_logo(&img, LogoFit::Pad))
+}
+
+/// 从另一个目录复制球队Logo
+pub fn copy_logo(src_dir: &Path, src_team_id: i64, dst_dir: &Path, dst_team_id: i64) -> Result<PathBuf> {
+    let img = image::open(logo_path(src_dir, src_team_id))?;
+    save_logo(dst_dir, dst_team_id, &img)
+}
+
+/// 移除球队Logo，移除前会备份原始Logo
+pub fn remove_logo(db_dir: &Path, team_id: i64) -> Result<()> {
+    let path = logo_path(db_dir, team_id);
+    if !utils::file_exists(&path) {
+        return Ok(());
+    }
+
+    backup_original(db_dir, team_id)?;
+    std::fs::remove_file(&path)?;
+    info!("已移除Logo: {}", path.display());
+    Ok(())
+}
+
+/// 恢复首次修改前的原始Logo
+pub fn restore_original_logo(db_dir: &Path, team_id: i64) -> Result<()> {
+    let path = logo_path(db_dir, team_id);
+    let backup = backup_path(db_dir, team_id);
+    let marker = missing_marker_path(db_dir, team_id);
+
+    if utils::file_exists(&backup) {
+        std::fs::copy(&backup, &path)?;
+        std::fs::remove_file(&backup)?;
+    } else if utils::file_exists(&marker) {
+        if utils::file_exists(&path) {
+            std::fs::remove_file(&path)?;
+        }
+        std::fs::remove_file(&marker)?;
+    }
+
+    info!("已恢复原始Logo: {}", path.display());
+    Ok(())
+}
+
+/// 将图片上传为egui纹理
+pub fn texture_from_image(ctx: &Context, name: impl Into<String>, img: &DynamicImage) -> TextureHandle {
+    let size = [img.width() as usize, img.height() as usize];
+    let rgba8 = utils::image_to_rgba8_bytes(img);
+    ctx.load_texture(
+        name,
+        ColorImage::from_rgba_unmultiplied(size, &rgba8),
+        TextureOptions::LINEAR,
+    )
+}
+
+/// 加载球队Logo纹理，Logo不存在时返回None
+pub fn load_logo_texture(ctx: &Context, db_dir: &Path, team_id: i64, size: u32, name: &str) -> Result<Option<TextureHandle>> {
+    let path = logo_path(db_dir, team_id);
+    if !utils::file_exists(&path) {
+        return Ok(None);
+    }
+
+    let img = utils::load_and_resize_image(&path, size, size)?;
+    Ok(Some(texture_from_image(ctx, format!("{}_{}", name, team_id), &img)))
+}
+
+/// 加载任意图片为缩略图纹理，保持原始宽高比
+pub fn load_thumbnail_texture(ctx: &Context, path: &Path, size: u32, name: impl Into<String>) -> Result<TextureHandle> {
+    let img = utils::load_and_resize_image(path, size, size)?;
+    Ok(texture_from_image(ctx, name, &img))
+}
+
+/// 没有Logo时显示的占位图
+pub fn placeholder_texture(ctx: &Context) -> TextureHandle {
+    let size = 128;
+    let center = size as f32 / 2.0;
+    let radius = center - 4.0;
+    let mut pixels = Vec::with_capacity(size * size);
+
+    for y in 0..size {
+        for x in 0..size {
+            let dx = x as f32 + 0.5 - center;
+            let dy = y as f32 + 0.5 - center;
+            let distance = (dx * dx + dy * dy).sqrt();
+
+            let color = if distance > radius {
+                Color32::TRANSPARENT
+            } else if distance > radius - 3.0 {
+                Color32::from_rgb(190, 190, 190)
+            } else if (dx.abs() < 4.0 && dy.abs() < 28.0) || (dy.abs() < 4.0 && dx.abs() < 28.0) {
+                // 中间的加号
+                Color32::from_rgb(170, 170, 170)
+            } else {
+                Color32::from_rgb(235, 235, 235)
+            };
+            pixels.push(color);
+        }
+    }
+
+    ctx.load_texture(
+        "team_logo_placeholder",
+        ColorImage { size: [size, size], pixels },
+        TextureOptions::LINEAR,
+    )
+}
+
+/// 图片文件与球队的匹配方式
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum MatchKind {
+    Id,
+    ExactName,
+    FuzzyName(f32),
+    Manual,
+    Unmatched,
+}
+
+impl MatchKind {
+    pub fn as_str(&self) -> String {
+        match self {
+            MatchKind::Id => "ID".to_string(),
+            MatchKind::ExactName => t!("名称").to_string(),
+            MatchKind::FuzzyName(score) => t!("模糊 {:.0}%", score * 100.0),
+            MatchKind::Manual => t!("手动").to_string(),
+            MatchKind::Unmatched => t!("未匹配").to_string(),
+        }
+    }
+}
+
+/// 单个图片文件的匹配结果
+#[derive(Debug, Clone)]
+pub struct LogoMatch {
+    pub file: PathBuf,
+    pub team_id: Option<i64>,
+    pub kind: MatchKind,
+}
+
+/// 列出目录中可导入的图片文件
+pub fn list_image_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        let is_image = path.extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| IMAGE_EXTENSIONS.iter().any(|e| e.eq_ignore_ascii_case(ext)))
+            .unwrap_or(false);
+        if is_image && path.is_file() {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// 按ID、完整名称和模糊名称依次将图片文件匹配到球队
+pub fn match_logo_files(files: &[PathBuf], teams: &[Team]) -> Vec<LogoMatch> {
+    let normalized_names: Vec<String> = teams.iter()
+        .map(|t| normalize_name(&t.name))
+        .collect();
+
+    files.iter()
+        .map(|file| {
+            let stem = file.file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_default();
+
+            let (team_id, kind) = match_stem(&stem, teams, &normalized_names);
+            LogoMatch {
+                file: file.clone(),
+                team_id,
+                kind,
+            }
+        })
+        .collect()
+}
+
+fn match_stem(stem: &str, teams: &[Team], normalized_names: &[String]) -> (Option<i64>, MatchKind) {
+    // 文件名为 "123" 或 "L123"
+    let id_text = stem.strip_prefix('L').or_else(|| stem.strip_prefix('l')).unwrap_or(stem);
+    if let Ok(id) = id_text.trim().parse::<i64>() {
+        if teams.iter().any(|t| t.id == id) {
+            return (Some(id), MatchKind::Id);
+        }
+    }
+
+    let normalized = normalize_name(stem);
+    if normalized.is_empty() {
+        return (None, MatchKind::Unmatched);
+    }
+
+    if let Some(idx) = normalized_names.iter().position(|name| *name == normalized) {
+        return (Some(teams[idx].id), MatchKind::ExactName);
+    }
+
+    let best = normalized_names.iter()
+        .enumerate()
+        .map(|(idx, name)| (idx, name_similarity(&normalized, name)))
+        .max_by(|a, b| a.1.total_cmp(&b.1));
+
+    match best {
+        Some((idx, score)) if score >= FUZZY_MATCH_THRESHOLD => (Some(teams[idx].id), MatchKind::FuzzyName(score)),
+        _ => (None, MatchKind::Unmatched),
+    }
+}
+
+// 忽略大小写、空白和常见分隔符
+fn normalize_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| !c.is_whitespace() && !matches!(c, '_' | '-' | '.' | '·'))
+        .flat_map(|c| c.to_lowercase())
+        .collect()
+}
+
+// 基于编辑距离的相似度，范围 0.0-1.0
+fn name_similarity(a: &str, b: &str) -> f32 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let max_len = a.len().max(b.len());
+    if max_len == 0 {
+        return 1.0;
+    }
+
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == cb { 0 } else { 1 };
+            current[j + 1] = (previous[j] + cost)
+                .min(previous[j + 1] + 1)
+                .min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    1.0 - previous[b.len()] as f32 / max_len as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn imported_logo_is_padded_to_logo_size() {
+        let dir = std::env::temp_dir().join(format!("cfs_logo_import_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let src = dir.join("wide.png");
+        RgbaImage::from_pixel(300, 120, image::Rgba([200, 30, 30, 255])).save(&src).unwrap();
+
+        let path = import_logo_file(&dir, 7, &src).unwrap();
+        let saved = image::open(&path).unwrap();
+        assert_eq!(saved.dimensions(), (LOGO_SIZE, LOGO_SIZE));
+        // 上下填充的部分是透明的，中间保留原图
+        assert_eq!(saved.get_pixel(LOGO_SIZE / 2, 0).0[3], 0);
+        assert_eq!(saved.get_pixel(LOGO_SIZE / 2, LOGO_SIZE / 2).0, [200, 30, 30, 255]);
+        assert!(has_original_backup(&dir, 7));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
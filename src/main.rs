@@ -1,13 +1,25 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")] // 在发布模式下隐藏控制台窗口
 
+#[macro_use]
+mod i18n;
+
 mod app;
+mod balance;
+mod chart;
 mod data;
 mod error;
+mod fonts;
+mod generator;
+mod logo;
+mod report;
+mod settings;
+mod stats;
 mod ui;
 mod utils;
 
 use app::TeamEditorApp;
 use log::info;
+use settings::AppSettings;
 use std::path::PathBuf;
 
 fn main() -> eframe::Result<()> {
@@ -15,75 +27,29 @@ fn main() -> eframe::Result<()> {
     env_logger::init();
     info!("CFS球队编辑器启动");
 
+    // 命令行参数中的数据库路径
+    let initial_db_path = std::env::args_os().nth(1).map(PathBuf::from);
+    if let Some(path) = &initial_db_path {
+        info!("命令行指定数据库: {}", path.display());
+    }
+
+    // 界面语言需要在创建窗口前确定
+    let settings = AppSettings::load();
+    i18n::set_language(settings.language);
+
     let options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
             .with_inner_size([1100.0, 800.0])
             .with_min_inner_size([800.0, 500.0]),
         centered: true,
+        // 需要系统外观才能在“跟随系统”主题下切换浅色和深色
+        follow_system_theme: true,
         ..Default::default()
     };
 
     eframe::run_native(
-        "CFS球队编辑器 BY.卡尔纳斯",
+        t!(app::WINDOW_TITLE),
         options,
-        Box::new(|cc| {
-            // 加载中文字体
-            setup_custom_fonts(&cc.egui_ctx);
-            Box::new(TeamEditorApp::new(cc))
-        }),
+        Box::new(move |cc| Box::new(TeamEditorApp::new(cc, initial_db_path, settings))),
     )
 }
-
-fn setup_custom_fonts(ctx: &egui::Context) {
-    // 创建字体
-    let mut fonts = egui::FontDefinitions::default();
-    
-    // 尝试从系统加载中文字体
-    let font_paths = [
-        // Windows 系统字体
-        "C:/Windows/Fonts/msyh.ttc",      // 微软雅黑
-        "C:/Windows/Fonts/simhei.ttf",    // 黑体
-        "C:/Windows/Fonts/simsun.ttc",    // 宋体
-        // Linux 系统字体
-        "/usr/share/fonts/truetype/droid/DroidSansFallbackFull.ttf",
-        // macOS 系统字体
-        "/System/Library/Fonts/PingFang.ttc",
-    ];
-    
-    // 尝试加载字体
-    for path in &font_paths {
-        let path = PathBuf::from(path);
-        if path.exists() {
-            match std::fs::read(&path) {
-                Ok(font_data) => {
-                    // 添加字体
-                    fonts.font_data.insert(
-                        "chinese_font".to_owned(),
-                        egui::FontData::from_owned(font_data),
-                    );
-                    
-                    // 将中文字体设置为首选字体
-                    fonts.families
-                        .get_mut(&egui::FontFamily::Proportional)
-                        .unwrap()
-                        .insert(0, "chinese_font".to_owned());
-                    
-                    // 同时也支持等宽字体
-                    fonts.families
-                        .get_mut(&egui::FontFamily::Monospace)
-                        .unwrap()
-                        .push("chinese_font".to_owned());
-                    
-                    info!("已加载中文字体: {}", path.display());
-                    break;
-                }
-                Err(err) => {
-                    eprintln!("无法读取字体文件 {}: {}", path.display(), err);
-                }
-            }
-        }
-    }
-    
-    // 应用字体
-    ctx.set_fonts(fonts);
-}
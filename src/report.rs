@@ -0,0 +1,163 @@
+// 统计报告
+//
+// 生成独立的HTML文件，包含统计摘要、按联赛的分组统计、以SVG内嵌的图表和
+// 对应的数据表格，不依赖外部资源，可以直接发送给其他人查看。
+
+use std::path::Path;
+
+use log::info;
+
+use crate::chart::{escape_xml, Chart};
+use crate::data::team::{Leagues, Team};
+use crate::error::Result;
+use crate::i18n;
+use crate::stats::{self, GroupBy, GroupKey, StatField, Summary};
+
+// 报告中图表的尺寸
+const REPORT_CHART_WIDTH: u32 = 960;
+const REPORT_CHART_HEIGHT: u32 = 600;
+
+const STYLE: &str = r#"
+body { font-family: "Microsoft YaHei", "PingFang SC", sans-serif; margin: 32px auto; max-width: 1000px; color: #333; }
+h1 { color: #28285a; }
+h2 { color: #3c3c64; border-bottom: 1px solid #dcdce6; padding-bottom: 6px; margin-top: 40px; }
+.meta { color: #777; }
+table { border-collapse: collapse; margin: 12px 0; }
+th, td { border: 1px solid #e0e0ea; padding: 6px 12px; text-align: right; }
+th { background: #f0f0f6; color: #3c3c64; }
+td:first-child, th:first-child { text-align: left; }
+tr:nth-child(even) td { background: #fafafc; }
+svg { max-width: 100%; height: auto; border: 1px solid #e6e6f0; border-radius: 6px; }
+"#;
+
+/// 报告中的一个图表及其数据表格
+pub struct ReportSection {
+    pub chart: Chart,
+    /// 表格第一列的标题
+    pub category: String,
+    /// 表格第二列的标题
+    pub unit: String,
+    pub rows: Vec<(String, i64)>,
+}
+
+/// 生成报告的HTML文本
+pub fn build_html_report(
+    db_name: &str,
+    teams: &[Team],
+    leagues: &Leagues,
+    sections: &[ReportSection],
+    show_data_table: bool,
+    show_percentage: bool,
+) -> String {
+    let mut html = String::new();
+    html.push_str(&format!(
+        "<!DOCTYPE html>\n<html lang=\"{}\">\n<head>\n<meta charset=\"utf-8\">\n",
+        i18n::language().html_lang()
+    ));
+    html.push_str(&format!("<title>{}</title>\n", t!("{} - 球队数据报告", escape_xml(db_name))));
+    html.push_str(&format!("<style>{}</style>\n</head>\n<body>\n", STYLE));
+
+    html.push_str(&format!("<h1>{}</h1>\n", t!("球队数据报告")));
+    html.push_str(&format!("<p class=\"meta\">{}</p>\n", t!(
+        "数据库: {} · 球队数量: {} · 生成时间: {}",
+        escape_xml(db_name),
+        teams.len(),
+        chrono::Local::now().format("%Y-%m-%d %H:%M")
+    )));
+
+    push_summary(&mut html, teams);
+    push_league_stats(&mut html, teams, leagues);
+
+    for section in sections {
+        html.push_str(&format!("<h2>{}</h2>\n", escape_xml(&section.chart.title)));
+        html.push_str(&section.chart.to_svg(REPORT_CHART_WIDTH, REPORT_CHART_HEIGHT));
+
+        if show_data_table && !section.rows.is_empty() {
+            push_data_table(&mut html, section, show_percentage);
+        }
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+/// 生成报告并写入文件
+pub fn save_html_report(path: &Path, html: &str) -> Result<()> {
+    std::fs::write(path, html)?;
+    info!("已生成统计报告: {}", path.display());
+    Ok(())
+}
+
+fn push_summary(html: &mut String, teams: &[Team]) {
+    html.push_str(&format!("<h2>{}</h2>\n<table>\n<tr><th>{}</th>", t!("统计摘要"), t!("字段")));
+    push_summary_header(html);
+    html.push_str("</tr>\n");
+
+    for field in StatField::ALL {
+        html.push_str(&format!("<tr><td>{}</td>", field.as_str()));
+        push_summary_cells(html, Summary::of_teams(teams, field).as_ref());
+        html.push_str("</tr>\n");
+    }
+    html.push_str("</table>\n");
+}
+
+fn push_league_stats(html: &mut String, teams: &[Team], leagues: &Leagues) {
+    html.push_str(&format!("<h2>{}</h2>\n", t!("按联赛统计")));
+
+    for field in StatField::ALL {
+        html.push_str(&format!("<h3>{}</h3>\n<table>\n<tr><th>{}</th>", field.as_str(), t!("联赛")));
+        push_summary_header(html);
+        html.push_str("</tr>\n");
+
+        for group in stats::group_stats(teams, GroupBy::League) {
+            let name = match group.key {
+                GroupKey::League(league_id) => leagues.name(league_id),
+                GroupKey::Location(location) => location,
+            };
+            html.push_str(&format!("<tr><td>{}</td>", escape_xml(&name)));
+            push_summary_cells(html, group.summaries.get(&field));
+            html.push_str("</tr>\n");
+        }
+        html.push_str("</table>\n");
+    }
+}
+
+fn push_summary_header(html: &mut String) {
+    for title in [t!("数量"), t!("平均值"), t!("中位数"), t!("下四分位数"), t!("上四分位数"), t!("标准差"), t!("最小值"), t!("最大值")] {
+        html.push_str(&format!("<th>{}</th>", title));
+    }
+}
+
+fn push_summary_cells(html: &mut String, summary: Option<&Summary>) {
+    match summary {
+        Some(s) => html.push_str(&format!(
+            "<td>{}</td><td>{:.1}</td><td>{:.1}</td><td>{:.1}</td><td>{:.1}</td><td>{:.1}</td><td>{}</td><td>{}</td>",
+            s.count, s.mean, s.median, s.q1, s.q3, s.std_dev, s.min, s.max
+        )),
+        None => html.push_str(&"<td>-</td>".repeat(8)),
+    }
+}
+
+fn push_data_table(html: &mut String, section: &ReportSection, show_percentage: bool) {
+    let total: i64 = section.rows.iter().map(|(_, count)| count).sum();
+
+    html.push_str(&format!(
+        "<table>\n<tr><th>{}</th><th>{}</th>",
+        escape_xml(&section.category),
+        escape_xml(&section.unit)
+    ));
+    if show_percentage {
+        html.push_str(&format!("<th>{}</th>", t!("占比")));
+    }
+    html.push_str("</tr>\n");
+
+    for (label, count) in &section.rows {
+        html.push_str(&format!("<tr><td>{}</td><td>{}</td>", escape_xml(label), count));
+        if show_percentage {
+            let percentage = if total > 0 { *count as f64 / total as f64 * 100.0 } else { 0.0 };
+            html.push_str(&format!("<td>{:.1}%</td>", percentage));
+        }
+        html.push_str("</tr>\n");
+    }
+    html.push_str("</table>\n");
+}
@@ -0,0 +1,103 @@
+use std::path::{Path, PathBuf};
+
+use log::{error, info};
+use serde::{Deserialize, Serialize};
+
+use crate::error::Result;
+use crate::fonts::FontSettings;
+use crate::i18n::Language;
+use crate::ui::theme::ThemeSettings;
+
+// 最近打开文件列表的最大长度
+const MAX_RECENT_FILES: usize = 10;
+
+// 配置文件名
+const SETTINGS_FILE_NAME: &str = "settings.json";
+
+/// 应用程序的持久化设置
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppSettings {
+    pub recent_files: Vec<PathBuf>,
+    /// 以只读方式打开数据库，需要切换到编辑模式后才能修改
+    pub open_read_only: bool,
+    /// 界面语言
+    pub language: Language,
+    /// 界面字体、字号和缩放
+    pub fonts: FontSettings,
+    /// 主题模式和自定义配色
+    pub theme: ThemeSettings,
+}
+
+impl AppSettings {
+    /// 从配置目录加载设置，读取失败时返回默认设置
+    pub fn load() -> Self {
+        let path = settings_path();
+        if !path.exists() {
+            return AppSettings::default();
+        }
+
+        match std::fs::read_to_string(&path) {
+            Ok(content) => match serde_json::from_str(&content) {
+                Ok(settings) => settings,
+                Err(e) => {
+                    error!("解析设置文件失败: {}", e);
+                    AppSettings::default()
+                }
+            },
+            Err(e) => {
+                error!("读取设置文件失败: {}", e);
+                AppSettings::default()
+            }
+        }
+    }
+
+    /// 将设置保存到配置目录
+    pub fn save(&self) -> Result<()> {
+        let path = settings_path();
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+
+        let content = serde_json::to_string_pretty(self)?;
+        std::fs::write(&path, content)?;
+        info!("设置已保存: {}", path.display());
+        Ok(())
+    }
+
+    /// 将文件添加到最近打开列表的最前面
+    pub fn add_recent_file(&mut self, path: &Path) {
+        let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        self.recent_files.retain(|p| p != &path);
+        self.recent_files.insert(0, path);
+        self.recent_files.truncate(MAX_RECENT_FILES);
+    }
+
+    /// 从最近打开列表中移除文件
+    pub fn remove_recent_file(&mut self, path: &Path) {
+        self.recent_files.retain(|p| p != path);
+    }
+
+    /// 清空最近打开列表
+    pub fn clear_recent_files(&mut self) {
+        self.recent_files.clear();
+    }
+}
+
+/// 获取配置目录
+pub fn config_dir() -> PathBuf {
+    let base = if cfg!(windows) {
+        std::env::var_os("APPDATA").map(PathBuf::from)
+    } else {
+        std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+    };
+
+    base.unwrap_or_else(|| PathBuf::from("."))
+        .join("CFS_Editor")
+}
+
+fn settings_path() -> PathBuf {
+    config_dir().join(SETTINGS_FILE_NAME)
+}
@@ -0,0 +1,512 @@
+// 球队数据统计
+//
+// 计算平均值、中位数、四分位数、标准差等描述统计量，并支持按联赛或地区分组。
+// 同时提供直方图的分组区间计算（等宽、分位数、对数和自定义区间）。
+
+use std::collections::HashMap;
+
+use crate::data::staff::Staff;
+use crate::data::team::Team;
+
+/// 参与统计的球队数值字段
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum StatField {
+    Wealth,
+    SupporterCount,
+    FoundYear,
+}
+
+impl StatField {
+    pub const ALL: [StatField; 3] = [StatField::Wealth, StatField::SupporterCount, StatField::FoundYear];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StatField::Wealth => t!("球队财富"),
+            StatField::SupporterCount => t!("支持者数量"),
+            StatField::FoundYear => t!("成立年份"),
+        }
+    }
+
+    pub fn value(&self, team: &Team) -> i64 {
+        match self {
+            StatField::Wealth => team.wealth,
+            StatField::SupporterCount => team.supporter_count,
+            StatField::FoundYear => team.found_year,
+        }
+    }
+}
+
+/// 分组方式
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum GroupBy {
+    League,
+    Location,
+}
+
+impl GroupBy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GroupBy::League => t!("按联赛"),
+            GroupBy::Location => t!("按地区"),
+        }
+    }
+}
+
+/// 分组的键
+#[derive(Debug, PartialEq, Eq, Hash, Clone, PartialOrd, Ord)]
+pub enum GroupKey {
+    League(i64),
+    Location(String),
+}
+
+impl GroupKey {
+    pub fn of(team: &Team, group_by: GroupBy) -> Self {
+        match group_by {
+            GroupBy::League => GroupKey::League(team.league_id),
+            GroupBy::Location => GroupKey::Location(team.location.clone()),
+        }
+    }
+}
+
+/// 两个字段之间的皮尔逊相关系数，数据少于两组、某个字段没有变化或包含NaN时返回None
+pub fn correlation(pairs: &[(f64, f64)]) -> Option<f64> {
+    if pairs.len() < 2 {
+        return None;
+    }
+
+    let n = pairs.len() as f64;
+    let mean_x = pairs.iter().map(|p| p.0).sum::<f64>() / n;
+    let mean_y = pairs.iter().map(|p| p.1).sum::<f64>() / n;
+
+    let mut covariance = 0.0;
+    let mut var_x = 0.0;
+    let mut var_y = 0.0;
+    for &(x, y) in pairs {
+        covariance += (x - mean_x) * (y - mean_y);
+        var_x += (x - mean_x).powi(2);
+        var_y += (y - mean_y).powi(2);
+    }
+
+    // 写成大于0的比较，方差为NaN时同样返回None
+    if !(var_x > 0.0 && var_y > 0.0) {
+        return None;
+    }
+    Some(covariance / (var_x.sqrt() * var_y.sqrt()))
+}
+
+/// 一组数值的描述统计量
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Summary {
+    pub count: usize,
+    pub mean: f64,
+    pub median: f64,
+    pub q1: f64,
+    pub q3: f64,
+    pub std_dev: f64,
+    pub min: i64,
+    pub max: i64,
+}
+
+impl Summary {
+    /// 计算描述统计量，没有数据时返回None
+    pub fn from_values(values: &[i64]) -> Option<Self> {
+        if values.is_empty() {
+            return None;
+        }
+
+        let mut sorted = values.to_vec();
+        sorted.sort_unstable();
+
+        let count = sorted.len();
+        let mean = sorted.iter().map(|&v| v as f64).sum::<f64>() / count as f64;
+        // 总体标准差
+        let variance = sorted.iter()
+            .map(|&v| (v as f64 - mean).powi(2))
+            .sum::<f64>() / count as f64;
+
+        Some(Summary {
+            count,
+            mean,
+            median: quantile(&sorted, 0.5),
+            q1: quantile(&sorted, 0.25),
+            q3: quantile(&sorted, 0.75),
+            std_dev: variance.sqrt(),
+            min: sorted[0],
+            max: sorted[count - 1],
+        })
+    }
+
+    /// 计算一组球队某个字段的统计量
+    pub fn of_teams<'a>(teams: impl IntoIterator<Item = &'a Team>, field: StatField) -> Option<Self> {
+        let values: Vec<i64> = teams.into_iter().map(|team| field.value(team)).collect();
+        Summary::from_values(&values)
+    }
+}
+
+/// 已排序数据的分位数，在相邻两个值之间线性插值
+///
+/// 没有数据时返回0，q超出0到1时按边界计算，q为NaN时返回NaN。
+pub fn quantile(sorted: &[i64], q: f64) -> f64 {
+    if q.is_nan() {
+        return f64::NAN;
+    }
+    match sorted.len() {
+        0 => 0.0,
+        1 => sorted[0] as f64,
+        len => {
+            let position = q.clamp(0.0, 1.0) * (len - 1) as f64;
+            let lower = position.floor() as usize;
+            let upper = position.ceil() as usize;
+            let fraction = position - lower as f64;
+            // 先转换为浮点数再相减，避免极端值相减时溢出
+            sorted[lower] as f64 + (sorted[upper] as f64 - sorted[lower] as f64) * fraction
+        }
+    }
+}
+
+/// 一个分组中各字段的统计量
+#[derive(Debug, Clone)]
+pub struct GroupStats {
+    pub key: GroupKey,
+    pub count: usize,
+    pub summaries: HashMap<StatField, Summary>,
+}
+
+/// 按联赛或地区分组统计，结果按球队数量从多到少排列
+pub fn group_stats(teams: &[Team], group_by: GroupBy) -> Vec<GroupStats> {
+    let mut groups: HashMap<GroupKey, Vec<&Team>> = HashMap::new();
+    for team in teams {
+        groups.entry(GroupKey::of(team, group_by)).or_default().push(team);
+    }
+
+    let mut stats: Vec<GroupStats> = groups.into_iter()
+        .map(|(key, members)| {
+            let summaries = StatField::ALL.iter()
+                .filter_map(|&field| Summary::of_teams(members.iter().copied(), field).map(|s| (field, s)))
+                .collect();
+            GroupStats { key, count: members.len(), summaries }
+        })
+        .collect();
+
+    stats.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.key.cmp(&b.key)));
+    stats
+}
+
+/// 按字段取最高（descending为true）或最低的n个球队
+pub fn top_teams<'a>(teams: impl IntoIterator<Item = &'a Team>, field: StatField, n: usize, descending: bool) -> Vec<&'a Team> {
+    let mut sorted: Vec<&Team> = teams.into_iter().collect();
+    sorted.sort_by_key(|team| field.value(team));
+    if descending {
+        sorted.reverse();
+    }
+    sorted.truncate(n);
+    sorted
+}
+
+/// 直方图可选的数值字段，包括球队和员工的字段
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum ChartDimension {
+    TeamWealth,
+    TeamSupporterCount,
+    TeamFoundYear,
+    TeamLeagueId,
+    StaffFame,
+    StaffAbility,
+}
+
+impl ChartDimension {
+    pub const ALL: [ChartDimension; 6] = [
+        ChartDimension::TeamWealth,
+        ChartDimension::TeamSupporterCount,
+        ChartDimension::TeamFoundYear,
+        ChartDimension::TeamLeagueId,
+        ChartDimension::StaffFame,
+        ChartDimension::StaffAbility,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChartDimension::TeamWealth => t!("球队财富"),
+            ChartDimension::TeamSupporterCount => t!("球迷数量"),
+            ChartDimension::TeamFoundYear => t!("成立年份"),
+            ChartDimension::TeamLeagueId => t!("联赛ID"),
+            ChartDimension::StaffFame => t!("员工声望"),
+            ChartDimension::StaffAbility => t!("员工能力"),
+        }
+    }
+
+    pub fn is_staff(&self) -> bool {
+        matches!(self, ChartDimension::StaffFame | ChartDimension::StaffAbility)
+    }
+
+    /// 统计对象的名称，用于表头
+    pub fn unit(&self) -> &'static str {
+        if self.is_staff() { t!("员工数量") } else { t!("球队数量") }
+    }
+
+    /// 球队字段的取值，员工字段返回None
+    pub fn team_value(&self, team: &Team) -> Option<i64> {
+        match self {
+            ChartDimension::TeamWealth => Some(team.wealth),
+            ChartDimension::TeamSupporterCount => Some(team.supporter_count),
+            ChartDimension::TeamFoundYear => Some(team.found_year),
+            ChartDimension::TeamLeagueId => Some(team.league_id),
+            ChartDimension::StaffFame | ChartDimension::StaffAbility => None,
+        }
+    }
+
+    /// 员工字段的取值，球队字段或能力数据无法解析时返回None
+    pub fn staff_value(&self, staff: &Staff) -> Option<i64> {
+        match self {
+            ChartDimension::StaffFame => Some(staff.fame),
+            ChartDimension::StaffAbility => staff.get_ability().ok(),
+            _ => None,
+        }
+    }
+
+    /// 收集字段的所有取值，能力数据无法解析的员工会被跳过
+    pub fn values(&self, teams: &[Team], staff: &[Staff]) -> Vec<i64> {
+        if self.is_staff() {
+            staff.iter().filter_map(|s| self.staff_value(s)).collect()
+        } else {
+            teams.iter().filter_map(|t| self.team_value(t)).collect()
+        }
+    }
+}
+
+/// 直方图的分组方式
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum BucketMode {
+    /// 每个区间宽度相同
+    EqualWidth,
+    /// 每个区间包含数量大致相同的数据
+    Quantile,
+    /// 区间边界按对数增长，适合跨越多个数量级的数据
+    LogScale,
+    /// 用户手动编辑的区间
+    Custom,
+}
+
+impl BucketMode {
+    pub const ALL: [BucketMode; 4] = [BucketMode::EqualWidth, BucketMode::Quantile, BucketMode::LogScale, BucketMode::Custom];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BucketMode::EqualWidth => t!("等宽"),
+            BucketMode::Quantile => t!("分位数"),
+            BucketMode::LogScale => t!("对数"),
+            BucketMode::Custom => t!("自定义"),
+        }
+    }
+}
+
+/// 直方图的一个区间，包含两端
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Bucket {
+    pub min: i64,
+    pub max: i64,
+}
+
+impl Bucket {
+    pub fn new(min: i64, max: i64) -> Self {
+        Bucket { min, max }
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        value >= self.min && value <= self.max
+    }
+
+    pub fn label(&self) -> String {
+        if self.max == i64::MAX {
+            format!("{}+", self.min)
+        } else if self.min == self.max {
+            self.min.to_string()
+        } else {
+            format!("{}-{}", self.min, self.max)
+        }
+    }
+}
+
+/// 根据数据自动计算分组区间，count为期望的区间数量
+///
+/// 自定义模式没有自动区间，返回空列表。
+pub fn auto_buckets(values: &[i64], mode: BucketMode, count: usize) -> Vec<Bucket> {
+    if values.is_empty() || count == 0 {
+        return Vec::new();
+    }
+
+    let mut sorted = values.to_vec();
+    sorted.sort_unstable();
+    let min = sorted[0];
+    let max = sorted[sorted.len() - 1];
+
+    // 区间的起点，最后一个区间延伸到最大值
+    let starts: Vec<i64> = match mode {
+        BucketMode::EqualWidth => {
+            let span = (max as i128 - min as i128 + 1) as f64;
+            (0..count)
+                .map(|i| min + (span * i as f64 / count as f64).floor() as i64)
+                .collect()
+        },
+        BucketMode::Quantile => {
+            (0..count)
+                .map(|i| quantile(&sorted, i as f64 / count as f64).ceil() as i64)
+                .collect()
+        },
+        BucketMode::LogScale => {
+            // 平移到从1开始，这样包含0或负数的数据也能按对数分组
+            let span = (max as f64 - min as f64 + 1.0).log10();
+            (0..count)
+                .map(|i| min + (10f64.powf(span * i as f64 / count as f64) - 1.0).floor() as i64)
+                .collect()
+        },
+        BucketMode::Custom => return Vec::new(),
+    };
+
+    let mut starts = starts;
+    starts[0] = min;
+    starts.dedup();
+
+    starts.iter()
+        .enumerate()
+        .map(|(i, &start)| {
+            let end = starts.get(i + 1).map_or(max, |next| next - 1);
+            Bucket::new(start, end)
+        })
+        .collect()
+}
+
+/// 统计每个区间中的数据数量，不在任何区间内的数据不计入
+pub fn count_in_buckets(values: &[i64], buckets: &[Bucket]) -> Vec<i64> {
+    let mut counts = vec![0; buckets.len()];
+    for &value in values {
+        if let Some(i) = buckets.iter().position(|b| b.contains(value)) {
+            counts[i] += 1;
+        }
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 区间应从最小值开始、到最大值结束，并且首尾相接、互不重叠
+    fn assert_covers(buckets: &[Bucket], min: i64, max: i64) {
+        assert_eq!(buckets.first().map(|b| b.min), Some(min));
+        assert_eq!(buckets.last().map(|b| b.max), Some(max));
+        for bucket in buckets {
+            assert!(bucket.min <= bucket.max, "{:?}", bucket);
+        }
+        for pair in buckets.windows(2) {
+            assert_eq!(pair[0].max + 1, pair[1].min, "{:?}", pair);
+        }
+    }
+
+    #[test]
+    fn quantile_of_empty_and_single_values() {
+        assert_eq!(quantile(&[], 0.5), 0.0);
+        assert_eq!(quantile(&[7], 0.0), 7.0);
+        assert_eq!(quantile(&[7], 0.5), 7.0);
+        assert_eq!(quantile(&[7], 1.0), 7.0);
+    }
+
+    #[test]
+    fn quantile_interpolates_between_values() {
+        let sorted = [1, 2, 3, 4];
+        assert_eq!(quantile(&sorted, 0.0), 1.0);
+        assert_eq!(quantile(&sorted, 0.5), 2.5);
+        assert_eq!(quantile(&sorted, 1.0), 4.0);
+        assert!((quantile(&sorted, 0.25) - 1.75).abs() < 1e-9);
+    }
+
+    #[test]
+    fn quantile_of_equal_values() {
+        let sorted = [5; 10];
+        for q in [0.0, 0.1, 0.5, 0.9, 1.0] {
+            assert_eq!(quantile(&sorted, q), 5.0);
+        }
+    }
+
+    #[test]
+    fn quantile_clamps_out_of_range_and_rejects_nan() {
+        let sorted = [10, 20, 30];
+        assert_eq!(quantile(&sorted, -1.0), 10.0);
+        assert_eq!(quantile(&sorted, 2.0), 30.0);
+        assert!(quantile(&sorted, f64::NAN).is_nan());
+        assert!(quantile(&[], f64::NAN).is_nan());
+    }
+
+    #[test]
+    fn quantile_does_not_overflow_on_extreme_values() {
+        let sorted = [i64::MIN, i64::MAX];
+        assert!(quantile(&sorted, 0.5).abs() < 1e6);
+    }
+
+    #[test]
+    fn auto_buckets_of_empty_values() {
+        for mode in BucketMode::ALL {
+            assert!(auto_buckets(&[], mode, 5).is_empty());
+        }
+        assert!(auto_buckets(&[1, 2, 3], BucketMode::EqualWidth, 0).is_empty());
+    }
+
+    #[test]
+    fn auto_buckets_of_single_value() {
+        for mode in [BucketMode::EqualWidth, BucketMode::Quantile, BucketMode::LogScale] {
+            assert_eq!(auto_buckets(&[42], mode, 5), vec![Bucket::new(42, 42)], "{:?}", mode);
+        }
+    }
+
+    #[test]
+    fn auto_buckets_of_equal_values() {
+        let values = [-3; 20];
+        for mode in [BucketMode::EqualWidth, BucketMode::Quantile, BucketMode::LogScale] {
+            assert_eq!(auto_buckets(&values, mode, 4), vec![Bucket::new(-3, -3)], "{:?}", mode);
+        }
+    }
+
+    #[test]
+    fn auto_buckets_cover_all_values() {
+        let values: Vec<i64> = (0..200).map(|i| (i * i) % 997 - 100).collect();
+        let min = *values.iter().min().unwrap();
+        let max = *values.iter().max().unwrap();
+
+        for mode in [BucketMode::EqualWidth, BucketMode::Quantile, BucketMode::LogScale] {
+            let buckets = auto_buckets(&values, mode, 8);
+            assert!(!buckets.is_empty() && buckets.len() <= 8, "{:?}", mode);
+            assert_covers(&buckets, min, max);
+
+            let counts = count_in_buckets(&values, &buckets);
+            assert_eq!(counts.iter().sum::<i64>(), values.len() as i64, "{:?}", mode);
+        }
+    }
+
+    #[test]
+    fn auto_buckets_equal_width() {
+        let values: Vec<i64> = (1..=100).collect();
+        let buckets = auto_buckets(&values, BucketMode::EqualWidth, 4);
+        assert_eq!(buckets, vec![
+            Bucket::new(1, 25),
+            Bucket::new(26, 50),
+            Bucket::new(51, 75),
+            Bucket::new(76, 100),
+        ]);
+    }
+
+    #[test]
+    fn auto_buckets_custom_mode_is_empty() {
+        assert!(auto_buckets(&[1, 2, 3], BucketMode::Custom, 3).is_empty());
+    }
+
+    #[test]
+    fn correlation_edge_cases() {
+        assert_eq!(correlation(&[]), None);
+        assert_eq!(correlation(&[(1.0, 2.0)]), None);
+        assert_eq!(correlation(&[(1.0, 2.0), (1.0, 3.0)]), None);
+        assert_eq!(correlation(&[(1.0, f64::NAN), (2.0, 3.0)]), None);
+
+        let r = correlation(&[(1.0, 2.0), (2.0, 4.0), (3.0, 6.0)]).unwrap();
+        assert!((r - 1.0).abs() < 1e-9);
+    }
+}
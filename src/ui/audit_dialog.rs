@@ -0,0 +1,181 @@
+// 审计日志查看对话框
+//
+// 按时间倒序显示审计日志，可以按表、日期范围和关键字筛选，并把筛选结果导出为CSV。
+
+use egui::{ComboBox, Context, Grid, RichText, ScrollArea};
+
+use crate::data::audit::{self, AuditEntry};
+use crate::ui::dialogs::setup_mac_window;
+use crate::ui::widgets;
+use crate::ui::theme;
+
+// 表格中最多显示的记录数
+const MAX_ROWS: usize = 1000;
+
+pub struct AuditDialog {
+    pub open: bool,
+    /// 为true时应用把筛选结果导出
+    pub export_requested: bool,
+    pub entries: Vec<AuditEntry>,
+    pub log_path: String,
+    pub entity_filter: Option<String>,
+    pub search_text: String,
+    /// 日期范围，格式为YYYY-MM-DD，为空时不限制
+    pub date_from: String,
+    pub date_to: String,
+}
+
+impl AuditDialog {
+    pub fn new() -> Self {
+        AuditDialog {
+            open: false,
+            export_requested: false,
+            entries: Vec::new(),
+            log_path: String::new(),
+            entity_filter: None,
+            search_text: String::new(),
+            date_from: String::new(),
+            date_to: String::new(),
+        }
+    }
+
+    pub fn open(&mut self, mut entries: Vec<AuditEntry>, log_path: String) {
+        // 最新的记录显示在最前面
+        entries.reverse();
+        self.entries = entries;
+        self.log_path = log_path;
+        self.export_requested = false;
+        self.open = true;
+    }
+
+    /// 符合当前筛选条件的记录
+    pub fn filtered_entries(&self) -> Vec<&AuditEntry> {
+        let search = self.search_text.trim().to_lowercase();
+        let date_from = self.date_from.trim();
+        let date_to = self.date_to.trim();
+
+        self.entries.iter()
+            .filter(|entry| self.entity_filter.as_ref().is_none_or(|entity| &entry.entity == entity))
+            .filter(|entry| {
+                let date = entry.timestamp.get(..10).unwrap_or(&entry.timestamp);
+                (date_from.is_empty() || date >= date_from) && (date_to.is_empty() || date <= date_to)
+            })
+            .filter(|entry| {
+                search.is_empty()
+                    || entry.id.to_lowercase().contains(&search)
+                    || entry.field.to_lowercase().contains(&search)
+                    || entry.old_text().to_lowercase().contains(&search)
+                    || entry.new_text().to_lowercase().contains(&search)
+            })
+            .collect()
+    }
+
+    // 日志中出现过的表
+    fn entities(&self) -> Vec<String> {
+        let mut entities: Vec<String> = self.entries.iter().map(|e| e.entity.clone()).collect();
+        entities.sort();
+        entities.dedup();
+        entities
+    }
+
+    /// 显示对话框，点击导出时返回true
+    pub fn show(&mut self, ctx: &Context) -> bool {
+        if !self.open {
+            return false;
+        }
+
+        let mut closed = false;
+        let mut export = false;
+
+        setup_mac_window(t!("审计日志"))
+            .fixed_size([860.0, 580.0])
+            .show(ctx, |ui| {
+                ui.add_space(5.0);
+                ui.heading(t!("审计日志"));
+                ui.label(RichText::new(&self.log_path).small().color(theme::palette().weak_text));
+                ui.add_space(5.0);
+                widgets::horizontal_separator(ui);
+                ui.add_space(10.0);
+
+                ui.horizontal(|ui| {
+                    ui.label(t!("表:"));
+                    let selected = self.entity_filter.as_deref().map_or(t!("全部"), audit::entity_label).to_string();
+                    ComboBox::from_id_source("audit_entity")
+                        .selected_text(selected)
+                        .width(100.0)
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut self.entity_filter, None, t!("全部"));
+                            for entity in self.entities() {
+                                let label = audit::entity_label(&entity).to_string();
+                                ui.selectable_value(&mut self.entity_filter, Some(entity), label);
+                            }
+                        });
+
+                    ui.add_space(10.0);
+                    ui.label(t!("日期:"));
+                    ui.add(egui::TextEdit::singleline(&mut self.date_from).hint_text("YYYY-MM-DD").desired_width(90.0));
+                    ui.label(t!("至"));
+                    ui.add(egui::TextEdit::singleline(&mut self.date_to).hint_text("YYYY-MM-DD").desired_width(90.0));
+
+                    ui.add_space(10.0);
+                    ui.label(t!("搜索:"));
+                    ui.add(egui::TextEdit::singleline(&mut self.search_text).hint_text(t!("主键、字段或值")).desired_width(160.0));
+                });
+                ui.add_space(10.0);
+
+                let filtered = self.filtered_entries();
+                ScrollArea::both().max_height(400.0).show(ui, |ui| {
+                    Grid::new("audit_entries")
+                        .num_columns(6)
+                        .striped(true)
+                        .spacing([16.0, 4.0])
+                        .show(ui, |ui| {
+                            for title in [t!("时间"), t!("表"), t!("主键"), t!("字段"), t!("原值"), t!("新值")] {
+                                ui.strong(title);
+                            }
+                            ui.end_row();
+
+                            for entry in filtered.iter().take(MAX_ROWS) {
+                                ui.label(&entry.timestamp);
+                                ui.label(audit::entity_label(&entry.entity));
+                                ui.label(&entry.id);
+                                ui.label(&entry.field);
+                                ui.label(entry.old_text());
+                                ui.label(entry.new_text());
+                                ui.end_row();
+                            }
+                        });
+                });
+
+                ui.add_space(10.0);
+                widgets::horizontal_separator(ui);
+
+                ui.horizontal(|ui| {
+                    let mut summary = t!("共 {} 条记录，筛选后 {} 条", self.entries.len(), filtered.len());
+                    if filtered.len() > MAX_ROWS {
+                        summary.push_str(&t!("（仅显示前 {} 条）", MAX_ROWS));
+                    }
+                    ui.label(summary);
+
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if widgets::mac_button(ui, t!("关闭")) {
+                            closed = true;
+                        }
+
+                        ui.add_space(10.0);
+
+                        if widgets::mac_primary_button(ui, t!("导出CSV")) && !filtered.is_empty() {
+                            export = true;
+                        }
+                    });
+                });
+            });
+
+        if closed {
+            self.open = false;
+        }
+
+        self.export_requested = export;
+        export
+    }
+}
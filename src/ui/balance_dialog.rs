@@ -0,0 +1,342 @@
+// 联赛经济平衡对话框
+//
+// 选择联赛和目标分布后预览每个球队的新财富，与当前财富并排显示在图表中，
+// 确认后通过批量保存写入数据库。
+
+use egui::{Align2, Color32, ComboBox, Context, FontId, Grid, Rect, RichText, Rounding, ScrollArea, Sense, Stroke, Ui, Vec2, pos2};
+
+use crate::balance::{self, BalanceParams, Proposal, WealthCurve};
+use crate::data::team::{Leagues, Team};
+use crate::ui::dialogs::setup_mac_window;
+use crate::ui::widgets;
+use crate::ui::theme;
+
+const CURRENT_COLOR: Color32 = Color32::from_rgb(170, 170, 185);
+const PROPOSED_COLOR: Color32 = Color32::from_rgb(70, 120, 230);
+
+pub struct BalanceDialog {
+    pub open: bool,
+    pub confirmed: bool,
+    pub teams: Vec<Team>,
+    pub leagues: Leagues,
+    pub league_id: Option<i64>,
+    pub curve: WealthCurve,
+    pub steepness: f64,
+    /// 为true时目标总财富等于联赛当前的总财富
+    pub keep_total: bool,
+    pub total_text: String,
+    pub min_wealth_text: String,
+    proposals: Vec<Proposal>,
+    error_message: Option<String>,
+}
+
+impl BalanceDialog {
+    pub fn new() -> Self {
+        BalanceDialog {
+            open: false,
+            confirmed: false,
+            teams: Vec::new(),
+            leagues: Leagues::default(),
+            league_id: None,
+            curve: WealthCurve::TopHeavy,
+            steepness: 1.0,
+            keep_total: true,
+            total_text: String::new(),
+            min_wealth_text: "0".to_string(),
+            proposals: Vec::new(),
+            error_message: None,
+        }
+    }
+
+    /// 打开对话框，league_id为默认选中的联赛
+    pub fn open(&mut self, teams: Vec<Team>, leagues: Leagues, league_id: Option<i64>) {
+        self.teams = teams;
+        self.leagues = leagues;
+        self.league_id = league_id.or_else(|| self.league_ids().first().copied());
+        self.confirmed = false;
+        self.error_message = None;
+        self.total_text = self.current_total().to_string();
+        self.open = true;
+    }
+
+    /// 财富有变化的球队
+    pub fn get_modified_teams(&self) -> Vec<Team> {
+        self.proposals.iter()
+            .filter(|p| p.change() != 0)
+            .filter_map(|p| {
+                let mut team = self.teams.iter().find(|t| t.id == p.team_id)?.clone();
+                team.wealth = p.proposed;
+                Some(team)
+            })
+            .collect()
+    }
+
+    pub fn league_name(&self) -> String {
+        self.league_id.map(|id| self.leagues.name(id)).unwrap_or_default()
+    }
+
+    // 有球队的联赛，按名称排列
+    fn league_ids(&self) -> Vec<i64> {
+        let mut ids: Vec<i64> = self.teams.iter().map(|t| t.league_id).collect();
+        ids.sort_unstable();
+        ids.dedup();
+        self.leagues.sort_by_name(&mut ids);
+        ids
+    }
+
+    fn league_teams(&self) -> Vec<&Team> {
+        self.teams.iter()
+            .filter(|t| Some(t.league_id) == self.league_id)
+            .collect()
+    }
+
+    fn current_total(&self) -> i64 {
+        self.league_teams().iter().map(|t| t.wealth).sum()
+    }
+
+    // 根据当前输入重新计算方案，输入无效时清空方案并返回错误
+    fn update_proposals(&mut self) {
+        self.proposals.clear();
+        self.error_message = None;
+
+        let total_wealth = if self.keep_total {
+            self.current_total()
+        } else {
+            match self.total_text.trim().parse::<i64>() {
+                Ok(total) if total >= 0 => total,
+                _ => {
+                    self.error_message = Some(t!("目标总财富必须是非负整数").to_string());
+                    return;
+                }
+            }
+        };
+
+        let min_wealth = match self.min_wealth_text.trim().parse::<i64>() {
+            Ok(min) if min >= 0 => min,
+            _ => {
+                self.error_message = Some(t!("最低财富必须是非负整数").to_string());
+                return;
+            }
+        };
+
+        let params = BalanceParams {
+            curve: self.curve,
+            total_wealth,
+            min_wealth,
+            steepness: self.steepness,
+        };
+        self.proposals = balance::propose(&self.league_teams(), &params);
+    }
+
+    pub fn show(&mut self, ctx: &Context) -> bool {
+        if !self.open {
+            return false;
+        }
+
+        self.update_proposals();
+
+        let mut closed = false;
+        let mut confirmed = false;
+
+        setup_mac_window(t!("联赛经济平衡"))
+            .fixed_size([820.0, 600.0])
+            .show(ctx, |ui| {
+                ui.add_space(5.0);
+                ui.heading(t!("重新分配联赛财富"));
+                ui.add_space(5.0);
+                widgets::horizontal_separator(ui);
+                ui.add_space(10.0);
+
+                self.show_controls(ui);
+                ui.add_space(10.0);
+
+                if let Some(message) = &self.error_message {
+                    widgets::error_message(ui, message);
+                } else if self.proposals.is_empty() {
+                    ui.label(t!("该联赛没有球队"));
+                } else {
+                    draw_comparison_chart(ui, &self.proposals, 220.0);
+                    ui.add_space(10.0);
+                    self.show_table(ui);
+                }
+
+                ui.add_space(10.0);
+                widgets::horizontal_separator(ui);
+
+                let changed = self.proposals.iter().filter(|p| p.change() != 0).count();
+                ui.horizontal(|ui| {
+                    ui.label(t!("将修改 {} 个球队的财富", changed));
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if widgets::mac_primary_button(ui, t!("应用")) && changed > 0 {
+                            confirmed = true;
+                            closed = true;
+                        }
+
+                        ui.add_space(10.0);
+
+                        if widgets::mac_button(ui, t!("取消")) {
+                            closed = true;
+                        }
+                    });
+                });
+            });
+
+        if closed {
+            self.open = false;
+        }
+
+        self.confirmed = confirmed;
+        confirmed
+    }
+
+    fn show_controls(&mut self, ui: &mut Ui) {
+        Grid::new("balance_controls")
+            .num_columns(2)
+            .spacing([10.0, 8.0])
+            .show(ui, |ui| {
+                ui.label(t!("联赛:"));
+                let previous = self.league_id;
+                ComboBox::from_id_source("balance_league")
+                    .selected_text(self.league_name())
+                    .width(200.0)
+                    .show_ui(ui, |ui| {
+                        for league_id in self.league_ids() {
+                            ui.selectable_value(&mut self.league_id, Some(league_id), self.leagues.name(league_id))
+                                .on_hover_text(self.leagues.hover_text(league_id));
+                        }
+                    });
+                if self.league_id != previous {
+                    self.total_text = self.current_total().to_string();
+                }
+                ui.end_row();
+
+                ui.label(t!("目标分布:"));
+                ui.horizontal(|ui| {
+                    for curve in WealthCurve::ALL {
+                        ui.selectable_value(&mut self.curve, curve, curve.as_str())
+                            .on_hover_text(curve.description());
+                    }
+                });
+                ui.end_row();
+
+                ui.label(t!("陡峭度:"));
+                ui.add_enabled(
+                    self.curve.uses_steepness(),
+                    egui::Slider::new(&mut self.steepness, 0.0..=3.0).step_by(0.1),
+                );
+                ui.end_row();
+
+                ui.label(t!("目标总财富:"));
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut self.keep_total, t!("保持当前总额"));
+                    ui.add_enabled(!self.keep_total, egui::TextEdit::singleline(&mut self.total_text).desired_width(120.0));
+                    ui.label(t!("当前: {}", self.current_total()));
+                });
+                ui.end_row();
+
+                ui.label(t!("最低财富:"));
+                ui.add(egui::TextEdit::singleline(&mut self.min_wealth_text).desired_width(120.0));
+                ui.end_row();
+            });
+
+        ui.label(RichText::new(self.curve.description()).small().color(theme::palette().weak_text));
+    }
+
+    fn show_table(&self, ui: &mut Ui) {
+        let palette = theme::palette();
+        ScrollArea::vertical().max_height(200.0).show(ui, |ui| {
+            Grid::new("balance_proposals")
+                .num_columns(4)
+                .striped(true)
+                .spacing([20.0, 4.0])
+                .show(ui, |ui| {
+                    ui.strong(t!("球队"));
+                    ui.strong(t!("当前财富"));
+                    ui.strong(t!("新财富"));
+                    ui.strong(t!("变化"));
+                    ui.end_row();
+
+                    for proposal in &self.proposals {
+                        ui.label(&proposal.name);
+                        ui.label(proposal.current.to_string());
+                        ui.label(proposal.proposed.to_string());
+
+                        let change = proposal.change();
+                        let color = if change > 0 {
+                            palette.success
+                        } else if change < 0 {
+                            palette.error
+                        } else {
+                            palette.weak_text
+                        };
+                        let percentage = if proposal.current != 0 {
+                            format!(" ({:+.1}%)", change as f64 / proposal.current as f64 * 100.0)
+                        } else {
+                            String::new()
+                        };
+                        ui.colored_label(color, format!("{:+}{}", change, percentage));
+                        ui.end_row();
+                    }
+                });
+        });
+    }
+}
+
+// 每个球队的当前财富和新财富并排显示的条形图
+fn draw_comparison_chart(ui: &mut Ui, proposals: &[Proposal], height: f32) {
+    let palette = theme::palette();
+    let (response, painter) = ui.allocate_painter(Vec2::new(ui.available_width(), height), Sense::hover());
+    let rect = response.rect;
+
+    painter.rect_filled(rect, Rounding::same(6.0), palette.surface_alt);
+    painter.rect_stroke(rect, Rounding::same(6.0), Stroke::new(1.0, palette.border));
+
+    // 图例
+    let legend_y = rect.min.y + 12.0;
+    for (i, (label, color)) in [(t!("当前财富"), CURRENT_COLOR), (t!("新财富"), PROPOSED_COLOR)].iter().enumerate() {
+        let x = rect.max.x - 170.0 + i as f32 * 85.0;
+        painter.rect_filled(Rect::from_min_size(pos2(x, legend_y - 5.0), Vec2::splat(10.0)), Rounding::same(2.0), *color);
+        painter.text(pos2(x + 14.0, legend_y), Align2::LEFT_CENTER, *label, FontId::proportional(11.0), palette.weak_text);
+    }
+
+    let max_value = proposals.iter()
+        .map(|p| p.current.max(p.proposed))
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    let plot = Rect::from_min_max(pos2(rect.min.x + 10.0, rect.min.y + 28.0), pos2(rect.max.x - 10.0, rect.max.y - 10.0));
+    let slot = plot.width() / proposals.len() as f32;
+    let bar_width = (slot * 0.4).min(24.0);
+
+    let hovered = response.hover_pos()
+        .filter(|pos| plot.contains(*pos))
+        .map(|pos| (((pos.x - plot.min.x) / slot) as usize).min(proposals.len() - 1));
+
+    for (i, proposal) in proposals.iter().enumerate() {
+        let center = plot.min.x + slot * (i as f32 + 0.5);
+        if hovered == Some(i) {
+            painter.rect_filled(
+                Rect::from_min_max(pos2(center - slot / 2.0, plot.min.y), pos2(center + slot / 2.0, plot.max.y)),
+                Rounding::ZERO,
+                palette.highlight,
+            );
+        }
+
+        for (offset, value, color) in [(-bar_width, proposal.current, CURRENT_COLOR), (0.0, proposal.proposed, PROPOSED_COLOR)] {
+            let bar_height = value.max(0) as f32 / max_value as f32 * plot.height();
+            painter.rect_filled(
+                Rect::from_min_max(pos2(center + offset, plot.max.y - bar_height), pos2(center + offset + bar_width, plot.max.y)),
+                Rounding::same(2.0),
+                color,
+            );
+        }
+    }
+
+    if let Some(proposal) = hovered.map(|i| &proposals[i]) {
+        egui::show_tooltip_at_pointer(ui.ctx(), egui::Id::new("balance_chart_tooltip"), |ui| {
+            ui.strong(&proposal.name);
+            ui.label(t!("当前财富: {}", proposal.current));
+            ui.label(t!("新财富: {}", proposal.proposed));
+        });
+    }
+}
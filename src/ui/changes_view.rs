@@ -0,0 +1,183 @@
+// 打开后的修改
+//
+// 显示当前数据与打开数据库时快照的逐字段差异，可以把单行或单个字段还原为原始值。
+// 对比在后台线程中执行，数据有写入后标记为过期，下次显示时重新对比。
+
+use egui::{Grid, RichText, ScrollArea, Ui};
+
+use crate::data::snapshot::{self, ChangeKind, Revert, RowChange, SnapshotTable};
+use crate::ui::widgets;
+use crate::ui::theme;
+
+/// 需要由应用处理的操作
+pub enum ChangesAction {
+    Refresh,
+    Revert(Vec<Revert>),
+}
+
+pub struct ChangesView {
+    pub changes: Vec<RowChange>,
+    /// 快照的创建时间
+    pub taken_at: Option<String>,
+    pub table_filter: Option<SnapshotTable>,
+    stale: bool,
+    waiting: bool,
+}
+
+impl ChangesView {
+    pub fn new() -> Self {
+        ChangesView {
+            changes: Vec::new(),
+            taken_at: None,
+            table_filter: None,
+            stale: true,
+            waiting: false,
+        }
+    }
+
+    /// 数据库有写入，下次显示时重新对比
+    pub fn invalidate(&mut self) {
+        self.stale = true;
+    }
+
+    pub fn set_changes(&mut self, changes: Vec<RowChange>, taken_at: String) {
+        self.changes = changes;
+        self.taken_at = Some(taken_at);
+        self.waiting = false;
+    }
+
+    /// 对比失败时停止等待结果
+    pub fn stop_waiting(&mut self) {
+        self.waiting = false;
+    }
+
+    fn refresh(&mut self) -> ChangesAction {
+        self.stale = false;
+        self.waiting = true;
+        ChangesAction::Refresh
+    }
+
+    pub fn ui(&mut self, ui: &mut Ui) -> Option<ChangesAction> {
+        let mut action = None;
+        if self.stale && !self.waiting {
+            action = Some(self.refresh());
+        }
+
+        widgets::titled_frame(t!("打开后的修改"), ui, |ui| {
+            ui.horizontal(|ui| {
+                match &self.taken_at {
+                    Some(taken_at) => ui.label(t!("与 {} 打开数据库时的数据对比", taken_at)),
+                    None => ui.label(t!("尚未对比")),
+                };
+                if self.waiting {
+                    ui.spinner();
+                }
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if widgets::mac_button(ui, t!("刷新")) {
+                        action = Some(self.refresh());
+                    }
+                });
+            });
+            ui.add_space(5.0);
+
+            // 按表筛选
+            ui.horizontal(|ui| {
+                let total = self.changes.len();
+                ui.selectable_value(&mut self.table_filter, None, t!("全部 ({})", total));
+                for table in SnapshotTable::ALL {
+                    let count = self.changes.iter().filter(|c| c.table == table).count();
+                    ui.selectable_value(&mut self.table_filter, Some(table), format!("{} ({})", table.as_str(), count));
+                }
+            });
+            ui.add_space(5.0);
+            widgets::horizontal_separator(ui);
+
+            let visible: Vec<&RowChange> = self.changes.iter()
+                .filter(|c| self.table_filter.is_none_or(|table| c.table == table))
+                .collect();
+            if visible.is_empty() {
+                ui.add_space(20.0);
+                ui.vertical_centered(|ui| {
+                    ui.label(if self.waiting { t!("正在对比...") } else { t!("没有修改") });
+                });
+                return;
+            }
+
+            ScrollArea::vertical().show(ui, |ui| {
+                for change in visible {
+                    if let Some(revert) = show_row_change(ui, change) {
+                        action = Some(ChangesAction::Revert(vec![revert]));
+                    }
+                    ui.add_space(4.0);
+                }
+            });
+        });
+
+        action
+    }
+}
+
+// 显示一行的变化，点击还原按钮时返回对应的操作
+fn show_row_change(ui: &mut Ui, change: &RowChange) -> Option<Revert> {
+    let palette = theme::palette();
+    let mut revert = None;
+    let key = snapshot::format_value(&change.key);
+    let color = match change.kind {
+        ChangeKind::Added => palette.success,
+        ChangeKind::Removed => palette.error,
+        ChangeKind::Modified => palette.accent_text,
+    };
+
+    ui.horizontal(|ui| {
+        ui.colored_label(color, format!("[{}]", change.kind.as_str()));
+        let header = t!(
+            "{} {} (主键: {}) · {} 个字段",
+            change.table.as_str(),
+            change.label,
+            key,
+            change.fields.len()
+        );
+        let id = ui.make_persistent_id((change.table.table_name(), &key));
+        egui::CollapsingHeader::new(header)
+            .id_source(id)
+            .show(ui, |ui| {
+                Grid::new(id.with("fields"))
+                    .num_columns(4)
+                    .striped(true)
+                    .spacing([16.0, 4.0])
+                    .show(ui, |ui| {
+                        ui.strong(t!("字段"));
+                        ui.strong(t!("原始值"));
+                        ui.strong(t!("当前值"));
+                        ui.label("");
+                        ui.end_row();
+
+                        for field in &change.fields {
+                            ui.label(&field.column);
+                            ui.label(snapshot::format_value(&field.original));
+                            ui.label(RichText::new(snapshot::format_value(&field.current)).color(color));
+                            // 只有修改的行可以单独还原字段
+                            if change.kind == ChangeKind::Modified && ui.small_button(t!("还原")).clicked() {
+                                revert = Some(Revert::Field {
+                                    table: change.table,
+                                    key: change.key.clone(),
+                                    column: field.column.clone(),
+                                });
+                            }
+                            ui.end_row();
+                        }
+                    });
+            });
+
+        let button_text = match change.kind {
+            ChangeKind::Added => t!("删除此行"),
+            ChangeKind::Removed => t!("恢复此行"),
+            ChangeKind::Modified => t!("还原此行"),
+        };
+        if ui.small_button(button_text).clicked() {
+            revert = Some(Revert::Row { table: change.table, key: change.key.clone() });
+        }
+    });
+
+    revert
+}
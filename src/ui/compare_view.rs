@@ -0,0 +1,340 @@
+use std::path::Path;
+
+use egui::{Grid, Image, RichText, ScrollArea, Stroke, Rounding, TextureHandle, Ui};
+use log::error;
+
+use crate::data::staff::Staff;
+use crate::data::team::{Leagues, Team, TeamField};
+use crate::data::team_filter::{FilterField, TeamSearchIndex};
+use crate::logo;
+use crate::ui::widgets;
+use crate::ui::theme;
+
+// 参考球队列表中最多显示的匹配数量
+const MAX_LISTED_TEAMS: usize = 200;
+
+/// 从参考数据库复制到工作数据库的操作
+pub enum ReferenceCopy {
+    Fields(Vec<TeamField>),
+    Staff,
+    Logo,
+}
+
+pub struct CompareView {
+    pub ref_teams: Vec<Team>,
+    pub ref_staff: Vec<Staff>,
+    pub ref_leagues: Leagues,
+    pub ref_db_name: String,
+    pub selected_ref_team_id: Option<i64>,
+    pub auto_match: bool,
+    pub search_text: String,
+    ref_search_index: TeamSearchIndex,
+    working_logo: Option<TextureHandle>,
+    ref_logo: Option<TextureHandle>,
+    loaded_logo_ids: Option<(Option<i64>, Option<i64>)>,
+}
+
+impl CompareView {
+    pub fn new() -> Self {
+        CompareView {
+            ref_teams: Vec::new(),
+            ref_staff: Vec::new(),
+            ref_leagues: Leagues::default(),
+            ref_db_name: String::new(),
+            selected_ref_team_id: None,
+            auto_match: true,
+            search_text: String::new(),
+            ref_search_index: TeamSearchIndex::new(&[], &Leagues::default()),
+            working_logo: None,
+            ref_logo: None,
+            loaded_logo_ids: None,
+        }
+    }
+
+    pub fn set_reference_data(
+        &mut self,
+        db_name: String,
+        teams: Vec<Team>,
+        staff: Vec<Staff>,
+        leagues: Leagues,
+    ) {
+        self.ref_db_name = db_name;
+        self.ref_search_index = TeamSearchIndex::new(&teams, &leagues);
+        self.ref_teams = teams;
+        self.ref_staff = staff;
+        self.ref_leagues = leagues;
+        self.selected_ref_team_id = None;
+        self.invalidate_logos();
+    }
+
+    pub fn clear(&mut self) {
+        *self = CompareView::new();
+    }
+
+    pub fn has_reference(&self) -> bool {
+        !self.ref_db_name.is_empty()
+    }
+
+    /// 强制下次绘制时重新加载Logo
+    pub fn invalidate_logos(&mut self) {
+        self.loaded_logo_ids = None;
+    }
+
+    pub fn get_selected_ref_team(&self) -> Option<&Team> {
+        let team_id = self.selected_ref_team_id?;
+        self.ref_teams.iter().find(|t| t.id == team_id)
+    }
+
+    pub fn get_selected_ref_staff(&self) -> Vec<Staff> {
+        match self.selected_ref_team_id {
+            Some(team_id) => self.ref_staff
+                .iter()
+                .filter(|s| s.team_id == team_id)
+                .cloned()
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    fn update_logos(&mut self, ctx: &egui::Context, working_team_id: Option<i64>, working_dir: Option<&Path>, ref_dir: Option<&Path>) {
+        let ids = (working_team_id, self.selected_ref_team_id);
+        if self.loaded_logo_ids == Some(ids) {
+            return;
+        }
+
+        self.working_logo = match (working_dir, working_team_id) {
+            (Some(dir), Some(team_id)) => load_logo_texture(ctx, dir, team_id, "compare_working_logo"),
+            _ => None,
+        };
+        self.ref_logo = match (ref_dir, self.selected_ref_team_id) {
+            (Some(dir), Some(team_id)) => load_logo_texture(ctx, dir, team_id, "compare_reference_logo"),
+            _ => None,
+        };
+        self.loaded_logo_ids = Some(ids);
+    }
+
+    pub fn ui(
+        &mut self,
+        ui: &mut Ui,
+        working_team: Option<&Team>,
+        working_staff: &[Staff],
+        working_dir: Option<&Path>,
+        ref_dir: Option<&Path>,
+    ) -> Option<ReferenceCopy> {
+        let palette = theme::palette();
+        let mut action = None;
+
+        if !self.has_reference() {
+            ui.vertical_centered(|ui| {
+                ui.add_space(50.0);
+                ui.heading(t!("尚未打开参考数据库"));
+                ui.add_space(20.0);
+                ui.label(t!("请通过\"文件 → 打开参考数据库（只读）\"选择一个数据库进行对比。"));
+                ui.add_space(50.0);
+            });
+            return None;
+        }
+
+        // 自动匹配同ID的参考球队
+        if self.auto_match {
+            if let Some(team) = working_team {
+                if self.ref_teams.iter().any(|t| t.id == team.id) {
+                    self.selected_ref_team_id = Some(team.id);
+                }
+            }
+        }
+
+        self.update_logos(ui.ctx(), working_team.map(|t| t.id), working_dir, ref_dir);
+
+        widgets::titled_frame(t!("数据库对比"), ui, |ui| {
+            ui.horizontal(|ui| {
+                ui.strong(t!("参考数据库（只读）:"));
+                ui.label(&self.ref_db_name);
+            });
+
+            // 参考球队选择
+            ui.horizontal(|ui| {
+                ui.label(t!("参考球队:"));
+                ui.add(egui::TextEdit::singleline(&mut self.search_text)
+                    .hint_text(t!("搜索参考球队..."))
+                    .desired_width(150.0));
+                ui.checkbox(&mut self.auto_match, t!("自动匹配同ID球队"));
+            });
+
+            egui::Frame::none()
+                .fill(palette.surface)
+                .stroke(Stroke::new(1.0, palette.border))
+                .rounding(Rounding::same(6.0))
+                .inner_margin(egui::Margin::same(6.0))
+                .show(ui, |ui| {
+                    ScrollArea::vertical()
+                        .id_source("compare_ref_team_list")
+                        .max_height(120.0)
+                        .auto_shrink([false, true])
+                        .show(ui, |ui| {
+                            let search_term = self.search_text.trim().to_lowercase();
+                            let search_index = &self.ref_search_index;
+                            let matches = self.ref_teams
+                                .iter()
+                                .enumerate()
+                                .filter(|(i, _)| search_term.is_empty() || search_index.contains(*i, FilterField::All, &search_term))
+                                .map(|(_, team)| team)
+                                .take(MAX_LISTED_TEAMS);
+
+                            for team in matches {
+                                let is_selected = self.selected_ref_team_id == Some(team.id);
+                                if ui.selectable_label(is_selected, team.to_string()).clicked() {
+                                    self.selected_ref_team_id = Some(team.id);
+                                    self.auto_match = false;
+                                }
+                            }
+                        });
+                });
+
+            ui.add_space(10.0);
+
+            let ref_team = self.get_selected_ref_team().cloned();
+            let (working_team, ref_team) = match (working_team, ref_team) {
+                (Some(w), Some(r)) => (w, r),
+                (None, _) => {
+                    ui.label(t!("请先在左侧列表中选择一个工作数据库中的球队"));
+                    return;
+                },
+                (_, None) => {
+                    ui.label(t!("请选择一个参考球队"));
+                    return;
+                },
+            };
+
+            // Logo对比
+            ui.columns(2, |columns| {
+                logo_cell(&mut columns[0], t!("工作数据库"), self.working_logo.as_ref());
+                logo_cell(&mut columns[1], t!("参考数据库"), self.ref_logo.as_ref());
+                columns[1].vertical_centered(|ui| {
+                    if widgets::mac_button(ui, t!("← 复制Logo")) {
+                        action = Some(ReferenceCopy::Logo);
+                    }
+                });
+            });
+
+            ui.add_space(10.0);
+            ui.heading(t!("字段对比"));
+            widgets::horizontal_separator(ui);
+
+            Grid::new("compare_fields_grid")
+                .num_columns(4)
+                .striped(true)
+                .spacing([12.0, 6.0])
+                .show(ui, |ui| {
+                    ui.strong(t!("字段"));
+                    ui.strong(t!("工作数据库"));
+                    ui.strong(t!("参考数据库"));
+                    ui.label("");
+                    ui.end_row();
+
+                    ui.label(t!("编号"));
+                    ui.label(working_team.id.to_string());
+                    ui.label(ref_team.id.to_string());
+                    ui.label("");
+                    ui.end_row();
+
+                    for field in TeamField::ALL {
+                        let working_value = field.value_string(working_team);
+                        let ref_value = field.value_string(&ref_team);
+                        let differs = working_value != ref_value;
+
+                        ui.label(field.as_str());
+                        if differs {
+                            ui.label(RichText::new(&working_value).color(palette.warning));
+                            ui.label(RichText::new(&ref_value).color(palette.warning));
+                        } else {
+                            ui.label(&working_value);
+                            ui.label(&ref_value);
+                        }
+
+                        if ui.add_enabled(differs, egui::Button::new(t!("← 复制"))).clicked() {
+                            action = Some(ReferenceCopy::Fields(vec![field]));
+                        }
+                        ui.end_row();
+                    }
+                });
+
+            ui.add_space(5.0);
+            if widgets::mac_button(ui, t!("← 复制全部字段")) {
+                action = Some(ReferenceCopy::Fields(TeamField::ALL.to_vec()));
+            }
+
+            ui.add_space(10.0);
+            ui.heading(t!("员工对比"));
+            widgets::horizontal_separator(ui);
+
+            let ref_staff = self.get_selected_ref_staff();
+            ui.columns(2, |columns| {
+                staff_cell(&mut columns[0], "compare_working_staff", working_staff);
+                staff_cell(&mut columns[1], "compare_reference_staff", &ref_staff);
+            });
+
+            ui.add_space(5.0);
+            let copy_staff = ui.add_enabled(!ref_staff.is_empty(), egui::Button::new(t!("← 复制员工")))
+                .on_hover_text(t!("把选中的员工作为新员工添加到当前球队，工作数据库中已有的员工不受影响"));
+            if copy_staff.clicked() {
+                action = Some(ReferenceCopy::Staff);
+            }
+        });
+
+        action
+    }
+}
+
+fn logo_cell(ui: &mut Ui, title: &str, texture: Option<&TextureHandle>) {
+    ui.vertical_centered(|ui| {
+        ui.strong(title);
+        match texture {
+            Some(texture) => {
+                ui.add(Image::new(texture).max_size(egui::vec2(96.0, 96.0)));
+            },
+            None => {
+                ui.label(RichText::new(t!("无Logo")).color(theme::palette().weak_text));
+            },
+        }
+    });
+}
+
+fn staff_cell(ui: &mut Ui, id: &str, staff_list: &[Staff]) {
+    if staff_list.is_empty() {
+        ui.label(RichText::new(t!("没有员工")).color(theme::palette().weak_text));
+        return;
+    }
+
+    ScrollArea::vertical()
+        .id_source(id)
+        .max_height(200.0)
+        .show(ui, |ui| {
+            Grid::new(id)
+                .num_columns(3)
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.strong(t!("姓名"));
+                    ui.strong(t!("能力值"));
+                    ui.strong(t!("知名度"));
+                    ui.end_row();
+
+                    for staff in staff_list {
+                        ui.label(&staff.name);
+                        ui.label(staff.get_ability().map(|a| a.to_string()).unwrap_or_else(|_| t!("错误").to_string()));
+                        ui.label(staff.fame.to_string());
+                        ui.end_row();
+                    }
+                });
+        });
+}
+
+fn load_logo_texture(ctx: &egui::Context, db_dir: &Path, team_id: i64, name: &str) -> Option<TextureHandle> {
+    match logo::load_logo_texture(ctx, db_dir, team_id, 96, name) {
+        Ok(texture) => texture,
+        Err(e) => {
+            error!("加载Logo失败: {}", e);
+            None
+        }
+    }
+}
@@ -0,0 +1,139 @@
+// 外部修改冲突对话框
+//
+// 保存时发现要写入的行已在编辑器之外被修改，逐行列出加载时、数据库当前和本次修改的值，
+// 由用户选择重新加载、按字段合并或直接覆盖。
+
+use egui::{Context, Grid, RichText, ScrollArea};
+
+use crate::data::conflict::SaveConflict;
+use crate::data::worker::DbCommand;
+use crate::ui::dialogs::setup_mac_window;
+use crate::ui::widgets;
+use crate::ui::theme;
+
+/// 用户选择的处理方式
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ConflictResolution {
+    /// 放弃本次修改，重新加载数据库
+    Reload,
+    /// 编辑器修改过的字段使用编辑器的值，其余字段保留数据库中的值
+    Merge,
+    /// 用编辑器的值覆盖整行
+    Overwrite,
+}
+
+pub struct ConflictDialog {
+    pub open: bool,
+    pub conflicts: Vec<SaveConflict>,
+    /// 因冲突而未执行的保存命令
+    pub command: Option<DbCommand>,
+    pub resolution: Option<ConflictResolution>,
+}
+
+impl ConflictDialog {
+    pub fn new() -> Self {
+        ConflictDialog {
+            open: false,
+            conflicts: Vec::new(),
+            command: None,
+            resolution: None,
+        }
+    }
+
+    pub fn open(&mut self, conflicts: Vec<SaveConflict>, command: DbCommand) {
+        self.conflicts = conflicts;
+        self.command = Some(command);
+        self.resolution = None;
+        self.open = true;
+    }
+
+    /// 显示对话框，选择了处理方式时返回true
+    pub fn show(&mut self, ctx: &Context) -> bool {
+        if !self.open {
+            return false;
+        }
+
+        let mut closed = false;
+        let mut resolution = None;
+
+        setup_mac_window(t!("保存冲突"))
+            .fixed_size([720.0, 480.0])
+            .show(ctx, |ui| {
+                ui.add_space(5.0);
+                ui.heading(t!("数据库已在编辑器之外被修改"));
+                ui.label(t!(
+                    "以下 {} 行在加载后被游戏或其他工具修改过，直接保存会覆盖这些修改。",
+                    self.conflicts.len()
+                ));
+                ui.add_space(5.0);
+                widgets::horizontal_separator(ui);
+                ui.add_space(10.0);
+
+                ScrollArea::vertical().max_height(330.0).show(ui, |ui| {
+                    for (index, conflict) in self.conflicts.iter().enumerate() {
+                        ui.strong(conflict.label());
+                        if conflict.is_deleted() {
+                            widgets::error_message(ui, t!("该行已在数据库中被删除"));
+                        }
+
+                        Grid::new(("conflict_fields", index))
+                            .num_columns(4)
+                            .striped(true)
+                            .spacing([20.0, 4.0])
+                            .show(ui, |ui| {
+                                ui.strong(t!("字段"));
+                                ui.strong(t!("加载时"));
+                                ui.strong(t!("数据库当前"));
+                                ui.strong(t!("本次修改"));
+                                ui.end_row();
+
+                                for field in conflict.fields() {
+                                    ui.label(field.field);
+                                    ui.label(&field.base);
+                                    ui.label(&field.theirs);
+                                    // 双方都修改了的字段合并时以本次修改为准
+                                    let ours = RichText::new(&field.ours);
+                                    ui.label(if field.is_both_changed() { ours.color(theme::palette().error) } else { ours });
+                                    ui.end_row();
+                                }
+                            });
+                        ui.add_space(10.0);
+                    }
+                });
+
+                ui.label(RichText::new(t!("红色表示双方都修改过的字段，合并时以本次修改为准")).small().color(theme::palette().weak_text));
+                ui.add_space(5.0);
+                widgets::horizontal_separator(ui);
+
+                ui.horizontal(|ui| {
+                    if widgets::mac_button(ui, t!("重新加载")) {
+                        resolution = Some(ConflictResolution::Reload);
+                    }
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if widgets::mac_primary_button(ui, t!("合并")) {
+                            resolution = Some(ConflictResolution::Merge);
+                        }
+
+                        ui.add_space(10.0);
+
+                        if widgets::mac_button(ui, t!("覆盖")) {
+                            resolution = Some(ConflictResolution::Overwrite);
+                        }
+
+                        ui.add_space(10.0);
+
+                        if widgets::mac_button(ui, t!("取消")) {
+                            closed = true;
+                        }
+                    });
+                });
+            });
+
+        if closed || resolution.is_some() {
+            self.open = false;
+        }
+
+        self.resolution = resolution;
+        resolution.is_some()
+    }
+}
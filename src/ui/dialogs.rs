@@ -2,16 +2,17 @@ use egui::{Color32, Context, Window, Rounding, Stroke, Vec2, Grid, ScrollArea};
 use log::error;
 
 use crate::data::staff::Staff;
-use crate::data::team::Team;
+use crate::data::team::{Leagues, Team};
 use crate::error::{AppError, Result};
 use crate::ui::widgets;
+use crate::ui::theme;
 
 // Mac风格的窗口设置
-fn setup_mac_window<'a>(title: &'a str) -> Window<'a> {
+pub fn setup_mac_window<'a>(title: &'a str) -> Window<'a> {
     Window::new(title)
         .frame(egui::Frame::none()
-            .fill(Color32::from_rgb(245, 245, 245))
-            .stroke(Stroke::new(1.0, Color32::from_rgb(200, 200, 200)))
+            .fill(theme::palette().window)
+            .stroke(Stroke::new(1.0, theme::palette().border_strong))
             .rounding(Rounding::same(8.0))
             .shadow(egui::epaint::Shadow {
                 extrusion: 5.0,
@@ -71,22 +72,22 @@ impl StaffEditDialog {
         let mut closed = false;
         let mut confirmed = false;
 
-        setup_mac_window("编辑员工")
+        setup_mac_window(t!("编辑员工"))
             .fixed_size([400.0, 250.0])
             .show(ctx, |ui| {
                 if let Some(staff) = &self.staff {
                     ui.add_space(5.0);
-                    ui.heading(format!("编辑员工信息 (ID: {})", staff.id));
+                    ui.heading(t!("编辑员工信息 (ID: {})", staff.id));
                     ui.add_space(10.0);
                     widgets::horizontal_separator(ui);
                     ui.add_space(10.0);
 
                     // 表单
-                    widgets::form_row(ui, "姓名:", &mut self.name);
+                    widgets::form_row(ui, t!("姓名:"), &mut self.name);
                     ui.add_space(5.0);
-                    widgets::form_row(ui, "能力值:", &mut self.ability);
+                    widgets::form_row(ui, t!("能力值:"), &mut self.ability);
                     ui.add_space(5.0);
-                    widgets::form_row(ui, "知名度:", &mut self.fame);
+                    widgets::form_row(ui, t!("知名度:"), &mut self.fame);
 
                     // 错误消息
                     if let Some(error) = &self.error_message {
@@ -101,10 +102,10 @@ impl StaffEditDialog {
                     // 按钮
                     ui.horizontal(|ui| {
                         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                            if widgets::mac_primary_button(ui, "保存") {
+                            if widgets::mac_primary_button(ui, t!("保存")) {
                                 // 验证
                                 if self.name.trim().is_empty() {
-                                    self.error_message = Some("姓名不能为空".to_string());
+                                    self.error_message = Some(t!("姓名不能为空").to_string());
                                 } else if let Err(e) = self.validate_numeric_inputs() {
                                     self.error_message = Some(e.to_string());
                                 } else {
@@ -115,7 +116,7 @@ impl StaffEditDialog {
                             
                             ui.add_space(10.0);
                             
-                            if widgets::mac_button(ui, "取消") {
+                            if widgets::mac_button(ui, t!("取消")) {
                                 closed = true;
                             }
                         });
@@ -135,15 +136,15 @@ impl StaffEditDialog {
         // 验证能力值
         match self.ability.trim().parse::<i64>() {
             Ok(n) if n >= 0 => {},
-            Ok(_) => return Err(AppError::InvalidInput("能力值必须为正数".to_string())),
-            Err(_) => return Err(AppError::InvalidInput("能力值必须为有效的整数".to_string())),
+            Ok(_) => return Err(AppError::InvalidInput(t!("能力值必须为正数").to_string())),
+            Err(_) => return Err(AppError::InvalidInput(t!("能力值必须为有效的整数").to_string())),
         }
 
         // 验证知名度
         match self.fame.trim().parse::<i64>() {
             Ok(n) if n >= 0 => {},
-            Ok(_) => return Err(AppError::InvalidInput("知名度必须为正数".to_string())),
-            Err(_) => return Err(AppError::InvalidInput("知名度必须为有效的整数".to_string())),
+            Ok(_) => return Err(AppError::InvalidInput(t!("知名度必须为正数").to_string())),
+            Err(_) => return Err(AppError::InvalidInput(t!("知名度必须为有效的整数").to_string())),
         }
 
         Ok(())
@@ -151,13 +152,13 @@ impl StaffEditDialog {
 
     pub fn get_updated_staff(&self) -> Result<Staff> {
         let staff = self.staff.clone()
-            .ok_or_else(|| AppError::Unknown("没有员工数据".to_string()))?;
+            .ok_or_else(|| AppError::Unknown(t!("没有员工数据").to_string()))?;
         
         let ability = self.ability.trim().parse::<i64>()
-            .map_err(|_| AppError::InvalidInput("无效的能力值".to_string()))?;
+            .map_err(|_| AppError::InvalidInput(t!("无效的能力值").to_string()))?;
         
         let fame = self.fame.trim().parse::<i64>()
-            .map_err(|_| AppError::InvalidInput("无效的知名度".to_string()))?;
+            .map_err(|_| AppError::InvalidInput(t!("无效的知名度").to_string()))?;
         
         let mut updated = staff;
         updated.name = self.name.clone();
@@ -203,7 +204,7 @@ impl MessageDialog {
                 
                 ui.horizontal(|ui| {
                     ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                        if widgets::mac_primary_button(ui, "确定") {
+                        if widgets::mac_primary_button(ui, t!("确定")) {
                     self.open = false;
                 }
                     });
@@ -253,14 +254,14 @@ impl ConfirmDialog {
                 
                 ui.horizontal(|ui| {
                     ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                        if widgets::mac_primary_button(ui, "确定") {
+                        if widgets::mac_primary_button(ui, t!("确定")) {
                             confirmed = true;
                         closed = true;
                     }
                         
                         ui.add_space(10.0);
                         
-                        if widgets::mac_button(ui, "取消") {
+                        if widgets::mac_button(ui, t!("取消")) {
                         closed = true;
                     }
                     });
@@ -288,7 +289,9 @@ pub struct BulkEditDialog {
     pub edit_location: bool,
     pub location: String,
     pub edit_league: bool,
-    pub league_id: String,
+    pub league_id: Option<i64>,
+    /// 可选的联赛，加载数据后设置
+    pub leagues: Leagues,
     pub edit_wealth_modifier: bool,
     pub wealth_modifier: String,
     pub wealth_modifier_type: WealthModifierType,
@@ -306,8 +309,8 @@ pub enum WealthModifierType {
 impl WealthModifierType {
     fn as_str(&self) -> &'static str {
         match self {
-            WealthModifierType::Absolute => "绝对值",
-            WealthModifierType::Percentage => "百分比",
+            WealthModifierType::Absolute => t!("绝对值"),
+            WealthModifierType::Percentage => t!("百分比"),
         }
     }
 }
@@ -325,7 +328,8 @@ impl BulkEditDialog {
             edit_location: false,
             location: String::new(),
             edit_league: false,
-            league_id: String::new(),
+            league_id: None,
+            leagues: Leagues::default(),
             edit_wealth_modifier: false,
             wealth_modifier: String::new(),
             wealth_modifier_type: WealthModifierType::Absolute,
@@ -346,7 +350,7 @@ impl BulkEditDialog {
         self.edit_location = false;
         self.location = String::new();
         self.edit_league = false;
-        self.league_id = String::new();
+        self.league_id = None;
         self.edit_wealth_modifier = false;
         self.wealth_modifier = String::new();
         self.wealth_modifier_type = WealthModifierType::Absolute;
@@ -365,11 +369,11 @@ impl BulkEditDialog {
         let mut closed = false;
         let mut confirmed = false;
 
-        setup_mac_window("批量编辑球队")
+        setup_mac_window(t!("批量编辑球队"))
             .fixed_size([700.0, 500.0])
             .show(ctx, |ui| {
                 ui.add_space(5.0);
-                ui.heading("批量编辑球队数据");
+                ui.heading(t!("批量编辑球队数据"));
                 ui.add_space(5.0);
                 widgets::horizontal_separator(ui);
                 ui.add_space(10.0);
@@ -378,34 +382,34 @@ impl BulkEditDialog {
                 ui.columns(2, |columns| {
                     // 左侧：球队选择列表
                     columns[0].vertical(|ui| {
-                        ui.heading("选择要编辑的球队");
+                        ui.heading(t!("选择要编辑的球队"));
                         ui.add_space(5.0);
                         
                         // 全选/全不选按钮
                         ui.horizontal(|ui| {
-                            if widgets::mac_button(ui, "全选") {
+                            if widgets::mac_button(ui, t!("全选")) {
                                 for selected in &mut self.selected_teams {
                                     *selected = true;
                                 }
                                 self.selected_count = self.teams.len();
                             }
                             
-                            if widgets::mac_button(ui, "全不选") {
+                            if widgets::mac_button(ui, t!("全不选")) {
                                 for selected in &mut self.selected_teams {
                                     *selected = false;
                                 }
                                 self.selected_count = 0;
                             }
                             
-                            ui.label(format!("已选择: {}/{}", self.selected_count, self.teams.len()));
+                            ui.label(t!("已选择: {}/{}", self.selected_count, self.teams.len()));
                         });
                         
                         ui.add_space(5.0);
                         
                         // 球队列表
                         egui::Frame::none()
-                            .fill(Color32::from_rgb(255, 255, 255))
-                            .stroke(Stroke::new(1.0, Color32::from_rgb(220, 220, 220)))
+                            .fill(theme::palette().surface)
+                            .stroke(Stroke::new(1.0, theme::palette().border))
                             .rounding(Rounding::same(6.0))
                             .inner_margin(egui::Margin::same(8.0))
                             .show(ui, |ui| {
@@ -437,52 +441,64 @@ impl BulkEditDialog {
                     
                     // 右侧：编辑选项
                     columns[1].vertical(|ui| {
-                        ui.heading("批量编辑选项");
+                        ui.heading(t!("批量编辑选项"));
                         ui.add_space(5.0);
                         
                         // 地区编辑
                         ui.horizontal(|ui| {
-                            ui.checkbox(&mut self.edit_location, "修改地区");
+                            ui.checkbox(&mut self.edit_location, t!("修改地区"));
                             ui.add_enabled(self.edit_location, egui::TextEdit::singleline(&mut self.location));
                         });
                         
                         // 联赛编辑
                         ui.horizontal(|ui| {
-                            ui.checkbox(&mut self.edit_league, "修改联赛ID");
-                            ui.add_enabled(self.edit_league, egui::TextEdit::singleline(&mut self.league_id));
+                            ui.checkbox(&mut self.edit_league, t!("修改联赛"));
+                            ui.add_enabled_ui(self.edit_league, |ui| {
+                                let mut league_ids = self.leagues.ids();
+                                self.leagues.sort_by_name(&mut league_ids);
+                                
+                                egui::ComboBox::from_id_source("bulk_edit_league")
+                                    .selected_text(self.league_id.map_or(t!("选择联赛").to_string(), |id| self.leagues.name(id)))
+                                    .show_ui(ui, |ui| {
+                                        for league_id in league_ids {
+                                            ui.selectable_value(&mut self.league_id, Some(league_id), self.leagues.name(league_id))
+                                                .on_hover_text(self.leagues.hover_text(league_id));
+                                        }
+                                    });
+                            });
                         });
                         
                         ui.add_space(10.0);
-                        ui.label("财富修改:");
+                        ui.label(t!("财富修改:"));
                         
                         // 财富修改
                         ui.horizontal(|ui| {
-                            ui.checkbox(&mut self.edit_wealth_modifier, "修改财富");
+                            ui.checkbox(&mut self.edit_wealth_modifier, t!("修改财富"));
                             ui.add_enabled(self.edit_wealth_modifier, egui::TextEdit::singleline(&mut self.wealth_modifier)
-                                .hint_text("输入数值"));
+                                .hint_text(t!("输入数值")));
                                 
                             egui::ComboBox::from_id_source("wealth_modifier_type")
                                 .selected_text(self.wealth_modifier_type.as_str())
                                 .show_ui(ui, |ui| {
-                                    ui.selectable_value(&mut self.wealth_modifier_type, WealthModifierType::Absolute, "绝对值");
-                                    ui.selectable_value(&mut self.wealth_modifier_type, WealthModifierType::Percentage, "百分比");
+                                    ui.selectable_value(&mut self.wealth_modifier_type, WealthModifierType::Absolute, t!("绝对值"));
+                                    ui.selectable_value(&mut self.wealth_modifier_type, WealthModifierType::Percentage, t!("百分比"));
                                 });
                         });
                         
                         ui.add_space(5.0);
-                        ui.label("球迷数量修改:");
+                        ui.label(t!("球迷数量修改:"));
                         
                         // 球迷数量修改
                         ui.horizontal(|ui| {
-                            ui.checkbox(&mut self.edit_supporter_modifier, "修改球迷数量");
+                            ui.checkbox(&mut self.edit_supporter_modifier, t!("修改球迷数量"));
                             ui.add_enabled(self.edit_supporter_modifier, egui::TextEdit::singleline(&mut self.supporter_modifier)
-                                .hint_text("输入数值"));
+                                .hint_text(t!("输入数值")));
                                 
                             egui::ComboBox::from_id_source("supporter_modifier_type")
                                 .selected_text(self.supporter_modifier_type.as_str())
                                 .show_ui(ui, |ui| {
-                                    ui.selectable_value(&mut self.supporter_modifier_type, WealthModifierType::Absolute, "绝对值");
-                                    ui.selectable_value(&mut self.supporter_modifier_type, WealthModifierType::Percentage, "百分比");
+                                    ui.selectable_value(&mut self.supporter_modifier_type, WealthModifierType::Absolute, t!("绝对值"));
+                                    ui.selectable_value(&mut self.supporter_modifier_type, WealthModifierType::Percentage, t!("百分比"));
                                 });
                         });
                         
@@ -501,12 +517,12 @@ impl BulkEditDialog {
                 // 底部按钮
                 ui.horizontal(|ui| {
                     ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                        if widgets::mac_primary_button(ui, "应用批量修改") {
+                        if widgets::mac_primary_button(ui, t!("应用批量修改")) {
                             if self.selected_count == 0 {
-                                self.error_message = Some("请至少选择一个球队".to_string());
+                                self.error_message = Some(t!("请至少选择一个球队").to_string());
                             } else if !self.edit_location && !self.edit_league && 
                                     !self.edit_wealth_modifier && !self.edit_supporter_modifier {
-                                self.error_message = Some("请至少选择一项要修改的属性".to_string());
+                                self.error_message = Some(t!("请至少选择一项要修改的属性").to_string());
                             } else if self.validate_inputs() {
                                 confirmed = true;
                                 closed = true;
@@ -515,7 +531,7 @@ impl BulkEditDialog {
                         
                         ui.add_space(10.0);
                         
-                        if widgets::mac_button(ui, "取消") {
+                        if widgets::mac_button(ui, t!("取消")) {
                             closed = true;
                         }
                     });
@@ -531,28 +547,21 @@ impl BulkEditDialog {
     }
     
     fn validate_inputs(&mut self) -> bool {
-        // 验证联赛ID
-        if self.edit_league {
-            if self.league_id.trim().is_empty() {
-                self.error_message = Some("联赛ID不能为空".to_string());
-                return false;
-            }
-            
-            if let Err(_) = self.league_id.trim().parse::<i64>() {
-                self.error_message = Some("联赛ID必须是有效的整数".to_string());
-                return false;
-            }
+        // 验证联赛
+        if self.edit_league && self.league_id.is_none() {
+            self.error_message = Some(t!("请选择联赛").to_string());
+            return false;
         }
         
         // 验证财富修改器
         if self.edit_wealth_modifier {
             if self.wealth_modifier.trim().is_empty() {
-                self.error_message = Some("财富修改值不能为空".to_string());
+                self.error_message = Some(t!("财富修改值不能为空").to_string());
                 return false;
             }
             
             if let Err(_) = self.wealth_modifier.trim().parse::<i64>() {
-                self.error_message = Some("财富修改值必须是有效的整数".to_string());
+                self.error_message = Some(t!("财富修改值必须是有效的整数").to_string());
                 return false;
             }
         }
@@ -560,12 +569,12 @@ impl BulkEditDialog {
         // 验证球迷数量修改器
         if self.edit_supporter_modifier {
             if self.supporter_modifier.trim().is_empty() {
-                self.error_message = Some("球迷数量修改值不能为空".to_string());
+                self.error_message = Some(t!("球迷数量修改值不能为空").to_string());
                 return false;
             }
             
             if let Err(_) = self.supporter_modifier.trim().parse::<i64>() {
-                self.error_message = Some("球迷数量修改值必须是有效的整数".to_string());
+                self.error_message = Some(t!("球迷数量修改值必须是有效的整数").to_string());
                 return false;
             }
         }
@@ -586,9 +595,9 @@ impl BulkEditDialog {
                         modified_team.location = self.location.clone();
                     }
                     
-                    // 应用联赛ID修改
+                    // 应用联赛修改
                     if self.edit_league {
-                        if let Ok(league_id) = self.league_id.trim().parse::<i64>() {
+                        if let Some(league_id) = self.league_id {
                             modified_team.league_id = league_id;
                         }
                     }
@@ -630,4 +639,4 @@ impl BulkEditDialog {
         
         modified_teams
     }
-} 
\ No newline at end of file
+}
@@ -0,0 +1,109 @@
+// 错误对话框
+//
+// 显示错误的原因说明和修复建议，技术细节默认折叠，可一键复制完整的错误报告用于反馈。
+
+use egui::{Context, RichText, ScrollArea, Vec2};
+
+use crate::error::ErrorReport;
+use crate::ui::dialogs::setup_mac_window;
+use crate::ui::widgets;
+use crate::ui::theme;
+
+pub struct ErrorDialog {
+    pub open: bool,
+    pub report: Option<ErrorReport>,
+    copied: bool,
+}
+
+impl ErrorDialog {
+    pub fn new() -> Self {
+        ErrorDialog {
+            open: false,
+            report: None,
+            copied: false,
+        }
+    }
+
+    pub fn show_report(&mut self, report: ErrorReport) {
+        self.report = Some(report);
+        self.copied = false;
+        self.open = true;
+    }
+
+    pub fn show(&mut self, ctx: &Context) {
+        if !self.open {
+            return;
+        }
+        let Some(report) = &self.report else {
+            self.open = false;
+            return;
+        };
+
+        let mut closed = false;
+
+        setup_mac_window(t!("错误"))
+            .min_size(Vec2::new(420.0, 180.0))
+            .max_width(560.0)
+            .show(ctx, |ui| {
+                ui.add_space(5.0);
+                if !report.operation.is_empty() {
+                    ui.heading(t!("{}失败", report.operation));
+                    ui.add_space(5.0);
+                }
+                widgets::error_message(ui, &report.summary);
+
+                if !report.suggestions.is_empty() {
+                    ui.add_space(10.0);
+                    ui.strong(t!("建议:"));
+                    for suggestion in &report.suggestions {
+                        ui.label(format!("• {}", suggestion));
+                    }
+                }
+
+                ui.add_space(10.0);
+                egui::CollapsingHeader::new(t!("详细信息"))
+                    .id_source("error_details")
+                    .show(ui, |ui| {
+                        ScrollArea::vertical().max_height(200.0).show(ui, |ui| {
+                            ui.label(RichText::new(&report.details).monospace().small());
+                        });
+                    });
+
+                ui.add_space(10.0);
+                widgets::horizontal_separator(ui);
+                ui.add_space(5.0);
+
+                ui.horizontal(|ui| {
+                    if widgets::mac_button(ui, t!("复制报告")) {
+                        ui.output_mut(|o| o.copied_text = report_text(report));
+                        self.copied = true;
+                    }
+                    if self.copied {
+                        ui.label(RichText::new(t!("已复制到剪贴板")).small().color(theme::palette().weak_text));
+                    }
+
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if widgets::mac_primary_button(ui, t!("确定")) {
+                            closed = true;
+                        }
+                    });
+                });
+            });
+
+        if closed {
+            self.open = false;
+            self.report = None;
+        }
+    }
+}
+
+// 复制到剪贴板的完整报告
+fn report_text(report: &ErrorReport) -> String {
+    let mut text = format!("{}\n", report.summary);
+    for suggestion in &report.suggestions {
+        text.push_str(&format!("- {}\n", suggestion));
+    }
+    text.push('\n');
+    text.push_str(&report.details);
+    text
+}
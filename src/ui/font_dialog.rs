@@ -0,0 +1,197 @@
+// 字体和缩放对话框
+//
+// 选择自定义字体文件、是否使用系统字体和内置字体，调整正文字号和界面缩放，
+// 并列出当前回退链中实际加载的字体，方便确认中文能否正常显示。
+
+use egui::{Context, Grid, RichText, Slider, Ui};
+use native_dialog::FileDialog;
+
+use crate::fonts::{self, ActiveFont, FontSettings, FONT_EXTENSIONS, FONT_SIZE_RANGE, UI_SCALE_RANGE};
+use crate::ui::dialogs::setup_mac_window;
+use crate::ui::widgets;
+use crate::ui::theme;
+
+pub struct FontDialog {
+    pub open: bool,
+    pub confirmed: bool,
+    pub settings: FontSettings,
+    /// 当前回退链中已加载的字体
+    pub active_fonts: Vec<ActiveFont>,
+    custom_font_note: Option<String>,
+    error_message: Option<String>,
+}
+
+impl FontDialog {
+    pub fn new() -> Self {
+        FontDialog {
+            open: false,
+            confirmed: false,
+            settings: FontSettings::default(),
+            active_fonts: Vec::new(),
+            custom_font_note: None,
+            error_message: None,
+        }
+    }
+
+    pub fn open(&mut self, settings: &FontSettings) {
+        self.settings = settings.clone();
+        self.confirmed = false;
+        self.custom_font_note = None;
+        self.error_message = None;
+        self.open = true;
+    }
+
+    pub fn show(&mut self, ctx: &Context) -> bool {
+        if !self.open {
+            return false;
+        }
+
+        let mut closed = false;
+        let mut confirmed = false;
+
+        setup_mac_window(t!("字体和缩放"))
+            .min_width(520.0)
+            .show(ctx, |ui| {
+                ui.add_space(5.0);
+                self.show_controls(ui);
+
+                if let Some(message) = &self.error_message {
+                    ui.add_space(5.0);
+                    widgets::error_message(ui, message);
+                }
+
+                ui.add_space(10.0);
+                widgets::horizontal_separator(ui);
+                ui.add_space(5.0);
+                self.show_active_fonts(ui);
+
+                ui.add_space(10.0);
+                widgets::horizontal_separator(ui);
+                ui.add_space(5.0);
+
+                ui.horizontal(|ui| {
+                    if widgets::mac_button(ui, t!("恢复默认")) {
+                        self.settings = FontSettings::default();
+                        self.custom_font_note = None;
+                        self.error_message = None;
+                    }
+
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if widgets::mac_primary_button(ui, t!("应用")) {
+                            confirmed = true;
+                            closed = true;
+                        }
+
+                        ui.add_space(10.0);
+
+                        if widgets::mac_button(ui, t!("取消")) {
+                            closed = true;
+                        }
+                    });
+                });
+            });
+
+        if closed {
+            self.open = false;
+        }
+
+        self.confirmed = confirmed;
+        confirmed
+    }
+
+    fn show_controls(&mut self, ui: &mut Ui) {
+        let palette = theme::palette();
+        Grid::new("font_settings")
+            .num_columns(2)
+            .spacing([10.0, 8.0])
+            .show(ui, |ui| {
+                ui.label(t!("字体文件:"));
+                ui.horizontal(|ui| {
+                    match &self.settings.custom_font {
+                        Some(path) => ui.label(path.display().to_string()),
+                        None => ui.label(RichText::new(t!("未选择")).color(palette.weak_text)),
+                    };
+                    if ui.button(t!("选择...")).clicked() {
+                        self.choose_font_file();
+                    }
+                    if self.settings.custom_font.is_some() && ui.button(t!("清除")).clicked() {
+                        self.settings.custom_font = None;
+                        self.custom_font_note = None;
+                        self.error_message = None;
+                    }
+                });
+                ui.end_row();
+
+                if let Some(note) = &self.custom_font_note {
+                    ui.label("");
+                    ui.label(RichText::new(note).small().color(palette.weak_text));
+                    ui.end_row();
+                }
+
+                ui.label(t!("系统字体:"));
+                ui.checkbox(&mut self.settings.use_system_fonts, t!("查找系统中已安装的中文字体"));
+                ui.end_row();
+
+                ui.label(t!("内置字体:"));
+                ui.add_enabled(
+                    fonts::has_embedded_font(),
+                    egui::Checkbox::new(&mut self.settings.use_embedded_font, t!("使用内置的中文子集字体")),
+                ).on_disabled_hover_text(t!("此版本编译时没有包含内置字体"));
+                ui.end_row();
+
+                ui.label(t!("正文字号:"));
+                ui.add(Slider::new(&mut self.settings.font_size, FONT_SIZE_RANGE).step_by(1.0));
+                ui.end_row();
+
+                ui.label(t!("界面缩放:"));
+                ui.add(Slider::new(&mut self.settings.ui_scale, UI_SCALE_RANGE)
+                    .step_by(0.05)
+                    .custom_formatter(|value, _| format!("{:.0}%", value * 100.0)));
+                ui.end_row();
+            });
+
+        ui.label(RichText::new(t!("自定义字体排在最前面，其中缺少的字符依次由系统中文字体和内置字体补上。")).small().color(palette.weak_text));
+    }
+
+    fn show_active_fonts(&self, ui: &mut Ui) {
+        ui.strong(t!("当前使用的字体"));
+        if self.active_fonts.is_empty() {
+            ui.label(RichText::new(t!("只使用egui自带的字体")).color(theme::palette().weak_text));
+        }
+        for (i, font) in self.active_fonts.iter().enumerate() {
+            let mut text = format!("{}. {}", i + 1, font.source.describe());
+            if !font.covers_cjk {
+                text.push_str(t!("（不含中文）"));
+            }
+            ui.label(text);
+        }
+        if !self.active_fonts.iter().any(|font| font.covers_cjk) {
+            widgets::error_message(ui, t!("没有可用的中文字体，中文会显示为方框，请选择一个中文字体文件"));
+        }
+    }
+
+    fn choose_font_file(&mut self) {
+        let dialog = FileDialog::new()
+            .add_filter(t!("字体文件"), &FONT_EXTENSIONS)
+            .show_open_single_file();
+
+        let Ok(Some(path)) = dialog else {
+            return;
+        };
+
+        match fonts::check_font_file(&path) {
+            Ok(covers_cjk) => {
+                self.custom_font_note = Some(if covers_cjk {
+                    t!("该字体包含中文字形").to_string()
+                } else {
+                    t!("该字体不含中文字形，中文将由后面的字体显示").to_string()
+                });
+                self.error_message = None;
+                self.settings.custom_font = Some(path);
+            },
+            Err(e) => {
+                self.error_message = Some(e.to_string());
+            },
+        }
+    }
+}
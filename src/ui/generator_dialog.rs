@@ -0,0 +1,287 @@
+// 测试数据生成对话框
+//
+// 编辑生成参数和词表，预览生成的球队和员工，确认后写入当前数据库。
+// 参数变化时才重新生成预览，相同的种子和参数得到相同的结果。
+
+use egui::{ComboBox, Context, DragValue, Grid, RichText, ScrollArea, Ui};
+
+use crate::data::team::Leagues;
+use crate::generator::{self, DistributionKind, GeneratedData, GeneratorConfig, ValueDistribution};
+use crate::ui::dialogs::setup_mac_window;
+use crate::ui::widgets;
+
+// 预览表格中最多显示的行数
+const PREVIEW_ROWS: usize = 50;
+
+pub struct GeneratorDialog {
+    pub open: bool,
+    pub confirmed: bool,
+    pub config: GeneratorConfig,
+    pub leagues: Leagues,
+    /// 当前参数生成的数据
+    pub data: GeneratedData,
+    word_texts: Vec<String>,
+    dirty: bool,
+}
+
+impl GeneratorDialog {
+    pub fn new() -> Self {
+        let mut config = GeneratorConfig {
+            seed: chrono::Local::now().timestamp() as u64,
+            ..Default::default()
+        };
+        let word_texts = config.words.lists_mut()
+            .iter()
+            .map(|(_, words)| words.join(" "))
+            .collect();
+
+        GeneratorDialog {
+            open: false,
+            confirmed: false,
+            config,
+            leagues: Leagues::default(),
+            data: GeneratedData::default(),
+            word_texts,
+            dirty: true,
+        }
+    }
+
+    /// 打开对话框，league_id为生成的球队默认所属的联赛
+    pub fn open(&mut self, leagues: Leagues, league_id: Option<i64>) {
+        self.leagues = leagues;
+        if let Some(league_id) = league_id {
+            self.config.league_id = league_id;
+        } else if self.leagues.get(self.config.league_id).is_none() {
+            let mut ids = self.leagues.ids();
+            self.leagues.sort_by_name(&mut ids);
+            self.config.league_id = ids.first().copied().unwrap_or(0);
+        }
+        self.confirmed = false;
+        self.dirty = true;
+        self.open = true;
+    }
+
+    pub fn show(&mut self, ctx: &Context) -> bool {
+        if !self.open {
+            return false;
+        }
+
+        if self.dirty {
+            self.data = generator::generate(&self.config);
+            self.dirty = false;
+        }
+
+        let mut closed = false;
+        let mut confirmed = false;
+
+        setup_mac_window(t!("生成测试数据"))
+            .fixed_size([860.0, 620.0])
+            .show(ctx, |ui| {
+                ui.add_space(5.0);
+                ui.heading(t!("生成虚构的球队和员工"));
+                ui.add_space(5.0);
+                widgets::horizontal_separator(ui);
+                ui.add_space(10.0);
+
+                ui.columns(2, |columns| {
+                    ScrollArea::vertical()
+                        .id_source("generator_settings")
+                        .max_height(500.0)
+                        .show(&mut columns[0], |ui| {
+                            self.show_settings(ui);
+                            ui.add_space(10.0);
+                            self.show_word_lists(ui);
+                        });
+
+                    self.show_preview(&mut columns[1]);
+                });
+
+                ui.add_space(10.0);
+                widgets::horizontal_separator(ui);
+
+                ui.horizontal(|ui| {
+                    ui.label(t!(
+                        "将生成 {} 个球队和 {} 个员工",
+                        self.data.teams.len(),
+                        self.data.staff.len()
+                    ));
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if widgets::mac_primary_button(ui, t!("写入数据库")) && !self.data.teams.is_empty() {
+                            confirmed = true;
+                            closed = true;
+                        }
+
+                        ui.add_space(10.0);
+
+                        if widgets::mac_button(ui, t!("取消")) {
+                            closed = true;
+                        }
+                    });
+                });
+            });
+
+        if closed {
+            self.open = false;
+        }
+
+        self.confirmed = confirmed;
+        confirmed
+    }
+
+    fn show_settings(&mut self, ui: &mut Ui) {
+        let mut changed = false;
+        let config = &mut self.config;
+
+        Grid::new("generator_settings_grid")
+            .num_columns(2)
+            .spacing([10.0, 8.0])
+            .show(ui, |ui| {
+                ui.label(t!("随机种子:"));
+                ui.horizontal(|ui| {
+                    changed |= ui.add(DragValue::new(&mut config.seed)).changed();
+                    if widgets::mac_button(ui, t!("随机")) {
+                        config.seed = generator::SeededRng::new(config.seed).next_u64() >> 16;
+                        changed = true;
+                    }
+                });
+                ui.end_row();
+
+                ui.label(t!("球队数量:"));
+                changed |= ui.add(DragValue::new(&mut config.team_count).clamp_range(1..=1000)).changed();
+                ui.end_row();
+
+                ui.label(t!("每队员工:"));
+                changed |= ui.add(DragValue::new(&mut config.staff_per_team).clamp_range(0..=50)).changed();
+                ui.end_row();
+
+                ui.label(t!("所属联赛:"));
+                let mut league_ids = self.leagues.ids();
+                self.leagues.sort_by_name(&mut league_ids);
+                ComboBox::from_id_source("generator_league")
+                    .selected_text(self.leagues.name(config.league_id))
+                    .width(180.0)
+                    .show_ui(ui, |ui| {
+                        for league_id in league_ids {
+                            changed |= ui.selectable_value(&mut config.league_id, league_id, self.leagues.name(league_id))
+                                .on_hover_text(self.leagues.hover_text(league_id))
+                                .changed();
+                        }
+                    });
+                ui.end_row();
+
+                ui.label(t!("成立年份:"));
+                ui.horizontal(|ui| {
+                    changed |= ui.add(DragValue::new(&mut config.found_year_min)).changed();
+                    ui.label("-");
+                    changed |= ui.add(DragValue::new(&mut config.found_year_max)).changed();
+                });
+                ui.end_row();
+
+                changed |= distribution_row(ui, t!("球队财富"), "generator_wealth", &mut config.wealth);
+                changed |= distribution_row(ui, t!("支持者数量"), "generator_supporters", &mut config.supporters);
+                changed |= distribution_row(ui, t!("员工声望"), "generator_fame", &mut config.fame);
+                changed |= distribution_row(ui, t!("员工能力"), "generator_ability", &mut config.ability);
+            });
+
+        self.dirty |= changed;
+    }
+
+    fn show_word_lists(&mut self, ui: &mut Ui) {
+        ui.strong(t!("词表（以空格或逗号分隔）"));
+        ui.add_space(5.0);
+
+        for ((label, words), text) in self.config.words.lists_mut().into_iter().zip(&mut self.word_texts) {
+            ui.label(label);
+            if ui.add(egui::TextEdit::multiline(text).desired_rows(2).desired_width(f32::INFINITY)).changed() {
+                *words = generator::split_words(text);
+                self.dirty = true;
+            }
+        }
+    }
+
+    fn show_preview(&self, ui: &mut Ui) {
+        ui.strong(t!("球队预览"));
+        ScrollArea::both()
+            .id_source("generator_team_preview")
+            .max_height(300.0)
+            .show(ui, |ui| {
+                Grid::new("generator_team_grid")
+                    .num_columns(7)
+                    .striped(true)
+                    .spacing([12.0, 4.0])
+                    .show(ui, |ui| {
+                        for title in [t!("球队名称"), t!("地区"), t!("主场"), t!("昵称"), t!("成立年份"), t!("财富"), t!("支持者")] {
+                            ui.strong(title);
+                        }
+                        ui.end_row();
+
+                        for team in self.data.teams.iter().take(PREVIEW_ROWS) {
+                            ui.label(&team.name);
+                            ui.label(&team.location);
+                            ui.label(&team.stadium_name);
+                            ui.label(&team.nickname);
+                            ui.label(team.found_year.to_string());
+                            ui.label(team.wealth.to_string());
+                            ui.label(team.supporter_count.to_string());
+                            ui.end_row();
+                        }
+                    });
+            });
+
+        ui.add_space(10.0);
+        ui.strong(t!("员工预览"));
+        ScrollArea::vertical()
+            .id_source("generator_staff_preview")
+            .max_height(160.0)
+            .show(ui, |ui| {
+                Grid::new("generator_staff_grid")
+                    .num_columns(4)
+                    .striped(true)
+                    .spacing([12.0, 4.0])
+                    .show(ui, |ui| {
+                        for title in [t!("姓名"), t!("球队"), t!("声望"), t!("能力")] {
+                            ui.strong(title);
+                        }
+                        ui.end_row();
+
+                        for staff in self.data.staff.iter().take(PREVIEW_ROWS) {
+                            // 生成数据中球队ID从1开始，与下标相差1
+                            let team_name = self.data.teams.get((staff.team_id - 1) as usize)
+                                .map_or("", |team| team.name.as_str());
+                            ui.label(&staff.name);
+                            ui.label(team_name);
+                            ui.label(staff.fame.to_string());
+                            ui.label(staff.get_ability().map(|a| a.to_string()).unwrap_or_default());
+                            ui.end_row();
+                        }
+                    });
+            });
+
+        if self.data.teams.len() > PREVIEW_ROWS {
+            ui.label(RichText::new(t!("仅显示前 {} 行", PREVIEW_ROWS)).small());
+        }
+    }
+}
+
+// 一个数值字段的分布设置，返回是否有修改
+fn distribution_row(ui: &mut Ui, label: &str, id: &str, distribution: &mut ValueDistribution) -> bool {
+    let mut changed = false;
+
+    ui.label(format!("{}:", label));
+    ui.horizontal(|ui| {
+        ComboBox::from_id_source(id)
+            .selected_text(distribution.kind.as_str())
+            .width(80.0)
+            .show_ui(ui, |ui| {
+                for kind in DistributionKind::ALL {
+                    changed |= ui.selectable_value(&mut distribution.kind, kind, kind.as_str()).changed();
+                }
+            });
+        changed |= ui.add(DragValue::new(&mut distribution.min)).changed();
+        ui.label("-");
+        changed |= ui.add(DragValue::new(&mut distribution.max)).changed();
+    });
+    ui.end_row();
+
+    changed
+}
@@ -0,0 +1,445 @@
+use std::path::{Path, PathBuf};
+
+use egui::{Color32, Context, Painter, Pos2, Rect, Rounding, Sense, Stroke, TextureHandle, Ui, Vec2};
+use image::{DynamicImage, GenericImageView};
+
+use crate::error::Result;
+use crate::logo::{self, ColorKey, CropRect, LogoEdit, LogoFit};
+use crate::ui::dialogs::setup_mac_window;
+use crate::ui::widgets;
+use crate::ui::theme;
+
+// 裁剪画布尺寸
+const CANVAS_SIZE: f32 = 320.0;
+
+// 显示用纹理的最大边长，编辑仍使用原始分辨率
+const MAX_DISPLAY_SIZE: u32 = 640;
+
+// 拖动角点时的判定半径（屏幕像素）
+const HANDLE_RADIUS: f32 = 8.0;
+
+#[derive(Clone, Copy)]
+enum DragMode {
+    // 移动裁剪框，记录指针相对裁剪框左上角的偏移
+    Move(Vec2),
+    // 从固定的一点拉出新的裁剪框（新建或拖动角点）
+    Resize(Pos2),
+}
+
+pub struct LogoEditorDialog {
+    pub open: bool,
+    pub team_id: i64,
+    pub source_path: Option<PathBuf>,
+    pub edit: LogoEdit,
+    pub square_crop: bool,
+    pub confirmed: bool,
+    source_image: Option<DynamicImage>,
+    source_texture: Option<TextureHandle>,
+    prepared_image: Option<DynamicImage>,
+    preview_texture: Option<TextureHandle>,
+    key_enabled: bool,
+    key_color: [u8; 3],
+    key_tolerance: u8,
+    picking_color: bool,
+    drag_mode: Option<DragMode>,
+    dirty: bool,
+}
+
+impl LogoEditorDialog {
+    pub fn new() -> Self {
+        LogoEditorDialog {
+            open: false,
+            team_id: 0,
+            source_path: None,
+            edit: LogoEdit::new(1, 1),
+            square_crop: true,
+            confirmed: false,
+            source_image: None,
+            source_texture: None,
+            prepared_image: None,
+            preview_texture: None,
+            key_enabled: false,
+            key_color: [255, 255, 255],
+            key_tolerance: 20,
+            picking_color: false,
+            drag_mode: None,
+            dirty: true,
+        }
+    }
+
+    pub fn open(&mut self, ctx: &Context, team_id: i64, path: &Path) -> Result<()> {
+        let img = image::open(path)?;
+        let (width, height) = img.dimensions();
+
+        let display = if width > MAX_DISPLAY_SIZE || height > MAX_DISPLAY_SIZE {
+            img.resize(MAX_DISPLAY_SIZE, MAX_DISPLAY_SIZE, image::imageops::FilterType::Triangle)
+        } else {
+            img.clone()
+        };
+
+        self.team_id = team_id;
+        self.source_path = Some(path.to_path_buf());
+        self.source_texture = Some(logo::texture_from_image(ctx, "logo_editor_source", &display));
+        self.source_image = Some(img);
+        self.edit = LogoEdit::new(width, height);
+        self.square_crop = width != height;
+        if self.square_crop {
+            self.edit.crop = CropRect::centered_square(width, height);
+        }
+        self.key_enabled = false;
+        self.picking_color = false;
+        self.drag_mode = None;
+        self.prepared_image = None;
+        self.preview_texture = None;
+        self.dirty = true;
+        self.confirmed = false;
+        self.open = true;
+        Ok(())
+    }
+
+    pub fn get_prepared_logo(&self) -> Option<&DynamicImage> {
+        self.prepared_image.as_ref()
+    }
+
+    fn source_size(&self) -> (u32, u32) {
+        self.source_image.as_ref().map_or((1, 1), |img| img.dimensions())
+    }
+
+    fn update_preview(&mut self, ctx: &Context) {
+        // 拖动过程中不重新生成，松开鼠标后再更新
+        if !self.dirty || ctx.input(|i| i.pointer.any_down()) {
+            return;
+        }
+
+        self.edit.color_key = if self.key_enabled {
+            Some(ColorKey { color: self.key_color, tolerance: self.key_tolerance })
+        } else {
+            None
+        };
+
+        if let Some(source) = &self.source_image {
+            let prepared = logo::apply_logo_edit(source, &self.edit);
+            self.preview_texture = Some(logo::texture_from_image(ctx, "logo_editor_preview", &prepared));
+            self.prepared_image = Some(prepared);
+        }
+        self.dirty = false;
+    }
+
+    pub fn show(&mut self, ctx: &Context) -> bool {
+        if !self.open {
+            return false;
+        }
+
+        self.update_preview(ctx);
+
+        let mut closed = false;
+        let mut confirmed = false;
+
+        setup_mac_window(t!("编辑Logo"))
+            .fixed_size([720.0, 560.0])
+            .show(ctx, |ui| {
+                ui.add_space(5.0);
+                ui.heading(t!("编辑球队Logo (ID: {})", self.team_id));
+                ui.add_space(5.0);
+                widgets::horizontal_separator(ui);
+
+                if let Some(path) = &self.source_path {
+                    let (width, height) = self.source_size();
+                    widgets::label_value(ui, t!("源文件:"), &path.display().to_string());
+                    widgets::label_value(ui, t!("源尺寸:"), &format!("{}×{}", width, height));
+                }
+
+                ui.add_space(5.0);
+
+                ui.horizontal_top(|ui| {
+                    // 左侧：裁剪画布
+                    ui.vertical(|ui| {
+                        ui.strong(t!("裁剪区域"));
+                        self.crop_canvas(ui);
+
+                        let crop = self.edit.crop;
+                        ui.label(t!("{}×{}，起点 ({}, {})", crop.width, crop.height, crop.x, crop.y));
+
+                        ui.horizontal(|ui| {
+                            if ui.checkbox(&mut self.square_crop, t!("锁定正方形")).changed() && self.square_crop {
+                                let side = crop.width.min(crop.height);
+                                self.edit.crop.width = side;
+                                self.edit.crop.height = side;
+                                self.dirty = true;
+                            }
+                            let (width, height) = self.source_size();
+                            if ui.button(t!("全图")).clicked() {
+                                self.edit.crop = CropRect::full(width, height);
+                                self.square_crop = false;
+                                self.dirty = true;
+                            }
+                            if ui.button(t!("居中正方形")).clicked() {
+                                self.edit.crop = CropRect::centered_square(width, height);
+                                self.square_crop = true;
+                                self.dirty = true;
+                            }
+                        });
+                    });
+
+                    ui.add_space(15.0);
+
+                    // 右侧：处理选项和预览
+                    ui.vertical(|ui| {
+                        ui.strong(t!("背景透明"));
+                        ui.horizontal(|ui| {
+                            if ui.checkbox(&mut self.key_enabled, t!("移除背景色")).changed() {
+                                self.dirty = true;
+                            }
+                            if egui::color_picker::color_edit_button_srgb(ui, &mut self.key_color).changed() {
+                                self.dirty = true;
+                            }
+                            let pick_text = if self.picking_color { t!("点击图片取色...") } else { t!("吸管") };
+                            if ui.selectable_label(self.picking_color, pick_text).clicked() {
+                                self.picking_color = !self.picking_color;
+                            }
+                        });
+                        ui.horizontal(|ui| {
+                            ui.label(t!("容差:"));
+                            if ui.add(egui::Slider::new(&mut self.key_tolerance, 0..=128)).changed() {
+                                self.dirty = true;
+                            }
+                        });
+
+                        ui.add_space(10.0);
+                        ui.strong(t!("转为正方形"));
+                        ui.horizontal(|ui| {
+                            for fit in [LogoFit::Pad, LogoFit::Crop] {
+                                if ui.selectable_value(&mut self.edit.fit, fit, fit.as_str()).changed() {
+                                    self.dirty = true;
+                                }
+                            }
+                        });
+                        ui.add_enabled_ui(self.edit.fit == LogoFit::Pad, |ui| {
+                            ui.horizontal(|ui| {
+                                ui.label(t!("填充颜色:"));
+                                let mut color = Color32::from_rgba_unmultiplied(
+                                    self.edit.pad_color[0],
+                                    self.edit.pad_color[1],
+                                    self.edit.pad_color[2],
+                                    self.edit.pad_color[3],
+                                );
+                                if egui::color_picker::color_edit_button_srgba(ui, &mut color, egui::color_picker::Alpha::OnlyBlend).changed() {
+                                    self.edit.pad_color = color.to_srgba_unmultiplied();
+                                    self.dirty = true;
+                                }
+                                if ui.button(t!("透明")).clicked() {
+                                    self.edit.pad_color = [0, 0, 0, 0];
+                                    self.dirty = true;
+                                }
+                            });
+                        });
+
+                        ui.add_space(10.0);
+                        ui.strong(t!("游戏内显示效果"));
+                        if let Some(texture) = &self.preview_texture {
+                            ui.horizontal(|ui| {
+                                for size in logo::PREVIEW_SIZES {
+                                    let (rect, _) = ui.allocate_exact_size(Vec2::splat(size), Sense::hover());
+                                    paint_checkerboard(ui.painter(), rect);
+                                    ui.painter().image(texture.id(), rect, full_uv(), Color32::WHITE);
+                                }
+                            });
+                        }
+                        ui.label(t!("保存为: L{}.png ({}×{} PNG)", self.team_id, logo::LOGO_SIZE, logo::LOGO_SIZE));
+                    });
+                });
+
+                ui.add_space(10.0);
+                widgets::horizontal_separator(ui);
+
+                ui.horizontal(|ui| {
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if widgets::mac_primary_button(ui, t!("保存")) && self.prepared_image.is_some() {
+                            confirmed = true;
+                            closed = true;
+                        }
+
+                        ui.add_space(10.0);
+
+                        if widgets::mac_button(ui, t!("取消")) {
+                            closed = true;
+                        }
+                    });
+                });
+            });
+
+        if closed {
+            self.open = false;
+            self.source_texture = None;
+            self.preview_texture = None;
+        }
+
+        self.confirmed = confirmed;
+        confirmed
+    }
+
+    fn crop_canvas(&mut self, ui: &mut Ui) {
+        let (canvas, response) = ui.allocate_exact_size(Vec2::splat(CANVAS_SIZE), Sense::click_and_drag());
+        let painter = ui.painter_at(canvas);
+        paint_checkerboard(&painter, canvas);
+
+        let texture = match &self.source_texture {
+            Some(texture) => texture,
+            None => return,
+        };
+
+        // 源图片在画布中的位置
+        let (width, height) = self.source_size();
+        let scale = (CANVAS_SIZE / width as f32).min(CANVAS_SIZE / height as f32);
+        let image_rect = Rect::from_center_size(canvas.center(), Vec2::new(width as f32, height as f32) * scale);
+        painter.image(texture.id(), image_rect, full_uv(), Color32::WHITE);
+
+        let to_image = |pos: Pos2| -> Pos2 {
+            let p = (pos - image_rect.min) / scale;
+            Pos2::new(p.x.clamp(0.0, width as f32), p.y.clamp(0.0, height as f32))
+        };
+        let crop = self.edit.crop;
+        let crop_screen = Rect::from_min_size(
+            image_rect.min + Vec2::new(crop.x as f32, crop.y as f32) * scale,
+            Vec2::new(crop.width as f32, crop.height as f32) * scale,
+        );
+
+        // 吸管取色
+        if self.picking_color {
+            if let (true, Some(pos)) = (response.clicked(), response.interact_pointer_pos()) {
+                let p = to_image(pos);
+                if let Some(source) = &self.source_image {
+                    let x = (p.x as u32).min(width - 1);
+                    let y = (p.y as u32).min(height - 1);
+                    let pixel = source.get_pixel(x, y).0;
+                    self.key_color = [pixel[0], pixel[1], pixel[2]];
+                    self.key_enabled = true;
+                    self.dirty = true;
+                }
+                self.picking_color = false;
+            }
+            if response.hovered() {
+                ui.ctx().set_cursor_icon(egui::CursorIcon::Crosshair);
+            }
+        } else {
+            self.handle_crop_drag(&response, crop_screen, to_image);
+        }
+
+        // 裁剪框外变暗
+        let shade = Color32::from_black_alpha(120);
+        let crop_screen = crop_screen.intersect(image_rect);
+        for rect in [
+            Rect::from_min_max(image_rect.min, Pos2::new(image_rect.max.x, crop_screen.min.y)),
+            Rect::from_min_max(Pos2::new(image_rect.min.x, crop_screen.max.y), image_rect.max),
+            Rect::from_min_max(Pos2::new(image_rect.min.x, crop_screen.min.y), Pos2::new(crop_screen.min.x, crop_screen.max.y)),
+            Rect::from_min_max(Pos2::new(crop_screen.max.x, crop_screen.min.y), Pos2::new(image_rect.max.x, crop_screen.max.y)),
+        ] {
+            if rect.is_positive() {
+                painter.rect_filled(rect, Rounding::ZERO, shade);
+            }
+        }
+
+        painter.rect_stroke(crop_screen, Rounding::ZERO, Stroke::new(1.5, Color32::WHITE));
+        for corner in corners(crop_screen) {
+            painter.circle(corner, 4.0, Color32::WHITE, Stroke::new(1.0, theme::palette().accent));
+        }
+    }
+
+    fn handle_crop_drag(&mut self, response: &egui::Response, crop_screen: Rect, to_image: impl Fn(Pos2) -> Pos2) {
+        let pointer = match response.interact_pointer_pos() {
+            Some(pos) => pos,
+            None => return,
+        };
+
+        if response.drag_started() {
+            let crop = self.edit.crop;
+            let screen_corners = corners(crop_screen);
+            let image_corners = [
+                Pos2::new(crop.x as f32, crop.y as f32),
+                Pos2::new((crop.x + crop.width) as f32, crop.y as f32),
+                Pos2::new(crop.x as f32, (crop.y + crop.height) as f32),
+                Pos2::new((crop.x + crop.width) as f32, (crop.y + crop.height) as f32),
+            ];
+
+            // 拖动角点时以对角为固定点
+            self.drag_mode = match screen_corners.iter().position(|c| c.distance(pointer) <= HANDLE_RADIUS) {
+                Some(index) => Some(DragMode::Resize(image_corners[3 - index])),
+                None if crop_screen.contains(pointer) => {
+                    Some(DragMode::Move(to_image(pointer) - image_corners[0]))
+                },
+                None => Some(DragMode::Resize(to_image(pointer))),
+            };
+        }
+
+        if response.dragged() {
+            let (width, height) = self.source_size();
+            let current = to_image(pointer);
+
+            match self.drag_mode {
+                Some(DragMode::Move(offset)) => {
+                    let crop = self.edit.crop;
+                    let x = (current.x - offset.x).clamp(0.0, (width - crop.width) as f32);
+                    let y = (current.y - offset.y).clamp(0.0, (height - crop.height) as f32);
+                    self.edit.crop.x = x.round() as u32;
+                    self.edit.crop.y = y.round() as u32;
+                    self.dirty = true;
+                },
+                Some(DragMode::Resize(anchor)) => {
+                    self.edit.crop = rect_from_points(anchor, current, self.square_crop).clamped(width, height);
+                    self.dirty = true;
+                },
+                None => {},
+            }
+        }
+
+        if response.drag_released() {
+            self.drag_mode = None;
+        }
+    }
+}
+
+// 由两个点生成裁剪框，锁定正方形时取较短的边
+fn rect_from_points(anchor: Pos2, current: Pos2, square: bool) -> CropRect {
+    let mut delta = current - anchor;
+    if square {
+        let side = delta.x.abs().min(delta.y.abs());
+        delta = Vec2::new(side * delta.x.signum(), side * delta.y.signum());
+    }
+
+    let min = anchor.min(anchor + delta);
+    let max = anchor.max(anchor + delta);
+    CropRect {
+        x: min.x.round() as u32,
+        y: min.y.round() as u32,
+        width: ((max.x - min.x).round() as u32).max(1),
+        height: ((max.y - min.y).round() as u32).max(1),
+    }
+}
+
+// 左上、右上、左下、右下
+fn corners(rect: Rect) -> [Pos2; 4] {
+    [rect.left_top(), rect.right_top(), rect.left_bottom(), rect.right_bottom()]
+}
+
+fn full_uv() -> Rect {
+    Rect::from_min_max(Pos2::ZERO, Pos2::new(1.0, 1.0))
+}
+
+// 透明区域的棋盘格背景
+fn paint_checkerboard(painter: &Painter, rect: Rect) {
+    let cell = 8.0;
+    painter.rect_filled(rect, Rounding::ZERO, Color32::WHITE);
+
+    let cols = (rect.width() / cell).ceil() as usize;
+    let rows = (rect.height() / cell).ceil() as usize;
+    for row in 0..rows {
+        for col in 0..cols {
+            if (row + col) % 2 == 0 {
+                continue;
+            }
+            let min = rect.min + Vec2::new(col as f32 * cell, row as f32 * cell);
+            let cell_rect = Rect::from_min_size(min, Vec2::splat(cell)).intersect(rect);
+            painter.rect_filled(cell_rect, Rounding::ZERO, Color32::from_gray(220));
+        }
+    }
+}
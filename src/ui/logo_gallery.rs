@@ -0,0 +1,494 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use egui::{Align2, Color32, FontId, Pos2, Rect, RichText, Rounding, ScrollArea, Sense, Stroke, TextureHandle, Ui, Vec2};
+use log::error;
+
+use crate::data::sponsor::Sponsor;
+use crate::data::team::Team;
+use crate::logo;
+use crate::ui::widgets;
+use crate::ui::theme;
+
+// 缩略图尺寸
+const THUMBNAIL_SIZE: u32 = 64;
+const CELL_SIZE: Vec2 = Vec2::new(96.0, 100.0);
+
+// 每帧最多加载的缩略图数量，避免滚动时卡顿
+const THUMBNAILS_PER_FRAME: usize = 8;
+
+// 每帧最多检查尺寸的Logo数量，只读取文件头，比加载缩略图快得多
+const SIZE_CHECKS_PER_FRAME: usize = 32;
+
+// 已检查过的Logo尺寸，文件修改时间不变时不再重新读取
+type SizeCache = HashMap<PathBuf, (SystemTime, (u32, u32))>;
+
+/// 点击缩略图后需要由应用处理的操作
+pub enum GalleryAction {
+    OpenTeam(i64),
+}
+
+#[derive(PartialEq, Clone, Copy)]
+pub enum GalleryFilter {
+    All,
+    Problems,
+    Missing,
+    WrongSize,
+}
+
+impl GalleryFilter {
+    fn as_str(&self) -> &'static str {
+        match self {
+            GalleryFilter::All => t!("全部"),
+            GalleryFilter::Problems => t!("仅有问题"),
+            GalleryFilter::Missing => t!("仅缺失"),
+            GalleryFilter::WrongSize => t!("仅尺寸不符"),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Hash, Clone)]
+enum GalleryKey {
+    Team(i64),
+    Sponsor(String),
+}
+
+#[derive(PartialEq, Clone, Copy)]
+enum LogoStatus {
+    Ok,
+    Missing,
+    WrongSize(u32, u32),
+    /// 文件存在，尺寸尚未检查
+    Unchecked,
+}
+
+struct GalleryItem {
+    key: GalleryKey,
+    name: String,
+    path: PathBuf,
+    status: LogoStatus,
+    modified: Option<SystemTime>,
+}
+
+impl GalleryItem {
+    // 只读取文件的修改时间，尺寸使用缓存或之后在界面中逐帧检查
+    fn new(key: GalleryKey, name: String, path: PathBuf, check_size: bool, sizes: &SizeCache) -> Self {
+        let modified = std::fs::metadata(&path)
+            .ok()
+            .filter(|meta| meta.is_file())
+            .map(|meta| meta.modified().unwrap_or(SystemTime::UNIX_EPOCH));
+
+        let status = match modified {
+            None => LogoStatus::Missing,
+            Some(_) if !check_size => LogoStatus::Ok,
+            Some(modified) => match sizes.get(&path) {
+                Some(&(cached_at, size)) if cached_at == modified => size_status(size),
+                _ => LogoStatus::Unchecked,
+            },
+        };
+
+        GalleryItem { key, name, path, status, modified }
+    }
+
+    // 读取图片文件头检查尺寸，无法读取时视为缺失
+    fn check_size(&mut self, sizes: &mut SizeCache) {
+        self.status = match image::image_dimensions(&self.path) {
+            Ok(size) => {
+                if let Some(modified) = self.modified {
+                    sizes.insert(self.path.clone(), (modified, size));
+                }
+                size_status(size)
+            },
+            Err(_) => LogoStatus::Missing,
+        };
+    }
+
+    fn matches(&self, filter: GalleryFilter, search_term: &str) -> bool {
+        let status_ok = match filter {
+            GalleryFilter::All => true,
+            GalleryFilter::Problems => matches!(self.status, LogoStatus::Missing | LogoStatus::WrongSize(..)),
+            GalleryFilter::Missing => self.status == LogoStatus::Missing,
+            GalleryFilter::WrongSize => matches!(self.status, LogoStatus::WrongSize(..)),
+        };
+        status_ok && (search_term.is_empty() || self.name.to_lowercase().contains(search_term))
+    }
+}
+
+pub struct LogoGalleryView {
+    pub filter: GalleryFilter,
+    pub search_text: String,
+    team_items: Vec<GalleryItem>,
+    sponsor_items: Vec<GalleryItem>,
+    sponsors: HashMap<String, Sponsor>,
+    selected_sponsor: Option<String>,
+    // 已加载的缩略图，None表示加载失败或文件不存在
+    textures: HashMap<GalleryKey, Option<TextureHandle>>,
+    sizes: SizeCache,
+    loads_this_frame: usize,
+}
+
+impl LogoGalleryView {
+    pub fn new() -> Self {
+        LogoGalleryView {
+            filter: GalleryFilter::All,
+            search_text: String::new(),
+            team_items: Vec::new(),
+            sponsor_items: Vec::new(),
+            sponsors: HashMap::new(),
+            selected_sponsor: None,
+            textures: HashMap::new(),
+            sizes: HashMap::new(),
+            loads_this_frame: 0,
+        }
+    }
+
+    /// 设置球队和赞助商数据，并检查每个Logo是否存在，尺寸在显示时逐帧检查
+    pub fn set_data(&mut self, db_dir: &Path, teams: &[Team], sponsors: Vec<Sponsor>) {
+        self.team_items = teams.iter()
+            .map(|team| GalleryItem::new(
+                GalleryKey::Team(team.id),
+                team.name.clone(),
+                logo::logo_path(db_dir, team.id),
+                true,
+                &self.sizes,
+            ))
+            .collect();
+
+        // 数据库中的赞助商，以及目录中没有对应赞助商的Logo文件
+        let mut sponsor_paths: BTreeMap<String, PathBuf> = sponsors.iter()
+            .map(|s| (s.sponsor_name.clone(), logo::sponsor_logo_path(db_dir, &s.sponsor_name)))
+            .collect();
+        if let Ok(files) = logo::list_image_files(&logo::sponsor_logo_dir(db_dir)) {
+            for file in files {
+                if let Some(stem) = file.file_stem() {
+                    sponsor_paths.entry(stem.to_string_lossy().to_string()).or_insert(file);
+                }
+            }
+        }
+
+        self.sponsor_items = sponsor_paths.into_iter()
+            .map(|(name, path)| GalleryItem::new(GalleryKey::Sponsor(name.clone()), name, path, false, &self.sizes))
+            .collect();
+        self.sponsors = sponsors.into_iter()
+            .map(|s| (s.sponsor_name.clone(), s))
+            .collect();
+        self.selected_sponsor = None;
+        self.textures.clear();
+    }
+
+    /// 球队Logo变化后重新检查状态和缩略图
+    pub fn invalidate_team(&mut self, team_id: i64) {
+        let key = GalleryKey::Team(team_id);
+        if let Some(item) = self.team_items.iter_mut().find(|item| item.key == key) {
+            *item = GalleryItem::new(key.clone(), item.name.clone(), item.path.clone(), true, &self.sizes);
+        }
+        self.textures.remove(&key);
+    }
+
+    /// 重新检查全部球队Logo
+    pub fn invalidate_all_teams(&mut self) {
+        for item in &mut self.team_items {
+            *item = GalleryItem::new(item.key.clone(), item.name.clone(), item.path.clone(), true, &self.sizes);
+        }
+        self.textures.retain(|key, _| !matches!(key, GalleryKey::Team(_)));
+    }
+
+    // 检查一批尚未检查尺寸的球队Logo，返回剩余的数量
+    fn check_pending_sizes(&mut self) -> usize {
+        let mut pending = self.team_items.iter_mut()
+            .filter(|item| item.status == LogoStatus::Unchecked);
+        for item in pending.by_ref().take(SIZE_CHECKS_PER_FRAME) {
+            item.check_size(&mut self.sizes);
+        }
+        pending.count()
+    }
+
+    pub fn ui(&mut self, ui: &mut Ui) -> Option<GalleryAction> {
+        let palette = theme::palette();
+        let mut action = None;
+        self.loads_this_frame = 0;
+        let unchecked = self.check_pending_sizes();
+
+        if self.team_items.is_empty() && self.sponsor_items.is_empty() {
+            ui.vertical_centered(|ui| {
+                ui.add_space(50.0);
+                ui.heading(t!("没有可显示的Logo"));
+                ui.add_space(20.0);
+                ui.label(t!("请先加载数据库"));
+                ui.add_space(50.0);
+            });
+            return None;
+        }
+
+        widgets::titled_frame(t!("Logo总览"), ui, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(t!("搜索:"));
+                ui.add(egui::TextEdit::singleline(&mut self.search_text)
+                    .hint_text(t!("球队或赞助商名称..."))
+                    .desired_width(150.0));
+
+                ui.add_space(10.0);
+                for filter in [GalleryFilter::All, GalleryFilter::Problems, GalleryFilter::Missing, GalleryFilter::WrongSize] {
+                    ui.selectable_value(&mut self.filter, filter, filter.as_str());
+                }
+            });
+
+            ui.horizontal(|ui| {
+                let missing = count_status(&self.team_items, |s| s == LogoStatus::Missing);
+                let wrong_size = count_status(&self.team_items, |s| matches!(s, LogoStatus::WrongSize(..)));
+                let sponsor_missing = count_status(&self.sponsor_items, |s| s == LogoStatus::Missing);
+
+                ui.label(t!("球队 {} 个，", self.team_items.len()));
+                ui.colored_label(palette.error, t!("缺失 {}", missing));
+                ui.colored_label(palette.warning, t!("尺寸不符 {}", wrong_size));
+                ui.label(t!("；赞助商 {} 个，", self.sponsor_items.len()));
+                ui.colored_label(palette.error, t!("缺失 {}", sponsor_missing));
+                if unchecked > 0 {
+                    ui.label(RichText::new(t!("（正在检查 {} 个Logo的尺寸）", unchecked)).color(palette.weak_text));
+                }
+            });
+
+            if let Some(name) = self.selected_sponsor.clone() {
+                ui.add_space(5.0);
+                self.sponsor_details_ui(ui, &name);
+            }
+        });
+
+        ui.add_space(10.0);
+
+        let search_term = self.search_text.to_lowercase();
+
+        ScrollArea::vertical()
+            .id_source("logo_gallery")
+            .auto_shrink([false, false])
+            .show(ui, |ui| {
+                egui::CollapsingHeader::new(RichText::new(t!("球队Logo")).strong())
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        let indices: Vec<usize> = (0..self.team_items.len())
+                            .filter(|&i| self.team_items[i].matches(self.filter, &search_term))
+                            .collect();
+
+                        if let Some(GalleryKey::Team(team_id)) = self.thumbnail_grid(ui, true, &indices) {
+                            action = Some(GalleryAction::OpenTeam(team_id));
+                        }
+                    });
+
+                ui.add_space(10.0);
+
+                egui::CollapsingHeader::new(RichText::new(t!("赞助商Logo")).strong())
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        let indices: Vec<usize> = (0..self.sponsor_items.len())
+                            .filter(|&i| self.sponsor_items[i].matches(self.filter, &search_term))
+                            .collect();
+
+                        if let Some(GalleryKey::Sponsor(name)) = self.thumbnail_grid(ui, false, &indices) {
+                            self.selected_sponsor = Some(name);
+                        }
+                    });
+            });
+
+        // 还有未加载的缩略图或未检查的尺寸时继续刷新
+        if self.loads_this_frame >= THUMBNAILS_PER_FRAME || unchecked > 0 {
+            ui.ctx().request_repaint();
+        }
+
+        action
+    }
+
+    // 绘制缩略图网格，返回被点击的项目
+    fn thumbnail_grid(&mut self, ui: &mut Ui, teams: bool, indices: &[usize]) -> Option<GalleryKey> {
+        let palette = theme::palette();
+        if indices.is_empty() {
+            ui.label(RichText::new(t!("没有符合条件的Logo")).color(palette.weak_text));
+            return None;
+        }
+
+        let mut clicked = None;
+
+        ui.horizontal_wrapped(|ui| {
+            ui.spacing_mut().item_spacing = Vec2::new(6.0, 6.0);
+
+            for &index in indices {
+                let item = if teams { &self.team_items[index] } else { &self.sponsor_items[index] };
+                let (rect, response) = ui.allocate_exact_size(CELL_SIZE, Sense::click());
+
+                // 只加载可见区域内的缩略图
+                if !ui.is_rect_visible(rect) {
+                    continue;
+                }
+
+                let key = item.key.clone();
+                let status = item.status;
+                let name = item.name.clone();
+                let path = item.path.clone();
+
+                let texture = self.thumbnail(ui.ctx(), &key, &path, status);
+                paint_cell(ui, rect, &name, texture.as_ref(), status, response.hovered());
+
+                let response = response.on_hover_ui(|ui| {
+                    ui.strong(&name);
+                    if let GalleryKey::Team(team_id) = &key {
+                        ui.label(t!("球队ID: {}", team_id));
+                    }
+                    ui.label(path.display().to_string());
+                    match status {
+                        LogoStatus::Ok => ui.label(t!("Logo正常")),
+                        LogoStatus::Unchecked => ui.label(t!("正在检查尺寸...")),
+                        LogoStatus::Missing => ui.colored_label(palette.error, t!("缺少Logo文件")),
+                        LogoStatus::WrongSize(w, h) => ui.colored_label(
+                            palette.warning,
+                            t!("尺寸为 {}×{}，游戏要求 {}×{}", w, h, logo::LOGO_SIZE, logo::LOGO_SIZE),
+                        ),
+                    };
+                });
+
+                if response.clicked() {
+                    clicked = Some(key);
+                }
+            }
+        });
+
+        clicked
+    }
+
+    // 获取缓存的缩略图，未加载时按每帧配额加载
+    fn thumbnail(&mut self, ctx: &egui::Context, key: &GalleryKey, path: &Path, status: LogoStatus) -> Option<TextureHandle> {
+        if let Some(texture) = self.textures.get(key) {
+            return texture.clone();
+        }
+
+        if status == LogoStatus::Missing {
+            self.textures.insert(key.clone(), None);
+            return None;
+        }
+
+        if self.loads_this_frame >= THUMBNAILS_PER_FRAME {
+            return None;
+        }
+        self.loads_this_frame += 1;
+
+        let name = match key {
+            GalleryKey::Team(team_id) => format!("gallery_team_{}", team_id),
+            GalleryKey::Sponsor(sponsor_name) => format!("gallery_sponsor_{}", sponsor_name),
+        };
+        let texture = match logo::load_thumbnail_texture(ctx, path, THUMBNAIL_SIZE, name) {
+            Ok(texture) => Some(texture),
+            Err(e) => {
+                error!("加载缩略图失败 {}: {}", path.display(), e);
+                None
+            }
+        };
+
+        self.textures.insert(key.clone(), texture.clone());
+        texture
+    }
+
+    fn sponsor_details_ui(&mut self, ui: &mut Ui, name: &str) {
+        let mut close = false;
+
+        widgets::rounded_frame(ui, |ui| {
+            ui.horizontal(|ui| {
+                ui.strong(t!("赞助商: {}", name));
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.small_button("✕").clicked() {
+                        close = true;
+                    }
+                });
+            });
+
+            match self.sponsors.get(name) {
+                Some(sponsor) => {
+                    widgets::label_value(ui, t!("类型:"), &sponsor.sponsor_type);
+                    widgets::label_value(ui, t!("行业:"), &sponsor.industry);
+                    widgets::label_value(ui, t!("总部:"), &sponsor.headquarter_location);
+                    widgets::label_value(ui, t!("描述:"), &sponsor.description);
+                },
+                None => {
+                    ui.colored_label(theme::palette().warning, t!("数据库中没有同名的赞助商，该Logo不会被游戏使用"));
+                },
+            }
+
+            let path = self.sponsor_items.iter()
+                .find(|item| item.key == GalleryKey::Sponsor(name.to_string()))
+                .map(|item| item.path.display().to_string())
+                .unwrap_or_default();
+            widgets::label_value(ui, t!("Logo文件:"), &path);
+        });
+
+        if close {
+            self.selected_sponsor = None;
+        }
+    }
+}
+
+fn size_status((width, height): (u32, u32)) -> LogoStatus {
+    if width != logo::LOGO_SIZE || height != logo::LOGO_SIZE {
+        LogoStatus::WrongSize(width, height)
+    } else {
+        LogoStatus::Ok
+    }
+}
+
+fn count_status(items: &[GalleryItem], predicate: impl Fn(LogoStatus) -> bool) -> usize {
+    items.iter().filter(|item| predicate(item.status)).count()
+}
+
+fn paint_cell(ui: &Ui, rect: Rect, name: &str, texture: Option<&TextureHandle>, status: LogoStatus, hovered: bool) {
+    let painter = ui.painter();
+    let palette = theme::palette();
+
+    let (stroke_color, fill) = match status {
+        LogoStatus::Ok | LogoStatus::Unchecked => (palette.border, palette.surface),
+        LogoStatus::Missing => (palette.error, palette.tint(palette.error)),
+        LogoStatus::WrongSize(..) => (palette.warning, palette.tint(palette.warning)),
+    };
+    let stroke_width = if hovered { 2.5 } else { 1.0 };
+    painter.rect(rect, Rounding::same(6.0), fill, Stroke::new(stroke_width, stroke_color));
+
+    // 缩略图区域
+    let image_rect = Rect::from_center_size(
+        Pos2::new(rect.center().x, rect.top() + 6.0 + THUMBNAIL_SIZE as f32 / 2.0),
+        Vec2::splat(THUMBNAIL_SIZE as f32),
+    );
+
+    match texture {
+        Some(texture) => {
+            let size = texture.size_vec2();
+            let scale = (THUMBNAIL_SIZE as f32 / size.x.max(size.y)).min(1.0);
+            let fitted = Rect::from_center_size(image_rect.center(), size * scale);
+            painter.image(
+                texture.id(),
+                fitted,
+                Rect::from_min_max(Pos2::ZERO, Pos2::new(1.0, 1.0)),
+                Color32::WHITE,
+            );
+        },
+        None => {
+            let text = if status == LogoStatus::Missing { t!("缺失") } else { "…" };
+            painter.text(
+                image_rect.center(),
+                Align2::CENTER_CENTER,
+                text,
+                FontId::proportional(14.0),
+                palette.weak_text,
+            );
+        },
+    }
+
+    // 名称，过长时截断
+    let label: String = if name.chars().count() > 8 {
+        format!("{}…", name.chars().take(7).collect::<String>())
+    } else {
+        name.to_string()
+    };
+    painter.text(
+        Pos2::new(rect.center().x, rect.bottom() - 14.0),
+        Align2::CENTER_CENTER,
+        label,
+        FontId::proportional(12.0),
+        palette.text,
+    );
+}
@@ -0,0 +1,340 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use egui::{Context, Grid, RichText, ScrollArea, Stroke, Rounding};
+use log::error;
+
+use crate::data::team::Team;
+use crate::error::Result;
+use crate::logo::{self, LogoMatch, MatchKind};
+use crate::ui::dialogs::setup_mac_window;
+use crate::ui::widgets;
+use crate::ui::theme;
+
+// 每帧处理的图片数量，保证导入过程中界面仍可刷新
+const FILES_PER_FRAME: usize = 2;
+
+/// 目标球队已有Logo时的处理方式
+#[derive(PartialEq, Clone, Copy)]
+pub enum ExistingLogoPolicy {
+    Skip,
+    Overwrite,
+}
+
+impl ExistingLogoPolicy {
+    fn as_str(&self) -> &'static str {
+        match self {
+            ExistingLogoPolicy::Skip => t!("跳过已有Logo"),
+            ExistingLogoPolicy::Overwrite => t!("覆盖已有Logo"),
+        }
+    }
+}
+
+struct ImportRow {
+    logo_match: LogoMatch,
+    team_id_text: String,
+    enabled: bool,
+}
+
+#[derive(Default)]
+struct ImportSummary {
+    imported: usize,
+    skipped: usize,
+    failed: Vec<String>,
+}
+
+pub struct LogoImportDialog {
+    pub open: bool,
+    pub policy: ExistingLogoPolicy,
+    folder: Option<PathBuf>,
+    db_dir: Option<PathBuf>,
+    team_names: HashMap<i64, String>,
+    rows: Vec<ImportRow>,
+    running: bool,
+    next_row: usize,
+    summary: Option<ImportSummary>,
+    error_message: Option<String>,
+}
+
+impl LogoImportDialog {
+    pub fn new() -> Self {
+        LogoImportDialog {
+            open: false,
+            policy: ExistingLogoPolicy::Skip,
+            folder: None,
+            db_dir: None,
+            team_names: HashMap::new(),
+            rows: Vec::new(),
+            running: false,
+            next_row: 0,
+            summary: None,
+            error_message: None,
+        }
+    }
+
+    pub fn open(&mut self, folder: &Path, db_dir: &Path, teams: &[Team]) -> Result<()> {
+        let files = logo::list_image_files(folder)?;
+        let matches = logo::match_logo_files(&files, teams);
+
+        self.rows = matches.into_iter()
+            .map(|logo_match| ImportRow {
+                team_id_text: logo_match.team_id.map(|id| id.to_string()).unwrap_or_default(),
+                enabled: logo_match.team_id.is_some(),
+                logo_match,
+            })
+            .collect();
+        self.team_names = teams.iter().map(|t| (t.id, t.name.clone())).collect();
+        self.folder = Some(folder.to_path_buf());
+        self.db_dir = Some(db_dir.to_path_buf());
+        self.running = false;
+        self.next_row = 0;
+        self.summary = None;
+        self.error_message = None;
+        self.open = true;
+        Ok(())
+    }
+
+    fn matched_count(&self) -> usize {
+        self.rows.iter().filter(|row| row.enabled && row.logo_match.team_id.is_some()).count()
+    }
+
+    // 被多个勾选的图片作为目标的球队ID，导入时后面的图片会覆盖前面的
+    fn duplicate_team_ids(&self) -> HashSet<i64> {
+        let mut seen = HashSet::new();
+        self.rows.iter()
+            .filter(|row| row.enabled)
+            .filter_map(|row| row.logo_match.team_id)
+            .filter(|&team_id| !seen.insert(team_id))
+            .collect()
+    }
+
+    // 处理一批图片，全部完成时返回true
+    fn process_batch(&mut self) -> bool {
+        let db_dir = match &self.db_dir {
+            Some(dir) => dir.clone(),
+            None => return true,
+        };
+        let summary = self.summary.get_or_insert_with(ImportSummary::default);
+
+        let mut processed = 0;
+        while self.next_row < self.rows.len() && processed < FILES_PER_FRAME {
+            let row = &self.rows[self.next_row];
+            self.next_row += 1;
+
+            let team_id = match (row.enabled, row.logo_match.team_id) {
+                (true, Some(team_id)) => team_id,
+                _ => continue,
+            };
+            processed += 1;
+
+            if self.policy == ExistingLogoPolicy::Skip && logo::logo_info(&db_dir, team_id).exists() {
+                summary.skipped += 1;
+                continue;
+            }
+
+            match logo::import_logo_file(&db_dir, team_id, &row.logo_match.file) {
+                Ok(_) => summary.imported += 1,
+                Err(e) => {
+                    error!("导入Logo失败 {}: {}", row.logo_match.file.display(), e);
+                    summary.failed.push(format!("{}: {}", file_name(&row.logo_match.file), e));
+                }
+            }
+        }
+
+        self.next_row >= self.rows.len()
+    }
+
+    /// 显示对话框，导入完成时返回true
+    pub fn show(&mut self, ctx: &Context) -> bool {
+        if !self.open {
+            return false;
+        }
+        let palette = theme::palette();
+
+        let mut finished = false;
+        if self.running {
+            if self.process_batch() {
+                self.running = false;
+                finished = true;
+            } else {
+                ctx.request_repaint();
+            }
+        }
+
+        let mut closed = false;
+        let duplicates = self.duplicate_team_ids();
+
+        setup_mac_window(t!("批量导入Logo"))
+            .fixed_size([720.0, 520.0])
+            .show(ctx, |ui| {
+                ui.add_space(5.0);
+                ui.heading(t!("从文件夹批量导入Logo"));
+                ui.add_space(5.0);
+                widgets::horizontal_separator(ui);
+
+                if let Some(folder) = &self.folder {
+                    widgets::label_value(ui, t!("文件夹:"), &folder.display().to_string());
+                }
+                ui.label(t!(
+                    "共 {} 个图片文件，已匹配 {} 个。文件名可以是球队ID（如 123 或 L123）或球队名称。",
+                    self.rows.len(),
+                    self.matched_count()
+                ));
+
+                ui.add_enabled_ui(!self.running, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(t!("已有Logo:"));
+                        for policy in [ExistingLogoPolicy::Skip, ExistingLogoPolicy::Overwrite] {
+                            ui.radio_value(&mut self.policy, policy, policy.as_str());
+                        }
+                    });
+                });
+
+                ui.add_space(5.0);
+
+                // 匹配结果表格
+                egui::Frame::none()
+                    .fill(palette.surface)
+                    .stroke(Stroke::new(1.0, palette.border))
+                    .rounding(Rounding::same(6.0))
+                    .inner_margin(egui::Margin::same(8.0))
+                    .show(ui, |ui| {
+                        ScrollArea::vertical()
+                            .max_height(280.0)
+                            .auto_shrink([false, false])
+                            .show(ui, |ui| {
+                                ui.add_enabled_ui(!self.running, |ui| {
+                                    Grid::new("logo_import_grid")
+                                        .num_columns(5)
+                                        .striped(true)
+                                        .spacing([10.0, 4.0])
+                                        .show(ui, |ui| {
+                                            ui.strong(t!("导入"));
+                                            ui.strong(t!("文件"));
+                                            ui.strong(t!("匹配方式"));
+                                            ui.strong(t!("球队ID"));
+                                            ui.strong(t!("球队"));
+                                            ui.end_row();
+
+                                            for row in &mut self.rows {
+                                                ui.checkbox(&mut row.enabled, "");
+                                                ui.label(file_name(&row.logo_match.file));
+
+                                                let kind_color = match row.logo_match.kind {
+                                                    MatchKind::Unmatched => palette.error,
+                                                    MatchKind::FuzzyName(_) => palette.warning,
+                                                    _ => palette.success,
+                                                };
+                                                ui.colored_label(kind_color, row.logo_match.kind.as_str());
+
+                                                let response = ui.add(egui::TextEdit::singleline(&mut row.team_id_text)
+                                                    .desired_width(60.0));
+                                                if response.changed() {
+                                                    // 手动修改球队ID
+                                                    let team_id = row.team_id_text.trim().parse::<i64>().ok()
+                                                        .filter(|id| self.team_names.contains_key(id));
+                                                    row.logo_match.team_id = team_id;
+                                                    row.logo_match.kind = if team_id.is_some() { MatchKind::Manual } else { MatchKind::Unmatched };
+                                                    row.enabled = team_id.is_some();
+                                                }
+
+                                                let duplicate = row.enabled && row.logo_match.team_id.is_some_and(|id| duplicates.contains(&id));
+                                                match row.logo_match.team_id.and_then(|id| self.team_names.get(&id)) {
+                                                    Some(name) if duplicate => {
+                                                        ui.colored_label(palette.error, format!("⚠ {}", name))
+                                                            .on_hover_text(t!("多个图片对应同一个球队"));
+                                                    },
+                                                    Some(name) => {
+                                                        ui.label(name);
+                                                    },
+                                                    None => {
+                                                        ui.label(RichText::new("-").color(palette.weak_text));
+                                                    },
+                                                }
+                                                ui.end_row();
+                                            }
+                                        });
+                                });
+                            });
+                    });
+
+                ui.add_space(5.0);
+
+                if !duplicates.is_empty() {
+                    widgets::error_message(ui, &t!(
+                        "有 {} 个球队对应了多个图片，请取消勾选多余的图片或修改球队ID后再导入",
+                        duplicates.len()
+                    ));
+                }
+
+                // 进度
+                if self.running || self.summary.is_some() {
+                    let progress = if self.rows.is_empty() {
+                        1.0
+                    } else {
+                        self.next_row as f32 / self.rows.len() as f32
+                    };
+                    ui.add(egui::ProgressBar::new(progress).show_percentage());
+                }
+
+                if let Some(summary) = &self.summary {
+                    if !self.running {
+                        ui.label(t!(
+                            "导入完成：成功 {} 个，跳过 {} 个，失败 {} 个",
+                            summary.imported,
+                            summary.skipped,
+                            summary.failed.len()
+                        ));
+                        for failure in &summary.failed {
+                            widgets::error_message(ui, failure);
+                        }
+                    }
+                }
+
+                if let Some(error) = &self.error_message {
+                    widgets::error_message(ui, error);
+                }
+
+                ui.add_space(5.0);
+                widgets::horizontal_separator(ui);
+
+                ui.horizontal(|ui| {
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if !self.running && widgets::mac_primary_button(ui, t!("开始导入")) {
+                            if self.matched_count() == 0 {
+                                self.error_message = Some(t!("没有可导入的图片").to_string());
+                            } else if duplicates.is_empty() {
+                                // 有重复的球队时不开始导入，表格下方已提示原因
+                                self.error_message = None;
+                                self.summary = None;
+                                self.next_row = 0;
+                                self.running = true;
+                            }
+                        }
+
+                        ui.add_space(10.0);
+
+                        if !self.running && widgets::mac_button(ui, t!("关闭")) {
+                            closed = true;
+                        }
+                    });
+                });
+            });
+
+        if closed {
+            self.open = false;
+        }
+
+        finished
+    }
+
+    pub fn imported_count(&self) -> usize {
+        self.summary.as_ref().map_or(0, |summary| summary.imported)
+    }
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
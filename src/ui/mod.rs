@@ -4,5 +4,20 @@ pub mod staff_list;
 pub mod dialogs;
 pub mod widgets; 
 pub mod theme;
+pub mod theme_dialog;
 pub mod visualization; 
+pub mod scatter_plot;
+pub mod compare_view;
+pub mod logo_import;
+pub mod logo_gallery;
+pub mod logo_editor;
+pub mod statistics_view;
+pub mod balance_dialog;
+pub mod generator_dialog;
+pub mod changes_view;
+pub mod audit_dialog;
+pub mod conflict_dialog;
+pub mod error_dialog;
+pub mod font_dialog;
+pub mod notifications;
 // pub mod sponsor_editor; // 已移除杂项编辑器功能 
\ No newline at end of file
@@ -0,0 +1,285 @@
+// 通知中心
+//
+// 成功和提示信息以自动消失的浮动通知显示，警告保留到手动关闭，都不会阻塞其他操作。
+// 所有通知（包括错误）都会记入历史，可在通知历史面板中查看。
+
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Local};
+use egui::{Align2, Color32, Context, RichText, Rounding, ScrollArea, Stroke};
+
+use crate::ui::theme;
+
+// 同时显示的最大通知数量，超出时丢弃最早的
+const MAX_VISIBLE: usize = 5;
+
+// 历史记录保留的最大条数
+const MAX_HISTORY: usize = 200;
+
+// 成功和提示通知的显示时长
+const TOAST_DURATION: Duration = Duration::from_secs(4);
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum NotificationLevel {
+    Success,
+    Info,
+    Warning,
+    Error,
+}
+
+impl NotificationLevel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationLevel::Success => t!("成功"),
+            NotificationLevel::Info => t!("提示"),
+            NotificationLevel::Warning => t!("警告"),
+            NotificationLevel::Error => t!("错误"),
+        }
+    }
+
+    fn icon(&self) -> &'static str {
+        match self {
+            NotificationLevel::Success => "✔",
+            NotificationLevel::Info => "ℹ",
+            NotificationLevel::Warning => "⚠",
+            NotificationLevel::Error => "✖",
+        }
+    }
+
+    fn accent(&self) -> Color32 {
+        let palette = theme::palette();
+        match self {
+            NotificationLevel::Success => palette.success,
+            NotificationLevel::Info => palette.accent,
+            NotificationLevel::Warning => palette.warning,
+            NotificationLevel::Error => palette.error,
+        }
+    }
+
+    fn background(&self) -> Color32 {
+        theme::palette().tint(self.accent())
+    }
+
+    // 警告和错误需要用户确认，不会自动消失
+    fn auto_dismiss(&self) -> bool {
+        matches!(self, NotificationLevel::Success | NotificationLevel::Info)
+    }
+}
+
+#[derive(Clone)]
+pub struct Notification {
+    pub level: NotificationLevel,
+    pub title: String,
+    pub message: String,
+    pub time: DateTime<Local>,
+}
+
+// 正在显示的浮动通知
+struct Toast {
+    notification: Notification,
+    shown_at: Instant,
+}
+
+pub struct NotificationCenter {
+    toasts: Vec<Toast>,
+    pub history: Vec<Notification>,
+    pub history_open: bool,
+    /// 上次打开历史面板后新增的通知数量
+    pub unread: usize,
+}
+
+impl NotificationCenter {
+    pub fn new() -> Self {
+        NotificationCenter {
+            toasts: Vec::new(),
+            history: Vec::new(),
+            history_open: false,
+            unread: 0,
+        }
+    }
+
+    pub fn success(&mut self, message: &str) {
+        self.push(NotificationLevel::Success, t!("成功"), message);
+    }
+
+    pub fn info(&mut self, title: &str, message: &str) {
+        self.push(NotificationLevel::Info, title, message);
+    }
+
+    pub fn warn(&mut self, title: &str, message: &str) {
+        self.push(NotificationLevel::Warning, title, message);
+    }
+
+    /// 错误由错误对话框显示，这里只记入历史
+    pub fn record_error(&mut self, title: &str, message: &str) {
+        self.add_history(Notification {
+            level: NotificationLevel::Error,
+            title: title.to_string(),
+            message: message.to_string(),
+            time: Local::now(),
+        });
+    }
+
+    pub fn push(&mut self, level: NotificationLevel, title: &str, message: &str) {
+        let notification = Notification {
+            level,
+            title: title.to_string(),
+            message: message.to_string(),
+            time: Local::now(),
+        };
+        self.add_history(notification.clone());
+
+        // 重复操作产生的相同通知只显示一次，重新开始计时
+        self.toasts.retain(|t| {
+            t.notification.level != level || t.notification.title != title || t.notification.message != message
+        });
+        self.toasts.push(Toast {
+            notification,
+            shown_at: Instant::now(),
+        });
+        if self.toasts.len() > MAX_VISIBLE {
+            self.toasts.remove(0);
+        }
+    }
+
+    fn add_history(&mut self, notification: Notification) {
+        self.history.push(notification);
+        if self.history.len() > MAX_HISTORY {
+            self.history.remove(0);
+        }
+        if !self.history_open {
+            self.unread += 1;
+        }
+    }
+
+    pub fn toggle_history(&mut self) {
+        self.history_open = !self.history_open;
+        if self.history_open {
+            self.unread = 0;
+        }
+    }
+
+    pub fn show(&mut self, ctx: &Context) {
+        self.show_toasts(ctx);
+        self.show_history(ctx);
+    }
+
+    fn show_toasts(&mut self, ctx: &Context) {
+        self.toasts.retain(|t| !t.notification.level.auto_dismiss() || t.shown_at.elapsed() < TOAST_DURATION);
+        if self.toasts.is_empty() {
+            return;
+        }
+
+        // 到时间后需要重绘才能移除通知
+        if let Some(remaining) = self.toasts.iter()
+            .filter(|t| t.notification.level.auto_dismiss())
+            .map(|t| TOAST_DURATION.saturating_sub(t.shown_at.elapsed()))
+            .min()
+        {
+            ctx.request_repaint_after(remaining);
+        }
+
+        let mut dismissed = None;
+        let mut hovered = false;
+
+        egui::Area::new("notification_area")
+            .order(egui::Order::Foreground)
+            .anchor(Align2::RIGHT_BOTTOM, [-12.0, -36.0])
+            .show(ctx, |ui| {
+                ui.set_max_width(320.0);
+                for (index, toast) in self.toasts.iter().enumerate() {
+                    let notification = &toast.notification;
+                    let level = notification.level;
+                    let response = egui::Frame::none()
+                        .fill(level.background())
+                        .stroke(Stroke::new(1.0, level.accent()))
+                        .rounding(Rounding::same(6.0))
+                        .inner_margin(8.0)
+                        .shadow(egui::epaint::Shadow {
+                            extrusion: 4.0,
+                            color: Color32::from_black_alpha(30),
+                        })
+                        .show(ui, |ui| {
+                            ui.horizontal(|ui| {
+                                ui.label(RichText::new(level.icon()).color(level.accent()));
+                                ui.strong(&notification.title);
+                                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                                    if ui.small_button("×").on_hover_text(t!("关闭")).clicked() {
+                                        dismissed = Some(index);
+                                    }
+                                });
+                            });
+                            ui.label(&notification.message);
+                        })
+                        .response;
+
+                    if response.hovered() {
+                        hovered = true;
+                    }
+                    ui.add_space(6.0);
+                }
+            });
+
+        if let Some(index) = dismissed {
+            self.toasts.remove(index);
+        }
+
+        // 鼠标悬停时暂停计时，方便阅读
+        if hovered {
+            for toast in &mut self.toasts {
+                toast.shown_at = Instant::now();
+            }
+        }
+    }
+
+    fn show_history(&mut self, ctx: &Context) {
+        if !self.history_open {
+            return;
+        }
+
+        let mut open = true;
+        let mut clear = false;
+
+        egui::Window::new(t!("通知历史"))
+            .open(&mut open)
+            .anchor(Align2::RIGHT_TOP, [-12.0, 40.0])
+            .default_size([340.0, 420.0])
+            .collapsible(false)
+            .resizable(true)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(t!("共 {} 条", self.history.len()));
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui.add_enabled(!self.history.is_empty(), egui::Button::new(t!("清空"))).clicked() {
+                            clear = true;
+                        }
+                    });
+                });
+                ui.separator();
+
+                if self.history.is_empty() {
+                    ui.label(RichText::new(t!("暂无通知")).color(theme::palette().weak_text));
+                    return;
+                }
+
+                ScrollArea::vertical().auto_shrink([false, false]).show(ui, |ui| {
+                    // 最新的通知显示在最上方
+                    for notification in self.history.iter().rev() {
+                        let level = notification.level;
+                        ui.horizontal(|ui| {
+                            ui.label(RichText::new(level.icon()).color(level.accent())).on_hover_text(level.as_str());
+                            ui.strong(&notification.title);
+                            ui.label(RichText::new(notification.time.format("%H:%M:%S").to_string()).small().color(theme::palette().weak_text));
+                        });
+                        ui.label(&notification.message);
+                        ui.separator();
+                    }
+                });
+            });
+
+        if clear {
+            self.history.clear();
+        }
+        self.history_open = open;
+    }
+}
@@ -0,0 +1,251 @@
+// 散点图控件
+//
+// 滚轮缩放、拖动平移、双击恢复完整视图，鼠标悬停显示最近的数据点，
+// 点击数据点返回其ID。缩放和平移状态保存在控件中，需要在帧之间保留。
+
+use egui::{Align2, Color32, FontId, Pos2, Rect, Rounding, Sense, Stroke, Ui, Vec2, pos2};
+
+use crate::chart;
+use crate::ui::theme;
+
+// 悬停和点击时识别数据点的距离（像素）
+const PICK_RADIUS: f32 = 8.0;
+const POINT_RADIUS: f32 = 3.5;
+// 坐标轴标签占用的边距
+const AXIS_MARGIN: f32 = 50.0;
+const TICK_COUNT: usize = 5;
+
+/// 散点图中的一个数据点
+pub struct ScatterPoint {
+    pub id: i64,
+    pub x: f64,
+    pub y: f64,
+    pub label: String,
+    pub color: Color32,
+}
+
+// 当前显示的数据范围
+#[derive(Clone, Copy)]
+struct ViewBounds {
+    x_min: f64,
+    x_max: f64,
+    y_min: f64,
+    y_max: f64,
+}
+
+impl ViewBounds {
+    fn fit(points: &[ScatterPoint]) -> Self {
+        let mut bounds = ViewBounds {
+            x_min: f64::MAX,
+            x_max: f64::MIN,
+            y_min: f64::MAX,
+            y_max: f64::MIN,
+        };
+        for point in points {
+            bounds.x_min = bounds.x_min.min(point.x);
+            bounds.x_max = bounds.x_max.max(point.x);
+            bounds.y_min = bounds.y_min.min(point.y);
+            bounds.y_max = bounds.y_max.max(point.y);
+        }
+
+        // 留出5%的边距，所有点取值相同时扩展为宽度1的范围
+        let x_pad = ((bounds.x_max - bounds.x_min) * 0.05).max(0.5);
+        let y_pad = ((bounds.y_max - bounds.y_min) * 0.05).max(0.5);
+        ViewBounds {
+            x_min: bounds.x_min - x_pad,
+            x_max: bounds.x_max + x_pad,
+            y_min: bounds.y_min - y_pad,
+            y_max: bounds.y_max + y_pad,
+        }
+    }
+
+    fn map_to_screen(self, rect: Rect, x: f64, y: f64) -> Pos2 {
+        let tx = (x - self.x_min) / (self.x_max - self.x_min);
+        let ty = (y - self.y_min) / (self.y_max - self.y_min);
+        pos2(
+            rect.min.x + tx as f32 * rect.width(),
+            rect.max.y - ty as f32 * rect.height(),
+        )
+    }
+
+    fn map_from_screen(self, rect: Rect, pos: Pos2) -> (f64, f64) {
+        let tx = ((pos.x - rect.min.x) / rect.width()) as f64;
+        let ty = ((rect.max.y - pos.y) / rect.height()) as f64;
+        (
+            self.x_min + tx * (self.x_max - self.x_min),
+            self.y_min + ty * (self.y_max - self.y_min),
+        )
+    }
+
+    // 以(x, y)为中心缩放，factor小于1时放大
+    fn zoom(&mut self, x: f64, y: f64, factor: f64) {
+        self.x_min = x + (self.x_min - x) * factor;
+        self.x_max = x + (self.x_max - x) * factor;
+        self.y_min = y + (self.y_min - y) * factor;
+        self.y_max = y + (self.y_max - y) * factor;
+    }
+
+    fn pan(&mut self, rect: Rect, delta: Vec2) {
+        let dx = delta.x as f64 / rect.width() as f64 * (self.x_max - self.x_min);
+        let dy = delta.y as f64 / rect.height() as f64 * (self.y_max - self.y_min);
+        self.x_min -= dx;
+        self.x_max -= dx;
+        self.y_min += dy;
+        self.y_max += dy;
+    }
+}
+
+pub struct ScatterPlot {
+    id: String,
+    // None表示显示全部数据
+    view: Option<ViewBounds>,
+}
+
+impl ScatterPlot {
+    pub fn new(id: &str) -> Self {
+        ScatterPlot {
+            id: id.to_string(),
+            view: None,
+        }
+    }
+
+    /// 恢复显示全部数据
+    pub fn reset_view(&mut self) {
+        self.view = None;
+    }
+
+    /// 绘制散点图，点击数据点时返回其ID
+    pub fn ui(
+        &mut self,
+        ui: &mut Ui,
+        points: &[ScatterPoint],
+        x_label: &str,
+        y_label: &str,
+        selected_id: Option<i64>,
+        height: f32,
+    ) -> Option<i64> {
+        let palette = theme::palette();
+        let size = Vec2::new(ui.available_width(), height);
+        let (response, painter) = ui.allocate_painter(size, Sense::click_and_drag());
+        let outer = response.rect;
+        let plot_rect = Rect::from_min_max(
+            pos2(outer.min.x + AXIS_MARGIN, outer.min.y + 10.0),
+            pos2(outer.max.x - 10.0, outer.max.y - AXIS_MARGIN * 0.6),
+        );
+
+        painter.rect_filled(outer, Rounding::same(6.0), palette.surface_alt);
+        painter.rect_stroke(outer, Rounding::same(6.0), Stroke::new(1.0, palette.border));
+
+        if points.is_empty() {
+            painter.text(outer.center(), Align2::CENTER_CENTER, t!("没有数据可显示"), FontId::proportional(14.0), palette.weak_text);
+            return None;
+        }
+
+        let mut view = self.view.unwrap_or_else(|| ViewBounds::fit(points));
+
+        // 缩放和平移
+        if response.double_clicked() {
+            self.view = None;
+            view = ViewBounds::fit(points);
+        } else {
+            if let Some(hover) = response.hover_pos() {
+                let scroll = ui.input(|i| i.scroll_delta.y);
+                if scroll != 0.0 && plot_rect.contains(hover) {
+                    let (x, y) = view.map_from_screen(plot_rect, hover);
+                    view.zoom(x, y, (-scroll as f64 / 200.0).exp());
+                    self.view = Some(view);
+                }
+            }
+            if response.dragged() {
+                view.pan(plot_rect, response.drag_delta());
+                self.view = Some(view);
+            }
+        }
+
+        self.draw_axes(&painter, plot_rect, &view, x_label, y_label);
+
+        // 绘制数据点，只绘制可见区域内的点
+        let clipped = painter.with_clip_rect(plot_rect);
+        let mut nearest: Option<(usize, f32)> = None;
+        let pointer = response.hover_pos();
+
+        for (index, point) in points.iter().enumerate() {
+            let pos = view.map_to_screen(plot_rect, point.x, point.y);
+            if !plot_rect.expand(POINT_RADIUS).contains(pos) {
+                continue;
+            }
+            clipped.circle_filled(pos, POINT_RADIUS, point.color);
+
+            if let Some(pointer) = pointer {
+                let distance = pos.distance(pointer);
+                if distance <= PICK_RADIUS && nearest.is_none_or(|(_, d)| distance < d) {
+                    nearest = Some((index, distance));
+                }
+            }
+        }
+
+        // 高亮当前选中的球队
+        if let Some(point) = selected_id.and_then(|id| points.iter().find(|p| p.id == id)) {
+            let pos = view.map_to_screen(plot_rect, point.x, point.y);
+            clipped.circle_stroke(pos, POINT_RADIUS + 3.0, Stroke::new(2.0, palette.heading));
+        }
+
+        let mut clicked = None;
+        if let Some((index, _)) = nearest {
+            let point = &points[index];
+            let pos = view.map_to_screen(plot_rect, point.x, point.y);
+            clipped.circle_stroke(pos, POINT_RADIUS + 2.0, Stroke::new(1.5, palette.text));
+
+            egui::show_tooltip_at_pointer(ui.ctx(), egui::Id::new(&self.id).with("tooltip"), |ui| {
+                ui.strong(&point.label);
+                ui.label(format!("{}: {}", x_label, point.x));
+                ui.label(format!("{}: {}", y_label, point.y));
+            });
+
+            if response.clicked() {
+                clicked = Some(point.id);
+            }
+        }
+
+        clicked
+    }
+
+    fn draw_axes(&self, painter: &egui::Painter, rect: Rect, view: &ViewBounds, x_label: &str, y_label: &str) {
+        let palette = theme::palette();
+        let grid_stroke = Stroke::new(0.5, palette.border);
+        let font = FontId::proportional(10.0);
+
+        for i in 0..=TICK_COUNT {
+            let t = i as f32 / TICK_COUNT as f32;
+
+            // X轴刻度
+            let x = rect.min.x + rect.width() * t;
+            painter.line_segment([pos2(x, rect.min.y), pos2(x, rect.max.y)], grid_stroke);
+            let x_value = view.x_min + (view.x_max - view.x_min) * t as f64;
+            painter.text(pos2(x, rect.max.y + 4.0), Align2::CENTER_TOP, chart::format_value(x_value), font.clone(), palette.weak_text);
+
+            // Y轴刻度
+            let y = rect.max.y - rect.height() * t;
+            painter.line_segment([pos2(rect.min.x, y), pos2(rect.max.x, y)], grid_stroke);
+            let y_value = view.y_min + (view.y_max - view.y_min) * t as f64;
+            painter.text(pos2(rect.min.x - 4.0, y), Align2::RIGHT_CENTER, chart::format_value(y_value), font.clone(), palette.weak_text);
+        }
+
+        painter.rect_stroke(rect, Rounding::ZERO, Stroke::new(1.0, palette.border_strong));
+
+        painter.text(
+            pos2(rect.center().x, rect.max.y + 18.0),
+            Align2::CENTER_TOP,
+            x_label,
+            FontId::proportional(12.0),
+            palette.heading,
+        );
+        painter.text(
+            pos2(rect.min.x + 4.0, rect.min.y + 2.0),
+            Align2::LEFT_TOP,
+            y_label,
+            FontId::proportional(12.0),
+            palette.heading,
+        );
+    }
+}
@@ -1,8 +1,9 @@
-use egui::{Color32, RichText, Ui, Stroke, Rounding, ScrollArea};
+use egui::{RichText, Ui, Stroke, Rounding, ScrollArea};
 use egui_extras::{Column, TableBuilder};
 
 use crate::data::staff::Staff;
 use crate::ui::widgets;
+use crate::ui::theme;
 
 pub struct StaffListView {
     pub all_staff: Vec<Staff>,
@@ -32,17 +33,40 @@ impl StaffListView {
         self.selected_index = None;
     }
 
+    /// 更新单个员工的数据，员工不存在时返回false
+    pub fn update_staff(&mut self, staff: &Staff, current_team_id: Option<i64>) -> bool {
+        let existing = match self.all_staff.iter_mut().find(|s| s.id == staff.id) {
+            Some(existing) => existing,
+            None => return false,
+        };
+        *existing = staff.clone();
+
+        // 同步当前球队的员工列表，员工可能已转入或转出当前球队
+        let position = self.team_staff.iter().position(|s| s.id == staff.id);
+        match (position, current_team_id == Some(staff.team_id)) {
+            (Some(index), true) => self.team_staff[index] = staff.clone(),
+            (Some(index), false) => {
+                self.team_staff.remove(index);
+                self.selected_index = None;
+            },
+            (None, true) => self.team_staff.push(staff.clone()),
+            (None, false) => {},
+        }
+        true
+    }
+
     pub fn get_selected_staff(&self) -> Option<&Staff> {
         self.selected_index.and_then(|idx| self.team_staff.get(idx))
     }
 
     pub fn ui(&mut self, ui: &mut Ui) -> Option<usize> {
+        let palette = theme::palette();
         let mut selected_staff_idx = None;
 
         // 直接在ui上操作，不使用titled_frame，以确保完全控制布局
         ui.vertical(|ui| {
             // 添加标题
-            ui.heading("员工信息");
+            ui.heading(t!("员工信息"));
             ui.add_space(4.0);
             ui.separator();
             ui.add_space(8.0);
@@ -52,8 +76,8 @@ impl StaffListView {
             
             // 表格容器
                 egui::Frame::none()
-                    .fill(Color32::from_rgb(255, 255, 255))
-                    .stroke(Stroke::new(1.0, Color32::from_rgb(220, 220, 220)))
+                    .fill(palette.surface)
+                    .stroke(Stroke::new(1.0, palette.border))
                     .rounding(Rounding::same(6.0))
                     .inner_margin(egui::Margin::same(8.0))
                 .show(ui, |ui| {
@@ -64,9 +88,9 @@ impl StaffListView {
                         // 没有员工时显示提示信息
                         ui.vertical_centered(|ui| {
                             ui.add_space(available_height / 3.0);
-                            ui.label(RichText::new("该球队没有员工").size(16.0).color(Color32::GRAY));
+                            ui.label(RichText::new(t!("该球队没有员工")).size(16.0).color(palette.weak_text));
                             ui.add_space(10.0);
-                            ui.label(RichText::new("请选择其他球队查看").size(14.0).color(Color32::GRAY));
+                            ui.label(RichText::new(t!("请选择其他球队查看")).size(14.0).color(palette.weak_text));
                         });
                     } else {
                         // 有员工时显示表格
@@ -86,13 +110,13 @@ impl StaffListView {
                                     ui.strong("ID"); 
                                 });
                                 header.col(|ui| { 
-                                    ui.strong("姓名"); 
+                                    ui.strong(t!("姓名")); 
                                 });
                                 header.col(|ui| { 
-                                    ui.strong("能力值"); 
+                                    ui.strong(t!("能力值")); 
                                 });
                                 header.col(|ui| { 
-                                    ui.strong("知名度"); 
+                                    ui.strong(t!("知名度")); 
                                 });
                             })
                             .body(|mut body| {
@@ -103,7 +127,7 @@ impl StaffListView {
                                     body.row(row_height, |mut row| {
                                         let ability = match staff.get_ability() {
                                             Ok(a) => a.to_string(),
-                                            Err(_) => "错误".to_string(),
+                                            Err(_) => t!("错误").to_string(),
                                         };
                                         
                                         row.col(|ui| {
@@ -113,7 +137,7 @@ impl StaffListView {
                                             let mut text = RichText::new(&staff.name);
                                             
                                             if is_selected {
-                                                text = text.strong().color(Color32::from_rgb(50, 100, 200));
+                                                text = text.strong().color(palette.accent_text);
                                             }
                                             
                                             if ui.selectable_label(is_selected, text).clicked() {
@@ -136,7 +160,7 @@ impl StaffListView {
 
                 ui.add_space(8.0);
             if !self.team_staff.is_empty() {
-                ui.small("双击员工记录可编辑");
+                ui.small(t!("双击员工记录可编辑"));
             }
         });
 
@@ -0,0 +1,391 @@
+// 统计分析视图
+//
+// 显示球队财富、支持者数量和成立年份的描述统计量、按联赛或地区分组的统计表、
+// 最高和最低的球队排名，以及两个联赛的并排对比。
+
+use std::collections::HashMap;
+use egui::{Ui, ComboBox, Grid, RichText, Stroke, Rounding};
+use crate::data::team::{Leagues, Team};
+use crate::stats::{self, GroupBy, GroupKey, GroupStats, StatField, Summary};
+use crate::ui::widgets;
+use crate::ui::theme;
+
+// 排名列表显示的球队数量
+const TOP_COUNT: usize = 10;
+
+// 从统计量中取出一项用于联赛对比
+type SummaryValue = fn(&Summary) -> f64;
+
+pub struct StatisticsView {
+    pub teams: Vec<Team>,
+    pub leagues: Leagues,
+    pub field: StatField,
+    pub group_by: GroupBy,
+    pub compare_left: Option<i64>,
+    pub compare_right: Option<i64>,
+    overall: HashMap<StatField, Summary>,
+    groups: Vec<GroupStats>,
+    dirty: bool,
+}
+
+impl StatisticsView {
+    pub fn new() -> Self {
+        StatisticsView {
+            teams: Vec::new(),
+            leagues: Leagues::default(),
+            field: StatField::Wealth,
+            group_by: GroupBy::League,
+            compare_left: None,
+            compare_right: None,
+            overall: HashMap::new(),
+            groups: Vec::new(),
+            dirty: true,
+        }
+    }
+
+    pub fn set_teams(&mut self, teams: Vec<Team>) {
+        self.teams = teams;
+        self.dirty = true;
+    }
+
+    pub fn set_leagues(&mut self, leagues: Leagues) {
+        self.leagues = leagues;
+    }
+
+    /// 更新单个球队的数据，下次显示时重新统计
+    pub fn update_team(&mut self, team: &Team) {
+        if let Some(existing) = self.teams.iter_mut().find(|t| t.id == team.id) {
+            *existing = team.clone();
+            self.dirty = true;
+        }
+    }
+
+    fn refresh(&mut self) {
+        if !self.dirty {
+            return;
+        }
+
+        self.overall = StatField::ALL.iter()
+            .filter_map(|&field| Summary::of_teams(&self.teams, field).map(|s| (field, s)))
+            .collect();
+        self.groups = stats::group_stats(&self.teams, self.group_by);
+
+        // 默认对比球队最多的两个联赛
+        if self.compare_left.is_none() || self.compare_right.is_none() {
+            let league_stats = if self.group_by == GroupBy::League {
+                self.groups.clone()
+            } else {
+                stats::group_stats(&self.teams, GroupBy::League)
+            };
+            let mut ids = league_stats.iter().filter_map(|g| match g.key {
+                GroupKey::League(id) => Some(id),
+                GroupKey::Location(_) => None,
+            });
+            self.compare_left = self.compare_left.or(ids.next());
+            self.compare_right = self.compare_right.or(ids.next());
+        }
+
+        self.dirty = false;
+    }
+
+    fn league_name(&self, league_id: i64) -> String {
+        self.leagues.name(league_id)
+    }
+
+    fn group_name(&self, key: &GroupKey) -> String {
+        match key {
+            GroupKey::League(league_id) => self.league_name(*league_id),
+            GroupKey::Location(location) if location.is_empty() => t!("(未设置)").to_string(),
+            GroupKey::Location(location) => location.clone(),
+        }
+    }
+
+    pub fn ui(&mut self, ui: &mut Ui) {
+        self.refresh();
+
+        widgets::titled_frame(t!("统计分析"), ui, |ui| {
+            if self.teams.is_empty() {
+                ui.label(t!("没有可统计的球队数据"));
+                return;
+            }
+
+            ui.horizontal(|ui| {
+                ui.strong(t!("统计字段:"));
+                ComboBox::from_id_source("stats_field")
+                    .selected_text(self.field.as_str())
+                    .width(120.0)
+                    .show_ui(ui, |ui| {
+                        for field in StatField::ALL {
+                            ui.selectable_value(&mut self.field, field, field.as_str());
+                        }
+                    });
+
+                ui.add_space(20.0);
+                ui.strong(t!("分组方式:"));
+                let previous = self.group_by;
+                ui.selectable_value(&mut self.group_by, GroupBy::League, GroupBy::League.as_str());
+                ui.selectable_value(&mut self.group_by, GroupBy::Location, GroupBy::Location.as_str());
+                if self.group_by != previous {
+                    self.groups = stats::group_stats(&self.teams, self.group_by);
+                }
+            });
+
+            ui.add_space(10.0);
+            self.show_overall(ui);
+
+            ui.add_space(20.0);
+            self.show_groups(ui);
+
+            ui.add_space(20.0);
+            self.show_rankings(ui);
+
+            ui.add_space(20.0);
+            self.show_league_comparison(ui);
+        });
+    }
+
+    fn show_overall(&self, ui: &mut Ui) {
+        ui.heading(RichText::new(t!("总体统计")).size(16.0).strong());
+        ui.add_space(10.0);
+
+        table_frame(ui, |ui| {
+            Grid::new("stats_overall_grid")
+                .num_columns(9)
+                .striped(true)
+                .spacing([14.0, 6.0])
+                .show(ui, |ui| {
+                    header_label(ui, t!("字段"));
+                    summary_header(ui);
+                    ui.end_row();
+
+                    for field in StatField::ALL {
+                        ui.label(field.as_str());
+                        match self.overall.get(&field) {
+                            Some(summary) => summary_cells(ui, summary),
+                            None => empty_cells(ui, 8),
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
+    }
+
+    fn show_groups(&self, ui: &mut Ui) {
+        ui.heading(
+            RichText::new(format!("{} - {}", self.group_by.as_str(), self.field.as_str()))
+                .size(16.0)
+                .strong()
+        );
+        ui.add_space(10.0);
+
+        table_frame(ui, |ui| {
+            Grid::new("stats_group_grid")
+                .num_columns(9)
+                .striped(true)
+                .spacing([14.0, 6.0])
+                .show(ui, |ui| {
+                    header_label(ui, if self.group_by == GroupBy::League { t!("联赛") } else { t!("地区") });
+                    summary_header(ui);
+                    ui.end_row();
+
+                    for group in &self.groups {
+                        let response = ui.label(self.group_name(&group.key));
+                        if let GroupKey::League(league_id) = group.key {
+                            response.on_hover_text(self.leagues.hover_text(league_id));
+                        }
+                        match group.summaries.get(&self.field) {
+                            Some(summary) => summary_cells(ui, summary),
+                            None => empty_cells(ui, 8),
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
+    }
+
+    fn show_rankings(&self, ui: &mut Ui) {
+        let highest = stats::top_teams(&self.teams, self.field, TOP_COUNT, true);
+        let lowest = stats::top_teams(&self.teams, self.field, TOP_COUNT, false);
+
+        ui.columns(2, |columns| {
+            self.show_ranking(&mut columns[0], "stats_top_grid", &t!("{} 最高 {}", self.field.as_str(), TOP_COUNT), &highest);
+            self.show_ranking(&mut columns[1], "stats_bottom_grid", &t!("{} 最低 {}", self.field.as_str(), TOP_COUNT), &lowest);
+        });
+    }
+
+    fn show_ranking(&self, ui: &mut Ui, id: &str, title: &str, teams: &[&Team]) {
+        ui.heading(RichText::new(title).size(16.0).strong());
+        ui.add_space(10.0);
+
+        table_frame(ui, |ui| {
+            Grid::new(id)
+                .num_columns(4)
+                .striped(true)
+                .spacing([10.0, 6.0])
+                .show(ui, |ui| {
+                    header_label(ui, t!("排名"));
+                    header_label(ui, t!("球队"));
+                    header_label(ui, t!("联赛"));
+                    header_label(ui, self.field.as_str());
+                    ui.end_row();
+
+                    for (rank, team) in teams.iter().enumerate() {
+                        ui.label((rank + 1).to_string());
+                        ui.label(&team.name);
+                        ui.label(self.league_name(team.league_id))
+                            .on_hover_text(self.leagues.hover_text(team.league_id));
+                        ui.label(self.field.value(team).to_string());
+                        ui.end_row();
+                    }
+                });
+        });
+    }
+
+    fn show_league_comparison(&mut self, ui: &mut Ui) {
+        let palette = theme::palette();
+        ui.heading(RichText::new(t!("联赛对比")).size(16.0).strong());
+        ui.add_space(10.0);
+
+        let mut league_ids = self.leagues.ids();
+        for team in &self.teams {
+            if !league_ids.contains(&team.league_id) {
+                league_ids.push(team.league_id);
+            }
+        }
+        self.leagues.sort_by_name(&mut league_ids);
+
+        ui.horizontal(|ui| {
+            self.league_combo(ui, "stats_compare_left", &league_ids, true);
+            ui.label(t!("对比"));
+            self.league_combo(ui, "stats_compare_right", &league_ids, false);
+        });
+        ui.add_space(10.0);
+
+        let (Some(left_id), Some(right_id)) = (self.compare_left, self.compare_right) else {
+            ui.label(t!("请选择两个联赛进行对比"));
+            return;
+        };
+
+        let left_teams = self.teams.iter().filter(|t| t.league_id == left_id);
+        let right_teams = self.teams.iter().filter(|t| t.league_id == right_id);
+        let left = Summary::of_teams(left_teams, self.field);
+        let right = Summary::of_teams(right_teams, self.field);
+
+        table_frame(ui, |ui| {
+            Grid::new("stats_compare_grid")
+                .num_columns(4)
+                .striped(true)
+                .spacing([14.0, 6.0])
+                .show(ui, |ui| {
+                    header_label(ui, self.field.as_str());
+                    header_label(ui, &self.league_name(left_id));
+                    header_label(ui, &self.league_name(right_id));
+                    header_label(ui, t!("差值"));
+                    ui.end_row();
+
+                    let rows: [(&str, SummaryValue); 8] = [
+                        (t!("球队数量"), |s| s.count as f64),
+                        (t!("平均值"), |s| s.mean),
+                        (t!("中位数"), |s| s.median),
+                        (t!("下四分位数"), |s| s.q1),
+                        (t!("上四分位数"), |s| s.q3),
+                        (t!("标准差"), |s| s.std_dev),
+                        (t!("最小值"), |s| s.min as f64),
+                        (t!("最大值"), |s| s.max as f64),
+                    ];
+
+                    for (label, get) in rows {
+                        let left_value = left.as_ref().map(get);
+                        let right_value = right.as_ref().map(get);
+                        ui.label(label);
+                        ui.label(left_value.map_or("-".to_string(), format_number));
+                        ui.label(right_value.map_or("-".to_string(), format_number));
+                        match (left_value, right_value) {
+                            (Some(l), Some(r)) => {
+                                let diff = l - r;
+                                let color = if diff > 0.0 {
+                                    palette.success
+                                } else if diff < 0.0 {
+                                    palette.error
+                                } else {
+                                    palette.weak_text
+                                };
+                                ui.label(RichText::new(format!("{:+.1}", diff)).color(color));
+                            },
+                            _ => {
+                                ui.label("-");
+                            },
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
+    }
+
+    fn league_combo(&mut self, ui: &mut Ui, id: &str, league_ids: &[i64], left: bool) {
+        let current = if left { self.compare_left } else { self.compare_right };
+        let selected_text = current.map_or(t!("选择联赛").to_string(), |league_id| self.league_name(league_id));
+        let mut selected = current;
+
+        ComboBox::from_id_source(id)
+            .selected_text(selected_text)
+            .width(160.0)
+            .show_ui(ui, |ui| {
+                for &league_id in league_ids {
+                    ui.selectable_value(&mut selected, Some(league_id), self.league_name(league_id))
+                        .on_hover_text(self.leagues.hover_text(league_id));
+                }
+            });
+
+        if left {
+            self.compare_left = selected;
+        } else {
+            self.compare_right = selected;
+        }
+    }
+}
+
+fn table_frame(ui: &mut Ui, add_contents: impl FnOnce(&mut Ui)) {
+    egui::Frame::none()
+        .fill(theme::palette().surface_alt)
+        .stroke(Stroke::new(1.0, theme::palette().border))
+        .rounding(Rounding::same(4.0))
+        .inner_margin(egui::Margin::same(10.0))
+        .show(ui, add_contents);
+}
+
+fn header_label(ui: &mut Ui, text: &str) {
+    ui.strong(RichText::new(text).color(theme::palette().heading));
+}
+
+fn summary_header(ui: &mut Ui) {
+    for title in [t!("数量"), t!("平均值"), t!("中位数"), t!("下四分位数"), t!("上四分位数"), t!("标准差"), t!("最小值"), t!("最大值")] {
+        header_label(ui, title);
+    }
+}
+
+fn summary_cells(ui: &mut Ui, summary: &Summary) {
+    ui.label(summary.count.to_string());
+    ui.label(format_number(summary.mean));
+    ui.label(format_number(summary.median));
+    ui.label(format_number(summary.q1));
+    ui.label(format_number(summary.q3));
+    ui.label(format_number(summary.std_dev));
+    ui.label(summary.min.to_string());
+    ui.label(summary.max.to_string());
+}
+
+fn empty_cells(ui: &mut Ui, count: usize) {
+    for _ in 0..count {
+        ui.label("-");
+    }
+}
+
+// 整数不显示小数部分
+fn format_number(value: f64) -> String {
+    if value.fract() == 0.0 {
+        format!("{:.0}", value)
+    } else {
+        format!("{:.1}", value)
+    }
+}
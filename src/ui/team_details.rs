@@ -1,19 +1,30 @@
-use std::collections::HashMap;
 use std::path::Path;
 
-use egui::{Color32, Image, Label, Layout, RichText, Ui};
+use egui::{Image, Label, Layout, RichText, Ui};
 use egui::widgets::TextEdit;
 use log::{error, info};
 
-use crate::data::team::Team;
+use crate::data::team::{Leagues, Team, TeamField};
 use crate::error::Result;
+use crate::logo::{self, LogoInfo};
 use crate::ui::widgets;
-use crate::utils;
+use crate::ui::theme;
+
+/// Logo区域触发的操作
+#[derive(PartialEq, Clone, Copy)]
+pub enum LogoAction {
+    Replace,
+    Edit,
+    Remove,
+    Restore,
+}
 
 pub struct TeamDetailsView {
     pub team: Option<Team>,
-    pub leagues: HashMap<i64, String>,
+    pub leagues: Leagues,
     pub logo_texture: Option<egui::TextureHandle>,
+    pub logo_info: Option<LogoInfo>,
+    placeholder_texture: Option<egui::TextureHandle>,
     pub edited_fields: EditableTeamFields,
     pub has_changes: bool,
 }
@@ -33,8 +44,10 @@ impl TeamDetailsView {
     pub fn new() -> Self {
         TeamDetailsView {
             team: None,
-            leagues: HashMap::new(),
+            leagues: Leagues::default(),
             logo_texture: None,
+            logo_info: None,
+            placeholder_texture: None,
             edited_fields: EditableTeamFields::default(),
             has_changes: false,
         }
@@ -54,36 +67,42 @@ impl TeamDetailsView {
         self.has_changes = false;
     }
 
-    pub fn set_leagues(&mut self, leagues: HashMap<i64, String>) {
+    /// 球队保存后同步详情，当前的编辑内容均已保存时返回true
+    pub fn update_saved_team(&mut self, team: &Team) -> bool {
+        if self.team.as_ref().map(|t| t.id) != Some(team.id) {
+            return false;
+        }
+
+        // 保存期间可能又有新的修改，此时保留编辑内容
+        let up_to_date = !self.has_changes || self.get_edited_team().is_some_and(|edited| {
+            TeamField::ALL.iter().all(|field| field.value_string(&edited) == field.value_string(team))
+        });
+
+        if up_to_date {
+            self.set_team(team.clone());
+        } else {
+            self.team = Some(team.clone());
+        }
+        up_to_date
+    }
+
+    pub fn set_leagues(&mut self, leagues: Leagues) {
         self.leagues = leagues;
     }
 
     pub fn load_logo(&mut self, ctx: &egui::Context, db_dir: &Path, team_id: i64) -> Result<()> {
-        let logo_path = utils::create_logo_path(db_dir, team_id);
+        self.logo_info = Some(logo::logo_info(db_dir, team_id));
         
-        if utils::file_exists(&logo_path) {
-            match utils::load_and_resize_image(&logo_path, 128, 128) {
-                Ok(img) => {
-                    let width = img.width() as usize;
-                    let height = img.height() as usize;
-                    let rgba8 = utils::image_to_rgba8_bytes(&img);
-                    
-                    self.logo_texture = Some(ctx.load_texture(
-                        format!("team_logo_{}", team_id),
-                        egui::ColorImage::from_rgba_unmultiplied([width, height], &rgba8),
-                        egui::TextureOptions::LINEAR
-                    ));
-                    Ok(())
-                },
-                Err(e) => {
-                    error!("加载Logo失败: {}", e);
-                    self.logo_texture = None;
-                    Err(e)
-                }
+        match logo::load_logo_texture(ctx, db_dir, team_id, 128, "team_logo") {
+            Ok(texture) => {
+                self.logo_texture = texture;
+                Ok(())
+            },
+            Err(e) => {
+                error!("加载Logo失败: {}", e);
+                self.logo_texture = None;
+                Err(e)
             }
-        } else {
-            self.logo_texture = None;
-            Ok(())
         }
     }
 
@@ -108,58 +127,95 @@ impl TeamDetailsView {
         })
     }
 
-    pub fn ui(&mut self, ui: &mut Ui) -> (bool, bool) {
-        let mut logo_clicked = false;
+    pub fn ui(&mut self, ui: &mut Ui) -> (Option<LogoAction>, bool) {
+        let palette = theme::palette();
+        let mut logo_action = None;
         let mut field_changed = false;
 
         if let Some(team) = &self.team {
-            widgets::titled_frame("球队详情", ui, |ui| {
+            widgets::titled_frame(t!("球队详情"), ui, |ui| {
                 // Logo区域
+                if self.placeholder_texture.is_none() {
+                    self.placeholder_texture = Some(logo::placeholder_texture(ui.ctx()));
+                }
+                
                 ui.vertical_centered(|ui| {
                     // 创建一个可视的框架，使Logo区域看起来可点击
                     egui::Frame::none()
-                        .fill(Color32::from_rgb(245, 245, 245))
-                        .stroke(egui::Stroke::new(1.0, Color32::from_rgb(200, 200, 200)))
+                        .fill(palette.window)
+                        .stroke(egui::Stroke::new(1.0, palette.border_strong))
                         .rounding(egui::Rounding::same(8.0))
                         .inner_margin(egui::Margin::same(10.0))
                         .show(ui, |ui| {
                             ui.vertical_centered(|ui| {
-                                let mut response = if let Some(texture) = &self.logo_texture {
-                                    // 显示Logo图像
-                                    let logo = Image::new(texture)
-                                        .max_size(egui::vec2(128.0, 128.0));
-                                    ui.add(logo.sense(egui::Sense::click()))
-                                } else {
-                                    // 显示"无Logo"文本
-                                    ui.add(Label::new(
-                                        RichText::new("无Logo\n点击添加").heading().color(Color32::GRAY)
-                                    ).sense(egui::Sense::click()))
+                                let texture = self.logo_texture.as_ref()
+                                    .or(self.placeholder_texture.as_ref());
+                                
+                                let response = match texture {
+                                    Some(texture) => {
+                                        // 显示Logo图像或占位图
+                                        let logo = Image::new(texture)
+                                            .max_size(egui::vec2(128.0, 128.0));
+                                        ui.add(logo.sense(egui::Sense::click()))
+                                    },
+                                    None => {
+                                        ui.add(Label::new(
+                                            RichText::new(t!("无Logo\n点击添加")).heading().color(palette.weak_text)
+                                        ).sense(egui::Sense::click()))
+                                    },
                                 };
                                 
-                                // 添加明确的按钮提示
-                                ui.add_space(5.0);
-                                ui.add(egui::Label::new(
-                                    RichText::new("📷 点击更换Logo")
-                                        .strong()
-                                        .color(Color32::from_rgb(50, 100, 200))
-                                ));
+                                // Logo状态
+                                match &self.logo_info {
+                                    Some(info) if info.has_wrong_size() => {
+                                        if let Some((w, h)) = info.dimensions {
+                                            ui.colored_label(
+                                                palette.warning,
+                                                t!("⚠ 尺寸 {}×{}，游戏要求 {}×{}", w, h, logo::LOGO_SIZE, logo::LOGO_SIZE)
+                                            );
+                                        }
+                                    },
+                                    Some(info) if info.exists() => {
+                                        ui.small(format!("{}×{} PNG", logo::LOGO_SIZE, logo::LOGO_SIZE));
+                                    },
+                                    _ => {
+                                        ui.small(RichText::new(t!("无Logo")).color(palette.weak_text));
+                                    },
+                                }
                                 
                                 // 检查点击
                                 if response.clicked() {
-                                    logo_clicked = true;
+                                    logo_action = Some(LogoAction::Replace);
                                 }
                                 
                                 // 鼠标悬停效果
                                 if response.hovered() {
-                                    response.mark_changed(); // 确保UI会更新
                                     ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
                                 }
+                                
+                                ui.add_space(5.0);
+                                let has_logo = self.logo_info.as_ref().is_some_and(|info| info.exists());
+                                let has_backup = self.logo_info.as_ref().is_some_and(|info| info.has_backup);
+                                ui.horizontal(|ui| {
+                                    if ui.button(RichText::new(t!("📷 更换Logo")).color(palette.accent_text)).clicked() {
+                                        logo_action = Some(LogoAction::Replace);
+                                    }
+                                    if ui.add_enabled(has_logo, egui::Button::new(t!("编辑Logo"))).clicked() {
+                                        logo_action = Some(LogoAction::Edit);
+                                    }
+                                    if ui.add_enabled(has_logo, egui::Button::new(t!("移除Logo"))).clicked() {
+                                        logo_action = Some(LogoAction::Remove);
+                                    }
+                                    if ui.add_enabled(has_backup, egui::Button::new(t!("恢复原始Logo"))).clicked() {
+                                        logo_action = Some(LogoAction::Restore);
+                                    }
+                                });
                             });
                         });
                 });
 
                 ui.add_space(10.0);
-                ui.heading("基本信息");
+                ui.heading(t!("基本信息"));
                 widgets::horizontal_separator(ui);
 
                 // 基本信息表单
@@ -167,16 +223,16 @@ impl TeamDetailsView {
                     // 左列
                     let mut changed = false;
                     let id_str = team.id.to_string();
-                    widgets::readonly_form_row(&mut columns[0], "编号:", &id_str);
-                    changed |= widgets::form_row(&mut columns[0], "球队名称:", &mut self.edited_fields.name);
-                    changed |= widgets::form_row(&mut columns[0], "球队财富（万）:", &mut self.edited_fields.wealth);
-                    changed |= widgets::form_row(&mut columns[0], "成立年份:", &mut self.edited_fields.found_year);
+                    widgets::readonly_form_row(&mut columns[0], t!("编号:"), &id_str);
+                    changed |= widgets::form_row(&mut columns[0], t!("球队名称:"), &mut self.edited_fields.name);
+                    changed |= widgets::form_row(&mut columns[0], t!("球队财富（万）:"), &mut self.edited_fields.wealth);
+                    changed |= widgets::form_row(&mut columns[0], t!("成立年份:"), &mut self.edited_fields.found_year);
                     
                     // 右列
-                    changed |= widgets::form_row(&mut columns[1], "所在地区:", &mut self.edited_fields.location);
-                    changed |= widgets::form_row(&mut columns[1], "支持者数量:", &mut self.edited_fields.supporter_count);
-                    changed |= widgets::form_row(&mut columns[1], "主场名称:", &mut self.edited_fields.stadium_name);
-                    changed |= widgets::form_row(&mut columns[1], "球队昵称:", &mut self.edited_fields.nickname);
+                    changed |= widgets::form_row(&mut columns[1], t!("所在地区:"), &mut self.edited_fields.location);
+                    changed |= widgets::form_row(&mut columns[1], t!("支持者数量:"), &mut self.edited_fields.supporter_count);
+                    changed |= widgets::form_row(&mut columns[1], t!("主场名称:"), &mut self.edited_fields.stadium_name);
+                    changed |= widgets::form_row(&mut columns[1], t!("球队昵称:"), &mut self.edited_fields.nickname);
                     
                     self.has_changes |= changed;
                     field_changed = changed;
@@ -184,19 +240,19 @@ impl TeamDetailsView {
 
                 // 联赛信息
                 ui.horizontal(|ui| {
-                    ui.label("所在联赛:");
-                    let league_name = self.leagues.get(&team.league_id)
+                    ui.label(t!("所在联赛:"));
+                    let league_name = self.leagues.get(team.league_id)
                         .map(|name| format!("{} (ID: {})", name, team.league_id))
-                        .unwrap_or_else(|| format!("未知联赛 (ID: {})", team.league_id));
+                        .unwrap_or_else(|| t!("未知联赛 (ID: {})", team.league_id));
                     ui.label(league_name);
                 });
             });
         } else {
             ui.vertical_centered(|ui| {
-                ui.label("请选择一个球队");
+                ui.label(t!("请选择一个球队"));
             });
         }
 
-        (logo_clicked, field_changed)
+        (logo_action, field_changed)
     }
 } 
\ No newline at end of file
@@ -1,37 +1,26 @@
-use egui::{Color32, RichText, ScrollArea, Ui, Stroke, Rounding, ComboBox};
+use std::time::{Duration, Instant};
+
+use egui::{RichText, ScrollArea, Ui, Stroke, Rounding, ComboBox};
 use log::info;
 
-use crate::data::team::Team;
+use crate::data::team::{Leagues, Team};
+pub use crate::data::team_filter::FilterField;
+use crate::data::team_filter::{TeamFilter, TeamFilterState};
 use crate::ui::widgets;
+use crate::ui::theme;
 
-#[derive(PartialEq, Clone, Copy)]
-pub enum FilterField {
-    Name,
-    Location,
-    League,
-    All,
-}
-
-impl FilterField {
-    fn as_str(&self) -> &'static str {
-        match self {
-            FilterField::Name => "球队名称",
-            FilterField::Location => "地区",
-            FilterField::League => "联赛",
-            FilterField::All => "全部字段",
-        }
-    }
-}
+// 输入搜索词后等待的时间，连续输入时只筛选一次
+const SEARCH_DEBOUNCE: Duration = Duration::from_millis(150);
 
 pub struct TeamListView {
     pub teams: Vec<Team>,
-    pub filtered_teams: Vec<Team>,
     pub selected_index: Option<usize>,
     pub search_text: String,
     pub filter_field: FilterField,
     pub unique_locations: Vec<String>,
     pub selected_location: Option<String>,
     pub unique_leagues: Vec<i64>,
+    pub leagues: Leagues,
     pub selected_league: Option<i64>,
     pub show_advanced_filters: bool,
     pub min_wealth: Option<i64>,
@@ -40,19 +29,24 @@ pub struct TeamListView {
     pub max_year: Option<i64>,
     pub wealth_filter_text: String,
     pub year_filter_text: String,
+    /// 从图表下钻时的筛选路径说明
+    pub drill_down_label: Option<String>,
+    drill_down_ids: Option<Vec<i64>>,
+    filter_state: TeamFilterState,
+    search_changed_at: Option<Instant>,
 }
 
 impl TeamListView {
     pub fn new() -> Self {
         TeamListView {
             teams: Vec::new(),
-            filtered_teams: Vec::new(),
             selected_index: None,
             search_text: String::new(),
             filter_field: FilterField::All,
             unique_locations: Vec::new(),
             selected_location: None,
             unique_leagues: Vec::new(),
+            leagues: Leagues::default(),
             selected_league: None,
             show_advanced_filters: false,
             min_wealth: None,
@@ -61,11 +55,18 @@ impl TeamListView {
             max_year: None,
             wealth_filter_text: String::new(),
             year_filter_text: String::new(),
+            drill_down_label: None,
+            drill_down_ids: None,
+            filter_state: TeamFilterState::new(),
+            search_changed_at: None,
         }
     }
 
-    pub fn set_teams(&mut self, teams: Vec<Team>) {
+    /// 同时设置球队和联赛，搜索键依赖联赛名称，两者都更新后只重建一次
+    pub fn set_data(&mut self, teams: Vec<Team>, leagues: Leagues) {
         self.teams = teams;
+        self.leagues = leagues;
+        self.filter_state.rebuild(&self.teams, &self.leagues);
         self.update_filter_options();
         self.apply_filter();
     }
@@ -79,86 +80,62 @@ impl TeamListView {
         locations.dedup();
         self.unique_locations = locations;
 
-        // 提取唯一联赛ID
+        // 提取唯一联赛ID，按联赛名称排列
         let mut leagues = self.teams.iter()
             .map(|t| t.league_id)
             .collect::<Vec<_>>();
         leagues.sort();
         leagues.dedup();
+        self.leagues.sort_by_name(&mut leagues);
         self.unique_leagues = leagues;
     }
 
     pub fn apply_filter(&mut self) {
-        // 开始过滤
-        self.filtered_teams = self.teams.clone();
-
-        // 应用搜索文本过滤
-        if !self.search_text.is_empty() {
-            let search_term = self.search_text.to_lowercase();
-            self.filtered_teams = self.filtered_teams
-                .iter()
-                .filter(|team| {
-                    match self.filter_field {
-                        FilterField::Name => team.name.to_lowercase().contains(&search_term),
-                        FilterField::Location => team.location.to_lowercase().contains(&search_term),
-                        FilterField::League => team.league_id.to_string().contains(&search_term),
-                        FilterField::All => team.search_string().to_lowercase().contains(&search_term),
-                    }
-                })
-                .cloned()
-                .collect();
-        }
+        let selected_id = self.get_selected_team_id();
 
-        // 应用地区过滤
-        if let Some(location) = &self.selected_location {
-            self.filtered_teams = self.filtered_teams
-                .iter()
-                .filter(|team| &team.location == location)
-                .cloned()
-                .collect();
-        }
+        let mut filter = TeamFilter::new();
+        filter.set_search_text(&self.search_text);
+        filter.field = self.filter_field;
+        filter.location = self.selected_location.clone();
+        filter.league_id = self.selected_league;
+        filter.min_wealth = self.min_wealth;
+        filter.max_wealth = self.max_wealth;
+        filter.min_year = self.min_year;
+        filter.max_year = self.max_year;
+        filter.team_ids = self.drill_down_ids.clone();
 
-        // 应用联赛过滤
-        if let Some(league_id) = self.selected_league {
-            self.filtered_teams = self.filtered_teams
-                .iter()
-                .filter(|team| team.league_id == league_id)
-                .cloned()
-                .collect();
-        }
+        self.filter_state.apply(&self.teams, &filter);
+        self.search_changed_at = None;
 
-        // 应用财富范围过滤
-        if let Some(min) = self.min_wealth {
-            self.filtered_teams = self.filtered_teams
-                .iter()
-                .filter(|team| team.wealth >= min)
-                .cloned()
-                .collect();
+        // 保持原来选中的球队
+        self.selected_index = None;
+        if let Some(team_id) = selected_id {
+            self.select_team_by_id(team_id);
         }
+    }
 
-        if let Some(max) = self.max_wealth {
-            self.filtered_teams = self.filtered_teams
-                .iter()
-                .filter(|team| team.wealth <= max)
-                .cloned()
-                .collect();
-        }
+    /// 当前筛选结果中的球队数量
+    pub fn filtered_count(&self) -> usize {
+        self.filter_state.indices().len()
+    }
 
-        // 应用成立年份范围过滤
-        if let Some(min) = self.min_year {
-            self.filtered_teams = self.filtered_teams
-                .iter()
-                .filter(|team| team.found_year >= min)
-                .cloned()
-                .collect();
-        }
+    /// 按筛选结果中的位置获取球队
+    pub fn filtered_team(&self, position: usize) -> Option<&Team> {
+        self.filter_state
+            .indices()
+            .get(position)
+            .and_then(|&index| self.teams.get(index))
+    }
 
-        if let Some(max) = self.max_year {
-            self.filtered_teams = self.filtered_teams
-                .iter()
-                .filter(|team| team.found_year <= max)
-                .cloned()
-                .collect();
+    // 搜索框输入后延迟筛选
+    fn apply_debounced_filter(&mut self, ui: &Ui) {
+        if let Some(changed_at) = self.search_changed_at {
+            let elapsed = changed_at.elapsed();
+            if elapsed >= SEARCH_DEBOUNCE {
+                self.apply_filter();
+            } else {
+                ui.ctx().request_repaint_after(SEARCH_DEBOUNCE - elapsed);
+            }
         }
     }
 
@@ -202,8 +179,55 @@ impl TeamListView {
         }
     }
 
+    /// 更新单个球队的数据，不重新应用筛选条件
+    pub fn update_team(&mut self, team: &Team) {
+        if let Some(index) = self.teams.iter().position(|t| t.id == team.id) {
+            self.teams[index] = team.clone();
+            self.filter_state.update_team(index, team, &self.leagues);
+        }
+    }
+
+    /// 清除所有筛选条件
+    pub fn clear_filters(&mut self) {
+        let selected_id = self.get_selected_team_id();
+
+        self.search_text.clear();
+        self.filter_field = FilterField::All;
+        self.selected_location = None;
+        self.selected_league = None;
+        self.min_wealth = None;
+        self.max_wealth = None;
+        self.min_year = None;
+        self.max_year = None;
+        self.wealth_filter_text.clear();
+        self.year_filter_text.clear();
+        self.drill_down_label = None;
+        self.drill_down_ids = None;
+        self.apply_filter();
+
+        if let Some(team_id) = selected_id {
+            self.select_team_by_id(team_id);
+        }
+    }
+
+    /// 只显示图表中选中的球队，label为显示在列表上方的筛选路径
+    pub fn set_drill_down(&mut self, label: String, mut team_ids: Vec<i64>) {
+        team_ids.sort_unstable();
+        self.drill_down_label = Some(label);
+        self.drill_down_ids = Some(team_ids);
+        self.apply_filter();
+    }
+
+    pub fn clear_drill_down(&mut self) {
+        if self.drill_down_ids.is_some() {
+            self.drill_down_label = None;
+            self.drill_down_ids = None;
+            self.apply_filter();
+        }
+    }
+
     pub fn get_selected_team(&self) -> Option<&Team> {
-        self.selected_index.and_then(|idx| self.filtered_teams.get(idx))
+        self.selected_index.and_then(|idx| self.filtered_team(idx))
     }
 
     pub fn get_selected_team_id(&self) -> Option<i64> {
@@ -211,23 +235,46 @@ impl TeamListView {
     }
 
     pub fn select_team_by_id(&mut self, team_id: i64) {
-        self.selected_index = self.filtered_teams
+        let teams = &self.teams;
+        self.selected_index = self.filter_state
+            .indices()
             .iter()
-            .position(|team| team.id == team_id);
+            .position(|&index| teams[index].id == team_id);
     }
 
     pub fn ui(&mut self, ui: &mut Ui) -> Option<i64> {
+        let palette = theme::palette();
         let mut selected_team_id = None;
 
-        widgets::titled_frame("球队列表", ui, |ui| {
+        self.apply_debounced_filter(ui);
+
+        widgets::titled_frame(t!("球队列表"), ui, |ui| {
+            // 图表下钻的筛选条件
+            if let Some(label) = self.drill_down_label.clone() {
+                egui::Frame::none()
+                    .fill(palette.highlight)
+                    .rounding(Rounding::same(4.0))
+                    .inner_margin(egui::Margin::symmetric(6.0, 4.0))
+                    .show(ui, |ui| {
+                        ui.horizontal_wrapped(|ui| {
+                            ui.label(RichText::new(t!("图表筛选: {}", label)).color(palette.heading));
+                            if ui.small_button("✕").on_hover_text(t!("清除图表筛选")).clicked() {
+                                self.clear_drill_down();
+                            }
+                        });
+                    });
+                ui.add_space(4.0);
+            }
+            
             // 基本搜索框
             ui.horizontal(|ui| {
-                ui.strong("搜索:");
+                ui.strong(t!("搜索:"));
                 
                 let search_response = ui.add(egui::TextEdit::singleline(&mut self.search_text)
-                    .hint_text("输入搜索关键词...")
+                    .hint_text(t!("输入搜索关键词..."))
                     .desired_width(150.0));
                 
+                let previous_field = self.filter_field;
                 ComboBox::from_id_source("filter_field")
                     .selected_text(self.filter_field.as_str())
                     .show_ui(ui, |ui| {
@@ -238,10 +285,17 @@ impl TeamListView {
                     });
                 
                 if search_response.changed() {
+                    self.search_changed_at = Some(Instant::now());
+                    ui.ctx().request_repaint_after(SEARCH_DEBOUNCE);
+                }
+                
+                // 按回车或切换字段时立即筛选
+                let enter_pressed = search_response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                if enter_pressed || self.filter_field != previous_field {
                     self.apply_filter();
                 }
                 
-                if widgets::mac_button(ui, "清除") {
+                if widgets::mac_button(ui, t!("清除")) {
                     self.search_text.clear();
                     self.apply_filter();
                 }
@@ -249,28 +303,28 @@ impl TeamListView {
 
             // 高级过滤器切换
             ui.horizontal(|ui| {
-                if widgets::mac_button(ui, if self.show_advanced_filters { "隐藏高级过滤" } else { "显示高级过滤" }) {
+                if widgets::mac_button(ui, if self.show_advanced_filters { t!("隐藏高级过滤") } else { t!("显示高级过滤") }) {
                     self.show_advanced_filters = !self.show_advanced_filters;
                 }
                 
-                ui.label(format!("共计: {} 个球队", self.filtered_teams.len()));
+                ui.label(t!("共计: {} 个球队", self.filtered_count()));
             });
 
             // 高级过滤器
             if self.show_advanced_filters {
                 ui.add_space(5.0);
                 egui::Frame::none()
-                    .fill(Color32::from_rgb(245, 245, 250))
-                    .stroke(Stroke::new(1.0, Color32::from_rgb(220, 220, 230)))
+                    .fill(palette.surface_alt)
+                    .stroke(Stroke::new(1.0, palette.border))
                     .rounding(Rounding::same(4.0))
                     .inner_margin(egui::Margin::same(8.0))
                     .show(ui, |ui| {
                         ui.horizontal(|ui| {
-                            ui.label("地区:");
+                            ui.label(t!("地区:"));
                             ComboBox::from_id_source("location_filter")
-                                .selected_text(self.selected_location.as_deref().unwrap_or("全部地区"))
+                                .selected_text(self.selected_location.as_deref().unwrap_or(t!("全部地区")))
                                 .show_ui(ui, |ui| {
-                                    if ui.selectable_label(self.selected_location.is_none(), "全部地区").clicked() {
+                                    if ui.selectable_label(self.selected_location.is_none(), t!("全部地区")).clicked() {
                                         self.selected_location = None;
                                         self.apply_filter();
                                     }
@@ -288,11 +342,11 @@ impl TeamListView {
                             
                             ui.add_space(10.0);
                             
-                            ui.label("联赛:");
+                            ui.label(t!("联赛:"));
                             ComboBox::from_id_source("league_filter")
-                                .selected_text(self.selected_league.map_or("全部联赛".to_string(), |id| format!("联赛 {}", id)))
+                                .selected_text(self.selected_league.map_or(t!("全部联赛").to_string(), |id| self.leagues.name(id)))
                                 .show_ui(ui, |ui| {
-                                    if ui.selectable_label(self.selected_league.is_none(), "全部联赛").clicked() {
+                                    if ui.selectable_label(self.selected_league.is_none(), t!("全部联赛")).clicked() {
                                         self.selected_league = None;
                                         self.apply_filter();
                                     }
@@ -300,8 +354,8 @@ impl TeamListView {
                                     for &league_id in &self.unique_leagues.clone() {
                                         if ui.selectable_label(
                                             self.selected_league == Some(league_id), 
-                                            format!("联赛 {}", league_id)
-                                        ).clicked() {
+                                            self.leagues.name(league_id)
+                                        ).on_hover_text(self.leagues.hover_text(league_id)).clicked() {
                                             self.selected_league = Some(league_id);
                                             self.apply_filter();
                                         }
@@ -310,14 +364,14 @@ impl TeamListView {
                         });
                         
                         ui.horizontal(|ui| {
-                            ui.label("财富范围:");
+                            ui.label(t!("财富范围:"));
                             let wealth_response = ui.add(egui::TextEdit::singleline(&mut self.wealth_filter_text)
-                                .hint_text("例如: 1000-5000")
+                                .hint_text(t!("例如: 1000-5000"))
                                 .desired_width(120.0));
                             
-                            ui.label("成立年份:");
+                            ui.label(t!("成立年份:"));
                             let year_response = ui.add(egui::TextEdit::singleline(&mut self.year_filter_text)
-                                .hint_text("例如: 1900-2000")
+                                .hint_text(t!("例如: 1900-2000"))
                                 .desired_width(120.0));
                             
                             if wealth_response.changed() {
@@ -332,7 +386,7 @@ impl TeamListView {
                         });
                         
                         ui.horizontal(|ui| {
-                            if widgets::mac_button(ui, "重置所有过滤") {
+                            if widgets::mac_button(ui, t!("重置所有过滤")) {
                                 self.search_text.clear();
                                 self.selected_location = None;
                                 self.selected_league = None;
@@ -352,27 +406,31 @@ impl TeamListView {
 
             // 球队列表
             egui::Frame::none()
-                .fill(Color32::from_rgb(255, 255, 255))
-                .stroke(Stroke::new(1.0, Color32::from_rgb(220, 220, 220)))
+                .fill(palette.surface)
+                .stroke(Stroke::new(1.0, palette.border))
                 .rounding(Rounding::same(6.0))
                 .inner_margin(egui::Margin::same(8.0))
                 .show(ui, |ui| {
-                    // 使用available_height()获取可用高度，使列表填充所有剩余空间
-                    let available_height = ui.available_height();
+                    // auto_shrink为false时列表会填充所有剩余空间
+                    let row_height = ui.spacing().interact_size.y;
+                    let total_rows = self.filtered_count();
                     
+                    // 只绘制可见的行
                     ScrollArea::vertical()
                         .auto_shrink([false, false])
                         .max_height(f32::INFINITY)
-                        .show(ui, |ui| {
-                            ui.set_min_height(available_height);
-                            
-                            for (idx, team) in self.filtered_teams.iter().enumerate() {
+                        .show_rows(ui, row_height, total_rows, |ui, row_range| {
+                            for idx in row_range {
+                                let team = match self.filter_state.indices().get(idx).and_then(|&index| self.teams.get(index)) {
+                                    Some(team) => team,
+                                    None => continue,
+                                };
                                 let is_selected = Some(idx) == self.selected_index;
                                 
                                 let mut text = RichText::new(&team.name);
                                 
                                 if is_selected {
-                                    text = text.strong().color(Color32::from_rgb(50, 100, 200));
+                                    text = text.strong().color(palette.accent_text);
                                 }
                                 
                                 let response = ui.selectable_value(
@@ -1,20 +1,236 @@
+// 界面主题
+//
+// 所有控件的颜色都来自当前配色（Palette），不直接写死颜色。内置浅色和深色两套配色，
+// 可以跟随系统外观切换，也可以在主题编辑器中基于内置配色调整并保存为自定义配色。
+
+use std::collections::BTreeMap;
+use std::sync::RwLock;
+
 use egui::{
     Color32, Context, FontFamily, FontId, Rounding, Stroke, TextStyle, Visuals,
-    style::{Selection, Widgets, WidgetVisuals},
+    style::{Selection, WidgetVisuals},
 };
+use serde::{Deserialize, Serialize};
+
+use crate::fonts::DEFAULT_FONT_SIZE;
+
+// 当前使用的配色，控件绘制时通过 palette() 读取
+static CURRENT: RwLock<Palette> = RwLock::new(Palette::LIGHT);
+
+/// 界面配色
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Palette {
+    /// 深色配色以egui的深色样式为基础
+    pub dark: bool,
+    /// 面板背景
+    pub panel: Color32,
+    /// 窗口和对话框背景
+    pub window: Color32,
+    /// 卡片、列表和图表区域的背景
+    pub surface: Color32,
+    /// 次要区域的背景，如表格和工具栏
+    pub surface_alt: Color32,
+    /// 输入框背景
+    pub input: Color32,
+    /// 按钮背景
+    pub control: Color32,
+    /// 表格隔行的背景
+    pub stripe: Color32,
+    /// 卡片和网格线等浅色边框
+    pub border: Color32,
+    /// 窗口、按钮和坐标轴等深色边框
+    pub border_strong: Color32,
+    /// 正文
+    pub text: Color32,
+    /// 标题和表头
+    pub heading: Color32,
+    /// 说明和提示等次要文字
+    pub weak_text: Color32,
+    /// 强调色，用于主要按钮和选中状态
+    pub accent: Color32,
+    /// 强调色背景上的文字
+    pub on_accent: Color32,
+    /// 选中的列表项和链接式按钮的文字
+    pub accent_text: Color32,
+    /// 文本选择背景
+    pub selection: Color32,
+    /// 悬停和筛选提示等高亮背景
+    pub highlight: Color32,
+    pub success: Color32,
+    pub warning: Color32,
+    pub error: Color32,
+}
+
+impl Palette {
+    pub const LIGHT: Palette = Palette {
+        dark: false,
+        panel: Color32::from_rgb(248, 248, 248),
+        window: Color32::from_rgb(245, 245, 245),
+        surface: Color32::from_rgb(255, 255, 255),
+        surface_alt: Color32::from_rgb(250, 250, 252),
+        input: Color32::from_rgb(240, 240, 240),
+        control: Color32::from_rgb(230, 230, 230),
+        stripe: Color32::from_rgb(230, 230, 230),
+        border: Color32::from_rgb(222, 222, 228),
+        border_strong: Color32::from_rgb(200, 200, 200),
+        text: Color32::from_rgb(50, 50, 50),
+        heading: Color32::from_rgb(50, 50, 90),
+        weak_text: Color32::from_rgb(130, 130, 140),
+        accent: Color32::from_rgb(80, 145, 245),
+        on_accent: Color32::from_rgb(255, 255, 255),
+        accent_text: Color32::from_rgb(50, 100, 200),
+        selection: Color32::from_rgb(180, 200, 255),
+        highlight: Color32::from_rgb(232, 237, 252),
+        success: Color32::from_rgb(40, 150, 70),
+        warning: Color32::from_rgb(200, 120, 40),
+        error: Color32::from_rgb(200, 60, 60),
+    };
+
+    pub const DARK: Palette = Palette {
+        dark: true,
+        panel: Color32::from_rgb(32, 33, 36),
+        window: Color32::from_rgb(40, 41, 45),
+        surface: Color32::from_rgb(46, 47, 52),
+        surface_alt: Color32::from_rgb(52, 53, 58),
+        input: Color32::from_rgb(28, 29, 32),
+        control: Color32::from_rgb(62, 64, 70),
+        stripe: Color32::from_rgb(55, 56, 62),
+        border: Color32::from_rgb(68, 70, 76),
+        border_strong: Color32::from_rgb(92, 94, 102),
+        text: Color32::from_rgb(225, 225, 230),
+        heading: Color32::from_rgb(200, 208, 240),
+        weak_text: Color32::from_rgb(145, 145, 155),
+        accent: Color32::from_rgb(80, 145, 245),
+        on_accent: Color32::from_rgb(255, 255, 255),
+        accent_text: Color32::from_rgb(125, 172, 255),
+        selection: Color32::from_rgb(50, 80, 140),
+        highlight: Color32::from_rgb(58, 62, 82),
+        success: Color32::from_rgb(80, 190, 110),
+        warning: Color32::from_rgb(230, 150, 60),
+        error: Color32::from_rgb(235, 95, 95),
+    };
+
+    /// 在主题编辑器中显示的颜色及其名称
+    pub fn colors_mut(&mut self) -> [(&'static str, &mut Color32); 20] {
+        [
+            (t!("面板背景"), &mut self.panel),
+            (t!("窗口背景"), &mut self.window),
+            (t!("卡片背景"), &mut self.surface),
+            (t!("次要背景"), &mut self.surface_alt),
+            (t!("输入框"), &mut self.input),
+            (t!("按钮"), &mut self.control),
+            (t!("隔行背景"), &mut self.stripe),
+            (t!("边框"), &mut self.border),
+            (t!("深色边框"), &mut self.border_strong),
+            (t!("正文"), &mut self.text),
+            (t!("标题"), &mut self.heading),
+            (t!("次要文字"), &mut self.weak_text),
+            (t!("强调色"), &mut self.accent),
+            (t!("强调色上的文字"), &mut self.on_accent),
+            (t!("选中文字"), &mut self.accent_text),
+            (t!("文本选择"), &mut self.selection),
+            (t!("高亮背景"), &mut self.highlight),
+            (t!("成功"), &mut self.success),
+            (t!("警告"), &mut self.warning),
+            (t!("错误"), &mut self.error),
+        ]
+    }
+
+    /// 在卡片背景上叠加少量颜色，用于通知和状态提示的背景
+    pub fn tint(&self, color: Color32) -> Color32 {
+        mix(self.surface, color, 0.12)
+    }
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Palette::LIGHT
+    }
+}
+
+/// 主题模式
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ThemeMode {
+    /// 跟随系统的浅色或深色外观
+    #[default]
+    System,
+    Light,
+    Dark,
+    /// 使用指定名称的自定义配色
+    Custom(String),
+}
+
+impl ThemeMode {
+    pub fn as_str(&self) -> String {
+        match self {
+            ThemeMode::System => t!("跟随系统").to_string(),
+            ThemeMode::Light => t!("浅色").to_string(),
+            ThemeMode::Dark => t!("深色").to_string(),
+            ThemeMode::Custom(name) => name.clone(),
+        }
+    }
+}
+
+/// 保存在设置中的自定义配色
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CustomPalette {
+    pub name: String,
+    pub palette: Palette,
+}
+
+/// 主题设置
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThemeSettings {
+    pub mode: ThemeMode,
+    pub custom_palettes: Vec<CustomPalette>,
+}
+
+impl ThemeSettings {
+    /// 按主题模式和系统外观选择配色，自定义配色已被删除时跟随系统
+    pub fn palette(&self, system_dark: bool) -> Palette {
+        let system = if system_dark { Palette::DARK } else { Palette::LIGHT };
+        match &self.mode {
+            ThemeMode::System => system,
+            ThemeMode::Light => Palette::LIGHT,
+            ThemeMode::Dark => Palette::DARK,
+            ThemeMode::Custom(name) => self.custom_palettes.iter()
+                .find(|custom| &custom.name == name)
+                .map_or(system, |custom| custom.palette),
+        }
+    }
+
+    /// 保存自定义配色，同名的配色会被替换
+    pub fn save_custom(&mut self, name: &str, palette: Palette) {
+        match self.custom_palettes.iter_mut().find(|custom| custom.name == name) {
+            Some(custom) => custom.palette = palette,
+            None => self.custom_palettes.push(CustomPalette {
+                name: name.to_string(),
+                palette,
+            }),
+        }
+    }
+
+    pub fn remove_custom(&mut self, name: &str) {
+        self.custom_palettes.retain(|custom| custom.name != name);
+        if self.mode == ThemeMode::Custom(name.to_string()) {
+            self.mode = ThemeMode::System;
+        }
+    }
+}
+
+/// 当前使用的配色
+pub fn palette() -> Palette {
+    *CURRENT.read().unwrap_or_else(|e| e.into_inner())
+}
 
 /// Mac风格的UI主题
 pub fn setup_mac_theme(ctx: &Context) {
     let mut style = (*ctx.style()).clone();
     
     // 字体设置
-    style.text_styles = [
-        (TextStyle::Heading, FontId::new(20.0, FontFamily::Proportional)),
-        (TextStyle::Body, FontId::new(16.0, FontFamily::Proportional)),
-        (TextStyle::Monospace, FontId::new(16.0, FontFamily::Monospace)),
-        (TextStyle::Button, FontId::new(16.0, FontFamily::Proportional)),
-        (TextStyle::Small, FontId::new(14.0, FontFamily::Proportional)),
-    ].into();
+    style.text_styles = text_styles(DEFAULT_FONT_SIZE);
     
     // 间距设置
     style.spacing.item_spacing = egui::vec2(8.0, 8.0);
@@ -23,14 +239,34 @@ pub fn setup_mac_theme(ctx: &Context) {
     style.spacing.menu_margin = egui::Margin::same(8.0);
     style.spacing.indent = 20.0;
     
-    // 视觉效果
-    let mut visuals = Visuals::light();
+    ctx.set_style(style);
+    apply_palette(ctx, &Palette::LIGHT);
+} 
+
+/// 切换配色，只替换颜色相关的样式，字号和间距保持不变
+pub fn apply_palette(ctx: &Context, palette: &Palette) {
+    *CURRENT.write().unwrap_or_else(|e| e.into_inner()) = *palette;
+
+    let mut style = (*ctx.style()).clone();
+    style.visuals = visuals(palette);
+    ctx.set_style(style);
+}
+
+fn visuals(palette: &Palette) -> Visuals {
+    let mut visuals = if palette.dark { Visuals::dark() } else { Visuals::light() };
     
     // 背景色
-    visuals.override_text_color = Some(Color32::from_rgb(50, 50, 50));
-    visuals.widgets.noninteractive.bg_fill = Color32::from_rgb(240, 240, 240);
-    visuals.extreme_bg_color = Color32::from_rgb(240, 240, 240);
-    visuals.faint_bg_color = Color32::from_rgb(230, 230, 230);
+    visuals.override_text_color = Some(palette.text);
+    visuals.panel_fill = palette.panel;
+    visuals.window_fill = palette.window;
+    visuals.window_stroke = Stroke::new(1.0, palette.border_strong);
+    visuals.widgets.noninteractive.bg_fill = palette.input;
+    visuals.widgets.noninteractive.bg_stroke = Stroke::new(1.0, palette.border);
+    visuals.extreme_bg_color = palette.input;
+    visuals.faint_bg_color = palette.stripe;
+    visuals.hyperlink_color = palette.accent_text;
+    visuals.warn_fg_color = palette.warning;
+    visuals.error_fg_color = palette.error;
     
     // 窗口圆角
     visuals.window_rounding = Rounding::same(8.0);
@@ -38,37 +274,59 @@ pub fn setup_mac_theme(ctx: &Context) {
     
     // 按钮样式
     let button_visuals = WidgetVisuals {
-        bg_fill: Color32::from_rgb(230, 230, 230),
-        weak_bg_fill: Color32::from_rgb(220, 220, 220),
-        bg_stroke: Stroke::new(1.0, Color32::from_rgb(200, 200, 200)),
+        bg_fill: palette.control,
+        weak_bg_fill: mix(palette.control, palette.text, 0.05),
+        bg_stroke: Stroke::new(1.0, palette.border_strong),
         rounding: Rounding::same(6.0),
-        fg_stroke: Stroke::new(1.0, Color32::from_rgb(50, 50, 50)),
+        fg_stroke: Stroke::new(1.0, palette.text),
         expansion: 0.0,
     };
     
     // 激活按钮样式
     let active_button_visuals = WidgetVisuals {
-        bg_fill: Color32::from_rgb(80, 145, 245),
-        weak_bg_fill: Color32::from_rgb(100, 160, 255),
-        bg_stroke: Stroke::new(1.0, Color32::from_rgb(70, 130, 220)),
+        bg_fill: palette.accent,
+        weak_bg_fill: mix(palette.accent, Color32::WHITE, 0.12),
+        bg_stroke: Stroke::new(1.0, mix(palette.accent, Color32::BLACK, 0.1)),
         rounding: Rounding::same(6.0),
-        fg_stroke: Stroke::new(1.0, Color32::from_rgb(255, 255, 255)),
+        fg_stroke: Stroke::new(1.0, palette.on_accent),
         expansion: 1.0,
     };
     
     // 应用按钮样式
-    visuals.widgets.inactive = button_visuals.clone();
-    visuals.widgets.hovered = button_visuals.clone();
+    visuals.widgets.inactive = button_visuals;
+    visuals.widgets.hovered = button_visuals;
     visuals.widgets.active = active_button_visuals;
     visuals.widgets.open = button_visuals;
     
     // 选择样式
     visuals.selection = Selection {
-        bg_fill: Color32::from_rgb(180, 200, 255),
-        stroke: Stroke::new(1.0, Color32::from_rgb(80, 145, 245)),
+        bg_fill: palette.selection,
+        stroke: Stroke::new(1.0, palette.accent),
     };
     
-    // 应用主题
-    style.visuals = visuals;
+    visuals
+}
+
+// 按比例混合两种不透明颜色
+fn mix(from: Color32, to: Color32, amount: f32) -> Color32 {
+    let channel = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * amount).round() as u8;
+    Color32::from_rgb(channel(from.r(), to.r()), channel(from.g(), to.g()), channel(from.b(), to.b()))
+}
+
+/// 按正文字号设置各种文字的大小
+pub fn set_font_size(ctx: &Context, size: f32) {
+    let mut style = (*ctx.style()).clone();
+    style.text_styles = text_styles(size);
     ctx.set_style(style);
-} 
\ No newline at end of file
+}
+
+// 标题和小字与正文保持默认主题中的比例
+fn text_styles(size: f32) -> BTreeMap<TextStyle, FontId> {
+    [
+        (TextStyle::Heading, FontId::new(size * 1.25, FontFamily::Proportional)),
+        (TextStyle::Body, FontId::new(size, FontFamily::Proportional)),
+        (TextStyle::Monospace, FontId::new(size, FontFamily::Monospace)),
+        (TextStyle::Button, FontId::new(size, FontFamily::Proportional)),
+        (TextStyle::Small, FontId::new(size * 0.875, FontFamily::Proportional)),
+    ].into()
+}
@@ -0,0 +1,201 @@
+// 主题对话框
+//
+// 选择跟随系统、浅色、深色或已保存的自定义配色，并可基于任一配色调整颜色后另存为自定义配色。
+// 对话框打开期间界面实时使用正在编辑的配色，取消时恢复原来的主题。
+
+use egui::{Context, Grid, RichText, Ui};
+
+use crate::ui::dialogs::setup_mac_window;
+use crate::ui::theme::{self, Palette, ThemeMode, ThemeSettings};
+use crate::ui::widgets;
+
+pub struct ThemeDialog {
+    pub open: bool,
+    pub confirmed: bool,
+    pub settings: ThemeSettings,
+    /// 正在编辑的配色，对话框打开时界面使用这套配色预览
+    pub palette: Palette,
+    /// 系统当前是否为深色外观
+    pub system_dark: bool,
+    name: String,
+    modified: bool,
+    error_message: Option<String>,
+}
+
+impl ThemeDialog {
+    pub fn new() -> Self {
+        ThemeDialog {
+            open: false,
+            confirmed: false,
+            settings: ThemeSettings::default(),
+            palette: Palette::LIGHT,
+            system_dark: false,
+            name: String::new(),
+            modified: false,
+            error_message: None,
+        }
+    }
+
+    pub fn open(&mut self, settings: &ThemeSettings) {
+        self.settings = settings.clone();
+        self.select_mode(settings.mode.clone());
+        self.confirmed = false;
+        self.open = true;
+    }
+
+    // 切换主题模式，编辑区显示该模式的配色
+    fn select_mode(&mut self, mode: ThemeMode) {
+        self.name = match &mode {
+            ThemeMode::Custom(name) => name.clone(),
+            _ => String::new(),
+        };
+        self.settings.mode = mode;
+        self.palette = self.settings.palette(self.system_dark);
+        self.modified = false;
+        self.error_message = None;
+    }
+
+    pub fn show(&mut self, ctx: &Context) -> bool {
+        if !self.open {
+            return false;
+        }
+
+        let mut closed = false;
+        let mut confirmed = false;
+
+        setup_mac_window(t!("主题"))
+            .min_width(520.0)
+            .show(ctx, |ui| {
+                ui.add_space(5.0);
+                self.show_modes(ui);
+
+                ui.add_space(10.0);
+                widgets::horizontal_separator(ui);
+                ui.add_space(5.0);
+                self.show_editor(ui);
+
+                if let Some(message) = &self.error_message {
+                    ui.add_space(5.0);
+                    widgets::error_message(ui, message);
+                }
+
+                ui.add_space(10.0);
+                widgets::horizontal_separator(ui);
+                ui.add_space(5.0);
+
+                ui.horizontal(|ui| {
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if widgets::mac_primary_button(ui, t!("应用")) && self.save_modified() {
+                            confirmed = true;
+                            closed = true;
+                        }
+
+                        ui.add_space(10.0);
+
+                        if widgets::mac_button(ui, t!("取消")) {
+                            closed = true;
+                        }
+                    });
+                });
+            });
+
+        if closed {
+            self.open = false;
+        }
+
+        self.confirmed = confirmed;
+        confirmed
+    }
+
+    fn show_modes(&mut self, ui: &mut Ui) {
+        let mut modes = vec![ThemeMode::System, ThemeMode::Light, ThemeMode::Dark];
+        modes.extend(self.settings.custom_palettes.iter().map(|custom| ThemeMode::Custom(custom.name.clone())));
+
+        ui.horizontal_wrapped(|ui| {
+            ui.label(t!("主题:"));
+            for mode in modes {
+                if ui.selectable_label(self.settings.mode == mode, mode.as_str()).clicked() {
+                    self.select_mode(mode);
+                }
+            }
+        });
+    }
+
+    fn show_editor(&mut self, ui: &mut Ui) {
+        ui.strong(t!("自定义配色"));
+        ui.horizontal(|ui| {
+            ui.label(t!("基于:"));
+            if ui.button(t!("浅色")).clicked() {
+                self.palette = Palette::LIGHT;
+                self.modified = true;
+            }
+            if ui.button(t!("深色")).clicked() {
+                self.palette = Palette::DARK;
+                self.modified = true;
+            }
+        });
+
+        ui.add_space(5.0);
+        let mut changed = false;
+        let mut colors = self.palette.colors_mut();
+        Grid::new("theme_colors")
+            .num_columns(4)
+            .spacing([10.0, 6.0])
+            .show(ui, |ui| {
+                for (i, (label, color)) in colors.iter_mut().enumerate() {
+                    ui.label(*label);
+                    changed |= ui.color_edit_button_srgba(color).changed();
+                    if i % 2 == 1 {
+                        ui.end_row();
+                    }
+                }
+            });
+        if changed {
+            self.modified = true;
+        }
+
+        ui.add_space(5.0);
+        ui.horizontal(|ui| {
+            ui.label(t!("名称:"));
+            ui.text_edit_singleline(&mut self.name);
+
+            if widgets::mac_button(ui, t!("保存配色")) {
+                self.modified = true;
+                self.save_modified();
+            }
+
+            let saved = self.settings.custom_palettes.iter().any(|custom| custom.name == self.name.trim());
+            if saved && widgets::mac_button(ui, t!("删除配色")) {
+                self.settings.remove_custom(self.name.trim());
+                self.select_mode(self.settings.mode.clone());
+            }
+        });
+
+        let hint = if self.modified {
+            t!("应用时会以上面的名称保存为自定义配色")
+        } else {
+            t!("修改任意颜色即可基于当前配色创建自定义配色")
+        };
+        ui.label(RichText::new(hint).small().color(theme::palette().weak_text));
+    }
+
+    // 把修改过的配色保存为自定义配色并选中，没有名称时提示错误
+    fn save_modified(&mut self) -> bool {
+        if !self.modified {
+            return true;
+        }
+
+        let name = self.name.trim().to_string();
+        if name.is_empty() {
+            self.error_message = Some(t!("请输入配色名称").to_string());
+            return false;
+        }
+
+        self.settings.save_custom(&name, self.palette);
+        self.settings.mode = ThemeMode::Custom(name.clone());
+        self.name = name;
+        self.modified = false;
+        self.error_message = None;
+        true
+    }
+}
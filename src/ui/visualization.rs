@@ -1,28 +1,80 @@
 use std::collections::HashMap;
 use egui::{Color32, Ui, ScrollArea, ComboBox, Grid, RichText, Stroke, Rounding, pos2, Rect, Align2, Vec2};
-use crate::data::team::Team;
+use crate::chart::{Chart, ChartData, ScatterDatum};
+use crate::data::staff::Staff;
+use crate::data::team::{Leagues, Team};
+use crate::stats::{self, Bucket, BucketMode, ChartDimension, StatField};
+use crate::report::ReportSection;
+use crate::ui::scatter_plot::{ScatterPlot, ScatterPoint};
 use crate::ui::widgets;
+use crate::ui::theme;
+
+// 散点图中按联赛区分的颜色
+const LEAGUE_COLORS: [Color32; 10] = [
+    Color32::from_rgb(70, 120, 230),
+    Color32::from_rgb(240, 130, 60),
+    Color32::from_rgb(60, 180, 100),
+    Color32::from_rgb(220, 70, 110),
+    Color32::from_rgb(140, 90, 220),
+    Color32::from_rgb(150, 190, 40),
+    Color32::from_rgb(40, 170, 190),
+    Color32::from_rgb(200, 160, 30),
+    Color32::from_rgb(120, 120, 120),
+    Color32::from_rgb(170, 100, 60),
+];
+
+// 地区和联赛分布图中单独显示的最大分类数，其余合并为"其他"
+const MAX_CATEGORIES: usize = 10;
+
+/// 图表交互后需要由应用处理的操作
+pub enum VisualizationAction {
+    SelectTeam(i64),
+    ExportChart,
+    ExportReport,
+    /// 点击图表分段后，在球队列表中只显示这些球队，label为完整的下钻路径
+    DrillDown { label: String, team_ids: Vec<i64> },
+    /// 返回到全部球队
+    ClearDrillDown,
+}
+
+/// 下钻路径中的一级，保存该级包含的球队ID（已排序）
+pub struct DrillStep {
+    pub label: String,
+    pub team_ids: Vec<i64>,
+}
 
 pub struct VisualizationView {
+    /// 当前下钻层级中的球队和员工，未下钻时为全部数据
     pub teams: Vec<Team>,
+    pub staff: Vec<Staff>,
+    all_teams: Vec<Team>,
+    all_staff: Vec<Staff>,
+    pub drill_path: Vec<DrillStep>,
     pub chart_type: ChartType,
     pub chart_style: ChartStyle,
-    pub wealth_ranges: Vec<(i64, i64)>,
-    pub supporter_ranges: Vec<(i64, i64)>,
+    pub dimension: ChartDimension,
+    pub bucket_mode: BucketMode,
+    pub bucket_count: usize,
+    /// 每个字段单独保存的自定义区间
+    pub custom_buckets: HashMap<ChartDimension, Vec<Bucket>>,
     pub location_counts: HashMap<String, i64>,
     pub league_counts: HashMap<i64, i64>,
-    pub selected_location: Option<String>,
+    // 排序后的联赛ID，用于分配散点图颜色
+    league_ids: Vec<i64>,
+    pub leagues: Leagues,
     pub show_data_table: bool,
     pub show_percentage: bool,
+    pub scatter_x: StatField,
+    pub scatter_y: StatField,
+    scatter_plot: ScatterPlot,
 }
 
 #[derive(PartialEq, Clone, Copy)]
 pub enum ChartType {
-    WealthDistribution,
-    SupporterDistribution,
+    NumericDistribution,
     LocationDistribution,
     LeagueDistribution,
-    FoundYearDistribution,
+    Scatter,
 }
 
 #[derive(PartialEq, Clone, Copy)]
@@ -34,11 +86,10 @@ pub enum ChartStyle {
 impl ChartType {
     fn as_str(&self) -> &'static str {
         match self {
-            ChartType::WealthDistribution => "球队财富分布",
-            ChartType::SupporterDistribution => "球迷数量分布",
-            ChartType::LocationDistribution => "地区分布",
-            ChartType::LeagueDistribution => "联赛分布",
-            ChartType::FoundYearDistribution => "成立年份分布",
+            ChartType::NumericDistribution => t!("数值分布"),
+            ChartType::LocationDistribution => t!("地区分布"),
+            ChartType::LeagueDistribution => t!("联赛分布"),
+            ChartType::Scatter => t!("散点图"),
         }
     }
 }
@@ -46,8 +97,8 @@ impl ChartType {
 impl ChartStyle {
     fn as_str(&self) -> &'static str {
         match self {
-            ChartStyle::BarChart => "条形图",
-            ChartStyle::PieChart => "饼图",
+            ChartStyle::BarChart => t!("条形图"),
+            ChartStyle::PieChart => t!("饼图"),
         }
     }
 }
@@ -56,35 +107,207 @@ impl VisualizationView {
     pub fn new() -> Self {
         VisualizationView {
             teams: Vec::new(),
-            chart_type: ChartType::WealthDistribution,
+            staff: Vec::new(),
+            all_teams: Vec::new(),
+            all_staff: Vec::new(),
+            drill_path: Vec::new(),
+            chart_type: ChartType::NumericDistribution,
             chart_style: ChartStyle::BarChart,
-            wealth_ranges: vec![
-                (0, 1000),
-                (1001, 5000),
-                (5001, 10000),
-                (10001, 50000),
-                (50001, i64::MAX),
-            ],
-            supporter_ranges: vec![
-                (0, 10000),
-                (10001, 50000),
-                (50001, 100000),
-                (100001, 500000),
-                (500001, i64::MAX),
-            ],
+            dimension: ChartDimension::TeamWealth,
+            bucket_mode: BucketMode::EqualWidth,
+            bucket_count: 8,
+            custom_buckets: ChartDimension::ALL.iter()
+                .filter_map(|&dimension| default_custom_buckets(dimension).map(|buckets| (dimension, buckets)))
+                .collect(),
             location_counts: HashMap::new(),
             league_counts: HashMap::new(),
-            selected_location: None,
+            league_ids: Vec::new(),
+            leagues: Leagues::default(),
             show_data_table: true,
             show_percentage: true,
+            scatter_x: StatField::Wealth,
+            scatter_y: StatField::SupporterCount,
+            scatter_plot: ScatterPlot::new("team_scatter"),
         }
     }
 
     pub fn set_teams(&mut self, teams: Vec<Team>) {
-        self.teams = teams;
+        self.all_teams = teams;
+        self.refresh_drill_down();
+    }
+
+    pub fn set_leagues(&mut self, leagues: Leagues) {
+        self.leagues = leagues;
+    }
+
+    pub fn set_staff(&mut self, staff: Vec<Staff>) {
+        self.all_staff = staff;
+        self.refresh_drill_down();
+    }
+
+    /// 更新单个员工的数据
+    pub fn update_staff(&mut self, staff: &Staff) {
+        let Some(existing) = self.all_staff.iter_mut().find(|s| s.id == staff.id) else {
+            return;
+        };
+        let team_changed = existing.team_id != staff.team_id;
+        *existing = staff.clone();
+
+        // 员工转会后可能进入或离开当前下钻层级
+        if team_changed && !self.drill_path.is_empty() {
+            self.refresh_drill_down();
+        } else if let Some(existing) = self.staff.iter_mut().find(|s| s.id == staff.id) {
+            *existing = staff.clone();
+        }
+    }
+
+    /// 更新单个球队的数据并重新统计，下钻层级中的球队不变
+    pub fn update_team(&mut self, team: &Team) {
+        if let Some(existing) = self.all_teams.iter_mut().find(|t| t.id == team.id) {
+            *existing = team.clone();
+        }
+        if let Some(existing) = self.teams.iter_mut().find(|t| t.id == team.id) {
+            *existing = team.clone();
+            self.update_statistics();
+        }
+    }
+
+    // 按下钻路径的最后一级重新筛选球队和员工
+    fn refresh_drill_down(&mut self) {
+        match self.drill_path.last() {
+            Some(step) => {
+                let ids = &step.team_ids;
+                self.teams = self.all_teams.iter()
+                    .filter(|t| ids.binary_search(&t.id).is_ok())
+                    .cloned()
+                    .collect();
+                self.staff = self.all_staff.iter()
+                    .filter(|s| ids.binary_search(&s.team_id).is_ok())
+                    .cloned()
+                    .collect();
+            },
+            None => {
+                self.teams = self.all_teams.clone();
+                self.staff = self.all_staff.clone();
+            },
+        }
+        self.scatter_plot.reset_view();
         self.update_statistics();
     }
 
+    /// 返回到全部球队
+    pub fn clear_drill_down(&mut self) {
+        if !self.drill_path.is_empty() {
+            self.drill_path.clear();
+            self.refresh_drill_down();
+        }
+    }
+
+    fn drill_down_label(&self) -> String {
+        self.drill_path.iter()
+            .map(|step| step.label.as_str())
+            .collect::<Vec<_>>()
+            .join(" › ")
+    }
+
+    // 进入下一级，返回需要应用到球队列表的筛选
+    fn drill_into(&mut self, label: String, mut team_ids: Vec<i64>) -> VisualizationAction {
+        team_ids.sort_unstable();
+        team_ids.dedup();
+        self.drill_path.push(DrillStep { label, team_ids: team_ids.clone() });
+        self.refresh_drill_down();
+        VisualizationAction::DrillDown { label: self.drill_down_label(), team_ids }
+    }
+
+    // 回到下钻路径的第depth级，0表示全部球队
+    fn drill_back_to(&mut self, depth: usize) -> VisualizationAction {
+        self.drill_path.truncate(depth);
+        self.refresh_drill_down();
+        match self.drill_path.last() {
+            Some(step) => VisualizationAction::DrillDown {
+                label: self.drill_down_label(),
+                team_ids: step.team_ids.clone(),
+            },
+            None => VisualizationAction::ClearDrillDown,
+        }
+    }
+
+    fn drill_into_bucket(&mut self, bucket: Bucket) -> VisualizationAction {
+        let dimension = self.dimension;
+        // 员工字段筛选出拥有该区间员工的球队
+        let team_ids = if dimension.is_staff() {
+            self.staff.iter()
+                .filter(|s| dimension.staff_value(s).is_some_and(|v| bucket.contains(v)))
+                .map(|s| s.team_id)
+                .collect()
+        } else {
+            self.teams.iter()
+                .filter(|t| dimension.team_value(t).is_some_and(|v| bucket.contains(v)))
+                .map(|t| t.id)
+                .collect()
+        };
+        self.drill_into(format!("{} {}", dimension.as_str(), bucket.label()), team_ids)
+    }
+
+    fn drill_into_location(&mut self, index: usize) -> VisualizationAction {
+        let rows = self.location_rows();
+        let selected = selected_categories(&rows, index);
+        let label = if index < MAX_CATEGORIES {
+            t!("地区 {}", rows[index].0)
+        } else {
+            t!("其他地区").to_string()
+        };
+        let team_ids = self.teams.iter()
+            .filter(|t| selected.iter().any(|(location, _)| location == &t.location))
+            .map(|t| t.id)
+            .collect();
+        self.drill_into(label, team_ids)
+    }
+
+    fn drill_into_league(&mut self, index: usize) -> VisualizationAction {
+        let order = self.league_order();
+        let selected: Vec<i64> = selected_categories(&order, index).iter().map(|(id, _)| *id).collect();
+        let label = if index < MAX_CATEGORIES {
+            self.leagues.name(order[index].0)
+        } else {
+            t!("其他联赛").to_string()
+        };
+        let team_ids = self.teams.iter()
+            .filter(|t| selected.contains(&t.league_id))
+            .map(|t| t.id)
+            .collect();
+        self.drill_into(label, team_ids)
+    }
+
+    fn show_breadcrumbs(&mut self, ui: &mut Ui) -> Option<VisualizationAction> {
+        if self.drill_path.is_empty() {
+            if self.chart_type != ChartType::Scatter {
+                ui.label(RichText::new(t!("点击条形或扇形可查看对应的球队")).small().color(theme::palette().weak_text));
+                ui.add_space(5.0);
+            }
+            return None;
+        }
+
+        let mut target = None;
+        ui.horizontal_wrapped(|ui| {
+            if ui.link(t!("全部球队")).clicked() {
+                target = Some(0);
+            }
+            let last = self.drill_path.len() - 1;
+            for (depth, step) in self.drill_path.iter().enumerate() {
+                ui.label("›");
+                if depth == last {
+                    ui.strong(format!("{} ({})", step.label, step.team_ids.len()));
+                } else if ui.link(&step.label).clicked() {
+                    target = Some(depth + 1);
+                }
+            }
+        });
+        ui.add_space(10.0);
+
+        target.map(|depth| self.drill_back_to(depth))
+    }
+
     pub fn update_statistics(&mut self) {
         // 更新地区统计
         self.location_counts.clear();
@@ -97,47 +320,196 @@ impl VisualizationView {
         for team in &self.teams {
             *self.league_counts.entry(team.league_id).or_insert(0) += 1;
         }
+        self.league_ids = self.league_counts.keys().copied().collect();
+        self.league_ids.sort_unstable();
+    }
+
+    // 联赛按ID排序后分配颜色，保证每次打开颜色一致
+    fn league_color(&self, league_id: i64) -> Color32 {
+        let index = self.league_ids.binary_search(&league_id).unwrap_or(0);
+        LEAGUE_COLORS[index % LEAGUE_COLORS.len()]
     }
 
-    pub fn ui(&mut self, ui: &mut Ui) {
-        widgets::titled_frame("数据可视化", ui, |ui| {
+    fn scatter_points(&self) -> Vec<ScatterPoint> {
+        self.teams.iter()
+            .map(|team| ScatterPoint {
+                id: team.id,
+                x: self.scatter_x.value(team) as f64,
+                y: self.scatter_y.value(team) as f64,
+                label: format!("{} ({})", team.name, self.leagues.name(team.league_id)),
+                color: self.league_color(team.league_id),
+            })
+            .collect()
+    }
+
+    // 数值字段每个区间的标签和数量
+    fn numeric_rows(&self, dimension: ChartDimension) -> Vec<(String, i64)> {
+        let values = dimension.values(&self.teams, &self.staff);
+        let buckets = self.buckets_for(dimension, &values);
+        let counts = stats::count_in_buckets(&values, &buckets);
+        buckets.iter().map(Bucket::label).zip(counts).collect()
+    }
+
+    // 按数量从多到少排列的地区分布
+    fn location_rows(&self) -> Vec<(String, i64)> {
+        let mut rows: Vec<(String, i64)> = self.location_counts.iter()
+            .map(|(k, v)| (k.clone(), *v))
+            .collect();
+        rows.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        rows
+    }
+
+    // 按数量从多到少排列的联赛ID和球队数量
+    fn league_order(&self) -> Vec<(i64, i64)> {
+        let mut rows: Vec<(i64, i64)> = self.league_counts.iter()
+            .map(|(k, v)| (*k, *v))
+            .collect();
+        rows.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        rows
+    }
+
+    // 按数量从多到少排列的联赛分布
+    fn league_rows(&self) -> Vec<(String, i64)> {
+        self.league_order()
+            .into_iter()
+            .map(|(id, count)| (self.leagues.name(id), count))
+            .collect()
+    }
+
+    fn category_chart(&self, title: &str, rows: &[(String, i64)]) -> Chart {
+        let entries = top_with_other(rows);
+        let data = match self.chart_style {
+            ChartStyle::BarChart => ChartData::Bar(entries),
+            ChartStyle::PieChart => ChartData::Pie(entries),
+        };
+        Chart::new(title, data)
+    }
+
+    fn scatter_chart(&self) -> Chart {
+        let points = self.scatter_points()
+            .into_iter()
+            .map(|p| ScatterDatum { x: p.x, y: p.y, color: [p.color.r(), p.color.g(), p.color.b()] })
+            .collect();
+        let legend = self.league_ids.iter()
+            .map(|&id| {
+                let color = self.league_color(id);
+                (self.leagues.name(id), [color.r(), color.g(), color.b()])
+            })
+            .collect();
+
+        Chart::new(
+            &format!("{} - {}", self.scatter_x.as_str(), self.scatter_y.as_str()),
+            ChartData::Scatter {
+                x_label: self.scatter_x.as_str().to_string(),
+                y_label: self.scatter_y.as_str().to_string(),
+                points,
+                legend,
+            },
+        )
+    }
+
+    /// 当前显示的图表，用于导出
+    pub fn current_chart(&self) -> Option<Chart> {
+        if self.teams.is_empty() {
+            return None;
+        }
+
+        let chart = match self.chart_type {
+            ChartType::NumericDistribution => {
+                let title = t!("{}分布", self.dimension.as_str());
+                self.category_chart(&title, &self.numeric_rows(self.dimension))
+            },
+            ChartType::LocationDistribution => self.category_chart(t!("地区分布"), &self.location_rows()),
+            ChartType::LeagueDistribution => self.category_chart(t!("联赛分布"), &self.league_rows()),
+            ChartType::Scatter => self.scatter_chart(),
+        };
+        Some(chart)
+    }
+
+    /// 报告中包含的图表，数值字段使用当前的分组方式
+    pub fn report_sections(&self) -> Vec<ReportSection> {
+        let mut sections: Vec<ReportSection> = ChartDimension::ALL.iter()
+            .filter(|dimension| **dimension != ChartDimension::TeamLeagueId)
+            .map(|&dimension| {
+                let rows = self.numeric_rows(dimension);
+                ReportSection {
+                    chart: self.category_chart(&t!("{}分布", dimension.as_str()), &rows),
+                    category: t!("{}范围", dimension.as_str()),
+                    unit: dimension.unit().to_string(),
+                    rows,
+                }
+            })
+            .filter(|section| !section.rows.is_empty())
+            .collect();
+
+        let location_rows = self.location_rows();
+        sections.push(ReportSection {
+            chart: self.category_chart(t!("地区分布"), &location_rows),
+            category: t!("地区").to_string(),
+            unit: t!("球队数量").to_string(),
+            rows: location_rows,
+        });
+
+        let league_rows = self.league_rows();
+        sections.push(ReportSection {
+            chart: self.category_chart(t!("联赛分布"), &league_rows),
+            category: t!("联赛").to_string(),
+            unit: t!("球队数量").to_string(),
+            rows: league_rows,
+        });
+
+        sections.push(ReportSection {
+            chart: self.scatter_chart(),
+            category: String::new(),
+            unit: String::new(),
+            rows: Vec::new(),
+        });
+
+        sections
+    }
+
+    /// 显示图表，selected_team_id为球队列表中当前选中的球队
+    pub fn ui(&mut self, ui: &mut Ui, selected_team_id: Option<i64>) -> Option<VisualizationAction> {
+        let palette = theme::palette();
+        let mut action = None;
+
+        widgets::titled_frame(t!("数据可视化"), ui, |ui| {
             // 图表控制面板
             egui::Frame::none()
-                .fill(Color32::from_rgb(245, 245, 250))
-                .stroke(Stroke::new(1.0, Color32::from_rgb(220, 220, 230)))
+                .fill(palette.surface_alt)
+                .stroke(Stroke::new(1.0, palette.border))
                 .rounding(Rounding::same(8.0))
                 .inner_margin(egui::Margin::same(12.0))
                 .show(ui, |ui| {
                     ui.horizontal(|ui| {
                         ui.vertical(|ui| {
                             ui.horizontal(|ui| {
-                                ui.strong(RichText::new("图表类型:").color(Color32::from_rgb(40, 40, 80)).size(14.0));
+                                ui.strong(RichText::new(t!("图表类型:")).color(palette.heading).size(14.0));
                                 ui.add_space(5.0);
                                 
                                 // 自定义下拉框样式
                                 let dropdown_text = RichText::new(self.chart_type.as_str())
                                     .strong()
-                                    .color(Color32::from_rgb(20, 20, 60))
+                                    .color(palette.heading)
                                     .size(14.0);
                                 
                                 ComboBox::from_id_source("chart_type")
                                     .selected_text(dropdown_text)
                                     .width(150.0)
                                     .show_ui(ui, |ui| {
-                                        ui.style_mut().visuals.widgets.active.fg_stroke = Stroke::new(1.5, Color32::from_rgb(20, 20, 60));
-                                        ui.style_mut().visuals.widgets.hovered.fg_stroke = Stroke::new(1.0, Color32::from_rgb(40, 40, 100));
+                                        ui.style_mut().visuals.widgets.active.fg_stroke = Stroke::new(1.5, palette.heading);
+                                        ui.style_mut().visuals.widgets.hovered.fg_stroke = Stroke::new(1.0, palette.heading);
                                         
                                         let chart_types = [
-                                            ChartType::WealthDistribution,
-                                            ChartType::SupporterDistribution,
+                                            ChartType::NumericDistribution,
                                             ChartType::LocationDistribution,
                                             ChartType::LeagueDistribution,
-                                            ChartType::FoundYearDistribution,
+                                            ChartType::Scatter,
                                         ];
                                         
                                         for chart_type in chart_types.iter() {
                                             let text = RichText::new(chart_type.as_str())
-                                                .color(Color32::from_rgb(20, 20, 60))
+                                                .color(palette.heading)
                                                 .size(14.0);
                                                 
                                             if ui.selectable_label(self.chart_type == *chart_type, text).clicked() {
@@ -145,26 +517,38 @@ impl VisualizationView {
                                             }
                                         }
                                     });
+
+                                if self.chart_type == ChartType::NumericDistribution {
+                                    ui.add_space(10.0);
+                                    ComboBox::from_id_source("chart_dimension")
+                                        .selected_text(RichText::new(self.dimension.as_str()).strong().size(14.0))
+                                        .width(120.0)
+                                        .show_ui(ui, |ui| {
+                                            for dimension in ChartDimension::ALL {
+                                                ui.selectable_value(&mut self.dimension, dimension, dimension.as_str());
+                                            }
+                                        });
+                                }
                             });
                             
                             ui.add_space(5.0);
                             
                             ui.horizontal(|ui| {
-                                ui.strong(RichText::new("图表样式:").color(Color32::from_rgb(40, 40, 80)).size(14.0));
+                                ui.strong(RichText::new(t!("图表样式:")).color(palette.heading).size(14.0));
                                 ui.add_space(5.0);
                                 
                                 // 自定义下拉框样式
                                 let dropdown_text = RichText::new(self.chart_style.as_str())
                                     .strong()
-                                    .color(Color32::from_rgb(20, 20, 60))
+                                    .color(palette.heading)
                                     .size(14.0);
                                 
                                 ComboBox::from_id_source("chart_style")
                                     .selected_text(dropdown_text)
                                     .width(150.0)
                                     .show_ui(ui, |ui| {
-                                        ui.style_mut().visuals.widgets.active.fg_stroke = Stroke::new(1.5, Color32::from_rgb(20, 20, 60));
-                                        ui.style_mut().visuals.widgets.hovered.fg_stroke = Stroke::new(1.0, Color32::from_rgb(40, 40, 100));
+                                        ui.style_mut().visuals.widgets.active.fg_stroke = Stroke::new(1.5, palette.heading);
+                                        ui.style_mut().visuals.widgets.hovered.fg_stroke = Stroke::new(1.0, palette.heading);
                                         
                                         let chart_styles = [
                                             ChartStyle::BarChart,
@@ -173,7 +557,7 @@ impl VisualizationView {
                                         
                                         for chart_style in chart_styles.iter() {
                                             let text = RichText::new(chart_style.as_str())
-                                                .color(Color32::from_rgb(20, 20, 60))
+                                                .color(palette.heading)
                                                 .size(14.0);
                                                 
                                             if ui.selectable_label(self.chart_style == *chart_style, text).clicked() {
@@ -189,12 +573,12 @@ impl VisualizationView {
                         ui.vertical(|ui| {
                             ui.horizontal(|ui| {
                                 // 自定义复选框样式
-                                ui.style_mut().visuals.widgets.active.fg_stroke = Stroke::new(1.5, Color32::from_rgb(20, 20, 60));
-                                ui.style_mut().visuals.widgets.hovered.fg_stroke = Stroke::new(1.0, Color32::from_rgb(40, 40, 100));
+                                ui.style_mut().visuals.widgets.active.fg_stroke = Stroke::new(1.5, palette.heading);
+                                ui.style_mut().visuals.widgets.hovered.fg_stroke = Stroke::new(1.0, palette.heading);
                                 
-                                let checkbox_text = RichText::new("显示数据表格")
+                                let checkbox_text = RichText::new(t!("显示数据表格"))
                                     .strong()
-                                    .color(Color32::from_rgb(40, 40, 80))
+                                    .color(palette.heading)
                                     .size(14.0);
                                     
                                 ui.checkbox(&mut self.show_data_table, checkbox_text);
@@ -204,26 +588,40 @@ impl VisualizationView {
                             
                             ui.horizontal(|ui| {
                                 // 自定义复选框样式
-                                ui.style_mut().visuals.widgets.active.fg_stroke = Stroke::new(1.5, Color32::from_rgb(20, 20, 60));
-                                ui.style_mut().visuals.widgets.hovered.fg_stroke = Stroke::new(1.0, Color32::from_rgb(40, 40, 100));
+                                ui.style_mut().visuals.widgets.active.fg_stroke = Stroke::new(1.5, palette.heading);
+                                ui.style_mut().visuals.widgets.hovered.fg_stroke = Stroke::new(1.0, palette.heading);
                                 
-                                let checkbox_text = RichText::new("显示百分比")
+                                let checkbox_text = RichText::new(t!("显示百分比"))
                                     .strong()
-                                    .color(Color32::from_rgb(40, 40, 80))
+                                    .color(palette.heading)
                                     .size(14.0);
                                     
                                 ui.checkbox(&mut self.show_percentage, checkbox_text);
                             });
                         });
+                        
+                        ui.add_space(30.0);
+                        
+                        ui.vertical(|ui| {
+                            if ui.button(t!("导出图表...")).on_hover_text(t!("将当前图表保存为PNG或SVG")).clicked() {
+                                action = Some(VisualizationAction::ExportChart);
+                            }
+                            
+                            ui.add_space(5.0);
+                            
+                            if ui.button(t!("生成报告...")).on_hover_text(t!("生成包含图表、数据表格和统计摘要的HTML报告")).clicked() {
+                                action = Some(VisualizationAction::ExportReport);
+                            }
+                        });
                     });
                 });
 
             ui.add_space(15.0);
 
             // 图表内容
-            egui::Frame::none()
-                .fill(Color32::from_rgb(255, 255, 255))
-                .stroke(Stroke::new(1.0, Color32::from_rgb(230, 230, 230)))
+            let chart_action = egui::Frame::none()
+                .fill(palette.surface)
+                .stroke(Stroke::new(1.0, palette.border))
                 .rounding(Rounding::same(8.0))
                 .inner_margin(egui::Margin::same(20.0))
                 .shadow(egui::epaint::Shadow {
@@ -231,266 +629,305 @@ impl VisualizationView {
                     color: Color32::from_black_alpha(20),
                 })
                 .show(ui, |ui| {
-                    match self.chart_type {
-                        ChartType::WealthDistribution => self.show_wealth_distribution(ui),
-                        ChartType::SupporterDistribution => self.show_supporter_distribution(ui),
+                    let breadcrumb_action = self.show_breadcrumbs(ui);
+                    let chart_action = match self.chart_type {
+                        ChartType::NumericDistribution => self.show_numeric_distribution(ui),
                         ChartType::LocationDistribution => self.show_location_distribution(ui),
                         ChartType::LeagueDistribution => self.show_league_distribution(ui),
-                        ChartType::FoundYearDistribution => self.show_found_year_distribution(ui),
-                    }
-                });
+                        ChartType::Scatter => self.show_scatter(ui, selected_team_id),
+                    };
+                    breadcrumb_action.or(chart_action)
+                })
+                .inner;
+            
+            if chart_action.is_some() {
+                action = chart_action;
+            }
         });
+
+        action
     }
 
-    fn show_wealth_distribution(&self, ui: &mut Ui) {
-        let mut values = vec![0; self.wealth_ranges.len()];
-        let mut labels = Vec::new();
+    fn show_scatter(&mut self, ui: &mut Ui, selected_team_id: Option<i64>) -> Option<VisualizationAction> {
+        ui.horizontal(|ui| {
+            ui.strong(t!("X轴:"));
+            let mut changed = field_combo(ui, "scatter_x", &mut self.scatter_x);
+            if ui.button("⇄").on_hover_text(t!("交换X轴和Y轴")).clicked() {
+                std::mem::swap(&mut self.scatter_x, &mut self.scatter_y);
+                changed = true;
+            }
+            ui.strong(t!("Y轴:"));
+            changed |= field_combo(ui, "scatter_y", &mut self.scatter_y);
+            if changed {
+                self.scatter_plot.reset_view();
+            }
 
-        // 计算每个财富范围的球队数量
-        for team in &self.teams {
-            for (i, (min, max)) in self.wealth_ranges.iter().enumerate() {
-                if team.wealth >= *min && team.wealth <= *max {
-                    values[i] += 1;
-                    break;
-                }
+            ui.add_space(20.0);
+            if ui.button(t!("重置视图")).clicked() {
+                self.scatter_plot.reset_view();
             }
-        }
+        });
 
-        // 创建标签
-        for (min, max) in &self.wealth_ranges {
-            if *max == i64::MAX {
-                labels.push(format!("{}+", min));
-            } else {
-                labels.push(format!("{}-{}", min, max));
+        let points = self.scatter_points();
+
+        let pairs: Vec<(f64, f64)> = points.iter().map(|p| (p.x, p.y)).collect();
+        let title = format!("{} - {}", self.scatter_x.as_str(), self.scatter_y.as_str());
+        ui.add_space(10.0);
+        ui.horizontal(|ui| {
+            ui.heading(RichText::new(&title).size(20.0).strong().color(theme::palette().heading));
+            ui.add_space(20.0);
+            match stats::correlation(&pairs) {
+                Some(r) => ui.label(t!("相关系数 r = {:.3}", r)),
+                None => ui.label(t!("相关系数: 无法计算")),
+            };
+        });
+        ui.label(RichText::new(t!("滚轮缩放，拖动平移，双击恢复，点击数据点选中球队")).small().color(theme::palette().weak_text));
+        ui.add_space(10.0);
+
+        let clicked = self.scatter_plot.ui(
+            ui,
+            &points,
+            self.scatter_x.as_str(),
+            self.scatter_y.as_str(),
+            selected_team_id,
+            450.0,
+        );
+
+        // 图例
+        ui.add_space(10.0);
+        ui.horizontal_wrapped(|ui| {
+            for &league_id in &self.league_ids {
+                ui.colored_label(self.league_color(league_id), "●");
+                ui.label(self.leagues.name(league_id))
+                    .on_hover_text(self.leagues.hover_text(league_id));
+                ui.add_space(8.0);
             }
+        });
+
+        clicked.map(VisualizationAction::SelectTeam)
+    }
+
+    fn show_numeric_distribution(&mut self, ui: &mut Ui) -> Option<VisualizationAction> {
+        let palette = theme::palette();
+        let values = self.dimension.values(&self.teams, &self.staff);
+        if values.is_empty() {
+            ui.label(t!("没有{}数据可供显示", self.dimension.as_str()));
+            return None;
         }
 
+        self.show_bucket_controls(ui, &values);
+
+        let buckets = self.buckets_for(self.dimension, &values);
+        let counts = stats::count_in_buckets(&values, &buckets);
+        let labels = buckets.iter().map(Bucket::label).collect::<Vec<_>>();
+        let outside = values.len() as i64 - counts.iter().sum::<i64>();
+
         // 绘制图表
-        ui.heading(RichText::new("球队财富分布").size(20.0).strong().color(Color32::from_rgb(60, 60, 80)));
+        let title = t!("{}分布", self.dimension.as_str());
+        ui.heading(RichText::new(&title).size(20.0).strong().color(palette.heading));
         ui.add_space(15.0);
-        
+
+        if buckets.is_empty() {
+            ui.label(t!("没有可用的区间，请添加自定义区间"));
+            return None;
+        }
+
         // 创建滚动区域以确保图表完整显示
-        ScrollArea::vertical()
+        let clicked = ScrollArea::vertical()
             .max_height(500.0)
             .show(ui, |ui| {
                 match self.chart_style {
-                    ChartStyle::BarChart => {
-                        // 为条形图分配足够的高度
-                        widgets::draw_bar_chart(ui, &values, &labels, "", 400.0);
-                    },
-                    ChartStyle::PieChart => {
-                        // 为饼图分配足够的高度
-                        self.draw_pie_chart(ui, &values, &labels, "", 500.0);
-                    }
+                    ChartStyle::BarChart => widgets::draw_bar_chart(ui, &counts, &labels, "", 400.0),
+                    ChartStyle::PieChart => self.draw_pie_chart(ui, &counts, &labels, "", 500.0),
                 }
-            });
+            })
+            .inner;
+        let action = clicked.map(|index| self.drill_into_bucket(buckets[index]));
+
+        if outside > 0 {
+            ui.colored_label(
+                palette.warning,
+                t!("有 {} 条数据不在任何区间内", outside)
+            );
+        }
 
         // 显示详细数据表格
         if self.show_data_table {
             ui.add_space(20.0);
-            ui.heading(RichText::new("财富分布详情").size(16.0).strong());
+            ui.heading(RichText::new(t!("{}详情", title)).size(16.0).strong());
             ui.add_space(10.0);
 
             // 添加表格边框
             egui::Frame::none()
-                .fill(Color32::from_rgb(250, 250, 252))
-                .stroke(Stroke::new(1.0, Color32::from_rgb(230, 230, 240)))
+                .fill(palette.surface_alt)
+                .stroke(Stroke::new(1.0, palette.border))
                 .rounding(Rounding::same(4.0))
                 .inner_margin(egui::Margin::same(10.0))
                 .show(ui, |ui| {
-                    Grid::new("wealth_distribution_grid")
+                    Grid::new("numeric_distribution_grid")
                         .num_columns(if self.show_percentage { 3 } else { 2 })
                         .striped(true)
                         .spacing([10.0, 6.0])
                         .show(ui, |ui| {
-                            ui.strong(RichText::new("财富范围").color(Color32::from_rgb(60, 60, 100)));
-                            ui.strong(RichText::new("球队数量").color(Color32::from_rgb(60, 60, 100)));
+                            ui.strong(RichText::new(t!("{}范围", self.dimension.as_str())).color(palette.heading));
+                            ui.strong(RichText::new(self.dimension.unit()).color(palette.heading));
                             if self.show_percentage {
-                                ui.strong(RichText::new("占比").color(Color32::from_rgb(60, 60, 100)));
+                                ui.strong(RichText::new(t!("占比")).color(palette.heading));
                             }
                             ui.end_row();
 
-                            let total_teams = self.teams.len() as f32;
-                            for (i, (min, max)) in self.wealth_ranges.iter().enumerate() {
-                                let range_text = if *max == i64::MAX {
-                                    format!("{}+", min)
-                                } else {
-                                    format!("{}-{}", min, max)
-                                };
-                                
-                                ui.label(range_text);
-                                ui.label(values[i].to_string());
-                                
+                            let total = values.len() as f32;
+                            for (label, count) in labels.iter().zip(&counts) {
+                                ui.label(label);
+                                ui.label(count.to_string());
+
                                 if self.show_percentage {
-                                    let percentage = if total_teams > 0.0 {
-                                        (values[i] as f32 / total_teams) * 100.0
-                                    } else {
-                                        0.0
-                                    };
-                                    ui.label(format!("{:.1}%", percentage));
+                                    ui.label(format!("{:.1}%", (*count as f32 / total) * 100.0));
                                 }
-                                
+
                                 ui.end_row();
                             }
                         });
                 });
         }
+
+        action
     }
 
-    fn show_supporter_distribution(&self, ui: &mut Ui) {
-        let mut values = vec![0; self.supporter_ranges.len()];
-        let mut labels = Vec::new();
+    // 字段使用的区间
+    fn buckets_for(&self, dimension: ChartDimension, values: &[i64]) -> Vec<Bucket> {
+        match self.bucket_mode {
+            BucketMode::Custom => self.custom_buckets.get(&dimension).cloned().unwrap_or_default(),
+            mode => stats::auto_buckets(values, mode, self.bucket_count),
+        }
+    }
 
-        // 计算每个球迷数量范围的球队数量
-        for team in &self.teams {
-            for (i, (min, max)) in self.supporter_ranges.iter().enumerate() {
-                if team.supporter_count >= *min && team.supporter_count <= *max {
-                    values[i] += 1;
-                    break;
-                }
+    fn show_bucket_controls(&mut self, ui: &mut Ui, values: &[i64]) {
+        ui.horizontal(|ui| {
+            ui.strong(t!("分组方式:"));
+            let previous = self.bucket_mode;
+            for mode in BucketMode::ALL {
+                ui.selectable_value(&mut self.bucket_mode, mode, mode.as_str());
             }
-        }
 
-        // 创建标签
-        for (min, max) in &self.supporter_ranges {
-            if *max == i64::MAX {
-                labels.push(format!("{}+", min));
-            } else {
-                labels.push(format!("{}-{}", min, max));
+            // 第一次切换到自定义时，以当前的自动区间作为初始值
+            if self.bucket_mode == BucketMode::Custom && previous != BucketMode::Custom
+                && !self.custom_buckets.contains_key(&self.dimension)
+            {
+                let buckets = stats::auto_buckets(values, previous, self.bucket_count);
+                self.custom_buckets.insert(self.dimension, buckets);
             }
-        }
 
-        // 绘制图表
-        ui.heading(RichText::new("球迷数量分布").size(20.0).strong().color(Color32::from_rgb(60, 60, 80)));
-        ui.add_space(15.0);
-        
-        // 创建滚动区域以确保图表完整显示
-        ScrollArea::vertical()
-            .max_height(500.0)
-            .show(ui, |ui| {
-                match self.chart_style {
-                    ChartStyle::BarChart => {
-                        widgets::draw_bar_chart(ui, &values, &labels, "", 400.0);
-                    },
-                    ChartStyle::PieChart => {
-                        self.draw_pie_chart(ui, &values, &labels, "", 500.0);
-                    }
-                }
-            });
+            if self.bucket_mode != BucketMode::Custom {
+                ui.add_space(20.0);
+                ui.label(t!("区间数量:"));
+                ui.add(egui::Slider::new(&mut self.bucket_count, 2..=30));
+            }
+        });
 
-        // 显示详细数据表格
-        if self.show_data_table {
-            ui.add_space(20.0);
-            ui.heading(RichText::new("球迷数量分布详情").size(16.0).strong());
+        if self.bucket_mode != BucketMode::Custom {
             ui.add_space(10.0);
+            return;
+        }
 
-            // 添加表格边框
-            egui::Frame::none()
-                .fill(Color32::from_rgb(250, 250, 252))
-                .stroke(Stroke::new(1.0, Color32::from_rgb(230, 230, 240)))
-                .rounding(Rounding::same(4.0))
-                .inner_margin(egui::Margin::same(10.0))
-                .show(ui, |ui| {
-                    Grid::new("supporter_distribution_grid")
-                        .num_columns(if self.show_percentage { 3 } else { 2 })
-                        .striped(true)
-                        .spacing([10.0, 6.0])
+        let dimension = self.dimension;
+        let buckets = self.custom_buckets.entry(dimension).or_default();
+        let mut remove = None;
+
+        ui.add_space(5.0);
+        egui::Frame::none()
+            .fill(theme::palette().surface_alt)
+            .stroke(Stroke::new(1.0, theme::palette().border))
+            .rounding(Rounding::same(4.0))
+            .inner_margin(egui::Margin::same(10.0))
+            .show(ui, |ui| {
+                ScrollArea::vertical().max_height(160.0).show(ui, |ui| {
+                    Grid::new("custom_bucket_grid")
+                        .num_columns(4)
+                        .spacing([10.0, 4.0])
                         .show(ui, |ui| {
-                            ui.strong(RichText::new("球迷数量范围").color(Color32::from_rgb(60, 60, 100)));
-                            ui.strong(RichText::new("球队数量").color(Color32::from_rgb(60, 60, 100)));
-                            if self.show_percentage {
-                                ui.strong(RichText::new("占比").color(Color32::from_rgb(60, 60, 100)));
-                            }
+                            ui.strong(t!("最小值"));
+                            ui.strong(t!("最大值"));
+                            ui.strong(t!("不限上限"));
+                            ui.label("");
                             ui.end_row();
 
-                            let total_teams = self.teams.len() as f32;
-                            for (i, (min, max)) in self.supporter_ranges.iter().enumerate() {
-                                let range_text = if *max == i64::MAX {
-                                    format!("{}+", min)
-                                } else {
-                                    format!("{}-{}", min, max)
-                                };
-                                
-                                ui.label(range_text);
-                                ui.label(values[i].to_string());
-                                
-                                if self.show_percentage {
-                                    let percentage = if total_teams > 0.0 {
-                                        (values[i] as f32 / total_teams) * 100.0
-                                    } else {
-                                        0.0
-                                    };
-                                    ui.label(format!("{:.1}%", percentage));
+                            for (i, bucket) in buckets.iter_mut().enumerate() {
+                                ui.add(egui::DragValue::new(&mut bucket.min));
+                                let mut unbounded = bucket.max == i64::MAX;
+                                ui.add_enabled(!unbounded, egui::DragValue::new(&mut bucket.max));
+                                if ui.checkbox(&mut unbounded, "").changed() {
+                                    bucket.max = if unbounded { i64::MAX } else { bucket.min };
+                                }
+                                if ui.small_button(t!("删除")).clicked() {
+                                    remove = Some(i);
                                 }
-                                
                                 ui.end_row();
                             }
                         });
                 });
+
+                ui.horizontal(|ui| {
+                    if ui.button(t!("添加区间")).clicked() {
+                        // 新区间接在最后一个区间之后
+                        let start = buckets.last()
+                            .map_or(0, |b| b.max.saturating_add(1));
+                        let end = if start == i64::MAX { start } else { start.saturating_add(999) };
+                        buckets.push(Bucket::new(start, end));
+                    }
+                    if ui.button(t!("按最小值排序")).clicked() {
+                        buckets.sort_by_key(|b| b.min);
+                    }
+                    if ui.button(t!("恢复默认")).clicked() {
+                        *buckets = default_custom_buckets(dimension)
+                            .unwrap_or_else(|| stats::auto_buckets(values, BucketMode::EqualWidth, self.bucket_count));
+                    }
+                });
+            });
+
+        if let Some(i) = remove {
+            buckets.remove(i);
         }
+        ui.add_space(10.0);
     }
 
-    fn show_location_distribution(&self, ui: &mut Ui) {
+    fn show_location_distribution(&mut self, ui: &mut Ui) -> Option<VisualizationAction> {
+        let palette = theme::palette();
         if self.location_counts.is_empty() {
-            ui.label("没有地区数据可供显示");
-            return;
+            ui.label(t!("没有地区数据可供显示"));
+            return None;
         }
 
-        // 准备数据
-        let mut locations: Vec<(String, i64)> = self.location_counts.iter()
-            .map(|(k, v)| (k.clone(), *v))
-            .collect();
-        
-        // 按数量排序
-        locations.sort_by(|a, b| b.1.cmp(&a.1));
-        
-        // 如果超过10个地区，只显示前10个
-        let (top_locations, other_count) = if locations.len() > 10 {
-            let top = locations[0..10].to_vec();
-            let other: i64 = locations[10..].iter().map(|(_, count)| count).sum();
-            (top, Some(other))
-        } else {
-            (locations.clone(), None)
-        };
-        
-        // 准备图表数据
-        let mut values = top_locations.iter().map(|(_, count)| *count).collect::<Vec<_>>();
-        let mut labels = top_locations.iter().map(|(name, _)| name.clone()).collect::<Vec<_>>();
-        
-        // 添加"其他"类别
-        if let Some(count) = other_count {
-            values.push(count);
-            labels.push("其他".to_string());
-        }
+        // 准备数据，超过10个地区时其余合并为"其他"
+        let locations = self.location_rows();
+        let (labels, values): (Vec<String>, Vec<i64>) = top_with_other(&locations).into_iter().unzip();
         
         // 绘制图表
-        ui.heading(RichText::new("地区分布").size(20.0).strong().color(Color32::from_rgb(60, 60, 80)));
+        ui.heading(RichText::new(t!("地区分布")).size(20.0).strong().color(palette.heading));
         ui.add_space(15.0);
         
         // 创建滚动区域以确保图表完整显示
-        ScrollArea::vertical()
+        let clicked = ScrollArea::vertical()
             .max_height(500.0)
             .show(ui, |ui| {
                 match self.chart_style {
-                    ChartStyle::BarChart => {
-                        widgets::draw_bar_chart(ui, &values, &labels, "", 400.0);
-                    },
-                    ChartStyle::PieChart => {
-                        self.draw_pie_chart(ui, &values, &labels, "", 500.0);
-                    }
+                    ChartStyle::BarChart => widgets::draw_bar_chart(ui, &values, &labels, "", 400.0),
+                    ChartStyle::PieChart => self.draw_pie_chart(ui, &values, &labels, "", 500.0),
                 }
-            });
+            })
+            .inner;
+        let action = clicked.map(|index| self.drill_into_location(index));
         
         // 显示详细数据表格
         if self.show_data_table {
             ui.add_space(20.0);
-            ui.heading(RichText::new("地区分布详情").size(16.0).strong());
+            ui.heading(RichText::new(t!("地区分布详情")).size(16.0).strong());
             ui.add_space(10.0);
             
             // 添加表格边框
             egui::Frame::none()
-                .fill(Color32::from_rgb(250, 250, 252))
-                .stroke(Stroke::new(1.0, Color32::from_rgb(230, 230, 240)))
+                .fill(palette.surface_alt)
+                .stroke(Stroke::new(1.0, palette.border))
                 .rounding(Rounding::same(4.0))
                 .inner_margin(egui::Margin::same(10.0))
                 .show(ui, |ui| {
@@ -501,10 +938,10 @@ impl VisualizationView {
                             .striped(true)
                             .spacing([10.0, 6.0])
                             .show(ui, |ui| {
-                                ui.strong(RichText::new("地区").color(Color32::from_rgb(60, 60, 100)));
-                                ui.strong(RichText::new("球队数量").color(Color32::from_rgb(60, 60, 100)));
+                                ui.strong(RichText::new(t!("地区")).color(palette.heading));
+                                ui.strong(RichText::new(t!("球队数量")).color(palette.heading));
                                 if self.show_percentage {
-                                    ui.strong(RichText::new("占比").color(Color32::from_rgb(60, 60, 100)));
+                                    ui.strong(RichText::new(t!("占比")).color(palette.heading));
                                 }
                                 ui.end_row();
                                 
@@ -528,69 +965,48 @@ impl VisualizationView {
                     });
                 });
         }
+        
+        action
     }
 
-    fn show_league_distribution(&self, ui: &mut Ui) {
+    fn show_league_distribution(&mut self, ui: &mut Ui) -> Option<VisualizationAction> {
+        let palette = theme::palette();
         if self.league_counts.is_empty() {
-            ui.label("没有联赛数据可供显示");
-            return;
+            ui.label(t!("没有联赛数据可供显示"));
+            return None;
         }
 
-        // 准备数据
-        let mut leagues: Vec<(i64, i64)> = self.league_counts.iter()
-            .map(|(k, v)| (*k, *v))
-            .collect();
-        
-        // 按数量排序
-        leagues.sort_by(|a, b| b.1.cmp(&a.1));
-        
-        // 如果超过10个联赛，只显示前10个
-        let (top_leagues, other_count) = if leagues.len() > 10 {
-            let top = leagues[0..10].to_vec();
-            let other: i64 = leagues[10..].iter().map(|(_, count)| count).sum();
-            (top, Some(other))
-        } else {
-            (leagues.clone(), None)
-        };
-        
-        // 准备图表数据
-        let mut values = top_leagues.iter().map(|(_, count)| *count).collect::<Vec<_>>();
-        let mut labels = top_leagues.iter().map(|(id, _)| format!("联赛 {}", id)).collect::<Vec<_>>();
-        
-        // 添加"其他"类别
-        if let Some(count) = other_count {
-            values.push(count);
-            labels.push("其他".to_string());
-        }
+        // 准备数据，超过10个联赛时其余合并为"其他"
+        let league_ids: Vec<i64> = self.league_order().into_iter().map(|(id, _)| id).collect();
+        let leagues = self.league_rows();
+        let (labels, values): (Vec<String>, Vec<i64>) = top_with_other(&leagues).into_iter().unzip();
         
         // 绘制图表
-        ui.heading(RichText::new("联赛分布").size(20.0).strong().color(Color32::from_rgb(60, 60, 80)));
+        ui.heading(RichText::new(t!("联赛分布")).size(20.0).strong().color(palette.heading));
         ui.add_space(15.0);
         
         // 创建滚动区域以确保图表完整显示
-        ScrollArea::vertical()
+        let clicked = ScrollArea::vertical()
             .max_height(500.0)
             .show(ui, |ui| {
                 match self.chart_style {
-                    ChartStyle::BarChart => {
-                        widgets::draw_bar_chart(ui, &values, &labels, "", 400.0);
-                    },
-                    ChartStyle::PieChart => {
-                        self.draw_pie_chart(ui, &values, &labels, "", 500.0);
-                    }
+                    ChartStyle::BarChart => widgets::draw_bar_chart(ui, &values, &labels, "", 400.0),
+                    ChartStyle::PieChart => self.draw_pie_chart(ui, &values, &labels, "", 500.0),
                 }
-            });
+            })
+            .inner;
+        let action = clicked.map(|index| self.drill_into_league(index));
         
         // 显示详细数据表格
         if self.show_data_table {
             ui.add_space(20.0);
-            ui.heading(RichText::new("联赛分布详情").size(16.0).strong());
+            ui.heading(RichText::new(t!("联赛分布详情")).size(16.0).strong());
             ui.add_space(10.0);
             
             // 添加表格边框
             egui::Frame::none()
-                .fill(Color32::from_rgb(250, 250, 252))
-                .stroke(Stroke::new(1.0, Color32::from_rgb(230, 230, 240)))
+                .fill(palette.surface_alt)
+                .stroke(Stroke::new(1.0, palette.border))
                 .rounding(Rounding::same(4.0))
                 .inner_margin(egui::Margin::same(10.0))
                 .show(ui, |ui| {
@@ -601,16 +1017,16 @@ impl VisualizationView {
                             .striped(true)
                             .spacing([10.0, 6.0])
                             .show(ui, |ui| {
-                                ui.strong(RichText::new("联赛ID").color(Color32::from_rgb(60, 60, 100)));
-                                ui.strong(RichText::new("球队数量").color(Color32::from_rgb(60, 60, 100)));
+                                ui.strong(RichText::new(t!("联赛")).color(palette.heading));
+                                ui.strong(RichText::new(t!("球队数量")).color(palette.heading));
                                 if self.show_percentage {
-                                    ui.strong(RichText::new("占比").color(Color32::from_rgb(60, 60, 100)));
+                                    ui.strong(RichText::new(t!("占比")).color(palette.heading));
                                 }
                                 ui.end_row();
                                 
                                 let total_teams = self.teams.len() as f32;
-                                for (league_id, count) in leagues.iter() {
-                                    ui.label(format!("联赛 {}", league_id));
+                                for ((league, count), &league_id) in leagues.iter().zip(&league_ids) {
+                                    ui.label(league).on_hover_text(self.leagues.hover_text(league_id));
                                     ui.label(count.to_string());
                                     
                                     if self.show_percentage {
@@ -628,110 +1044,15 @@ impl VisualizationView {
                     });
                 });
         }
-    }
-    
-    fn show_found_year_distribution(&self, ui: &mut Ui) {
-        if self.teams.is_empty() {
-            ui.label("没有成立年份数据可供显示");
-            return;
-        }
-        
-        // 计算成立年份的范围
-        let min_year = self.teams.iter().map(|t| t.found_year).min().unwrap_or(1800);
-        let max_year = self.teams.iter().map(|t| t.found_year).max().unwrap_or(2023);
-        
-        // 创建年代范围（每20年一个区间）
-        let period = 20;
-        let mut year_ranges = Vec::new();
-        let mut current = min_year - (min_year % period);
-        while current <= max_year {
-            year_ranges.push((current, current + period - 1));
-            current += period;
-        }
-        
-        // 计算每个年代范围的球队数量
-        let mut values = vec![0; year_ranges.len()];
-        for team in &self.teams {
-            for (i, (min, max)) in year_ranges.iter().enumerate() {
-                if team.found_year >= *min && team.found_year <= *max {
-                    values[i] += 1;
-                    break;
-                }
-            }
-        }
         
-        // 创建标签
-        let labels = year_ranges.iter()
-            .map(|(min, max)| format!("{}-{}", min, max))
-            .collect::<Vec<_>>();
-        
-        // 绘制图表
-        ui.heading(RichText::new("成立年份分布").size(20.0).strong().color(Color32::from_rgb(60, 60, 80)));
-        ui.add_space(15.0);
-        
-        // 创建滚动区域以确保图表完整显示
-        ScrollArea::vertical()
-            .max_height(500.0)
-            .show(ui, |ui| {
-                match self.chart_style {
-                    ChartStyle::BarChart => {
-                        widgets::draw_bar_chart(ui, &values, &labels, "", 400.0);
-                    },
-                    ChartStyle::PieChart => {
-                        self.draw_pie_chart(ui, &values, &labels, "", 500.0);
-                    }
-                }
-            });
-        
-        // 显示详细数据表格
-        if self.show_data_table {
-            ui.add_space(20.0);
-            ui.heading(RichText::new("成立年份分布详情").size(16.0).strong());
-            ui.add_space(10.0);
-            
-            // 添加表格边框
-            egui::Frame::none()
-                .fill(Color32::from_rgb(250, 250, 252))
-                .stroke(Stroke::new(1.0, Color32::from_rgb(230, 230, 240)))
-                .rounding(Rounding::same(4.0))
-                .inner_margin(egui::Margin::same(10.0))
-                .show(ui, |ui| {
-                    Grid::new("found_year_distribution_grid")
-                        .num_columns(if self.show_percentage { 3 } else { 2 })
-                        .striped(true)
-                        .spacing([10.0, 6.0])
-                        .show(ui, |ui| {
-                            ui.strong(RichText::new("年份范围").color(Color32::from_rgb(60, 60, 100)));
-                            ui.strong(RichText::new("球队数量").color(Color32::from_rgb(60, 60, 100)));
-                            if self.show_percentage {
-                                ui.strong(RichText::new("占比").color(Color32::from_rgb(60, 60, 100)));
-                            }
-                            ui.end_row();
-                            
-                            let total_teams = self.teams.len() as f32;
-                            for (i, (min, max)) in year_ranges.iter().enumerate() {
-                                ui.label(format!("{}-{}", min, max));
-                                ui.label(values[i].to_string());
-                                
-                                if self.show_percentage {
-                                    let percentage = if total_teams > 0.0 {
-                                        (values[i] as f32 / total_teams) * 100.0
-                                    } else {
-                                        0.0
-                                    };
-                                    ui.label(format!("{:.1}%", percentage));
-                                }
-                                
-                                ui.end_row();
-                            }
-                        });
-                });
-        }
+        action
     }
-
-    fn draw_pie_chart(&self, ui: &mut Ui, values: &[i64], labels: &[String], title: &str, size: f32) {
+    
+    // 绘制饼图，返回被点击的扇形下标
+    fn draw_pie_chart(&self, ui: &mut Ui, values: &[i64], labels: &[String], title: &str, size: f32) -> Option<usize> {
+        let palette = theme::palette();
         if values.is_empty() || labels.is_empty() {
-            return;
+            return None;
         }
         
         ui.heading(title);
@@ -746,10 +1067,12 @@ impl VisualizationView {
         // 计算总和
         let total: i64 = values.iter().sum();
         if total <= 0 {
-            ui.label("没有数据可显示");
-            return;
+            ui.label(t!("没有数据可显示"));
+            return None;
         }
         
+        let mut clicked = None;
+        
         // 定义饼图尺寸和位置
         let available_width = ui.available_width();
         let chart_height = 400.0;
@@ -763,7 +1086,7 @@ impl VisualizationView {
             // 为饼图和图例分配空间
             let (response, painter) = ui.allocate_painter(
                 Vec2::new(available_width, chart_height),
-                egui::Sense::hover()
+                egui::Sense::click()
             );
             let rect = response.rect;
             
@@ -771,6 +1094,21 @@ impl VisualizationView {
             let center = pos2(center_x, rect.min.y + pie_size / 2.0 + 20.0);
             let radius = pie_size / 2.0;
             
+            // 鼠标所在的扇形，中心的总计区域不响应
+            let hovered_slice = response.hover_pos().and_then(|pos| {
+                let offset = pos - center;
+                let distance = offset.length();
+                if distance > radius || distance < radius * 0.3 {
+                    return None;
+                }
+                let angle = offset.y.atan2(offset.x).rem_euclid(std::f32::consts::TAU);
+                let mut end_angle = 0.0;
+                data.iter().position(|(_, value)| {
+                    end_angle += std::f32::consts::TAU * (*value as f32 / total as f32);
+                    angle < end_angle
+                })
+            });
+            
             // 定义扇形颜色
             let colors = [
                 Color32::from_rgb(100, 150, 250), // 蓝色
@@ -820,7 +1158,11 @@ impl VisualizationView {
                     },
                     closed: true,
                     fill: color,
-                    stroke: Stroke::new(1.0, Color32::WHITE),
+                    stroke: if hovered_slice == Some(i) {
+                        Stroke::new(2.0, palette.heading)
+                    } else {
+                        Stroke::new(1.0, palette.surface)
+                    },
                 }));
                 
                 // 如果扇形角度太小，将标签信息保存起来，稍后单独处理
@@ -839,7 +1181,7 @@ impl VisualizationView {
                     
                     painter.line_segment(
                         [pos2(label_x, label_y), pos2(outer_x, outer_y)],
-                        Stroke::new(1.0, Color32::DARK_GRAY)
+                        Stroke::new(1.0, palette.weak_text)
                     );
                     
                     // 计算百分比
@@ -867,7 +1209,7 @@ impl VisualizationView {
                     let galley = painter.layout_no_wrap(
                         text.clone(),
                         font_id.clone(),
-                        Color32::DARK_GRAY
+                        palette.weak_text
                     );
                     
                     let text_rect = Rect::from_min_size(
@@ -881,7 +1223,7 @@ impl VisualizationView {
                     painter.rect_filled(
                         text_rect,
                         Rounding::same(2.0),
-                        Color32::from_rgba_unmultiplied(255, 255, 255, 220)
+                        palette.surface.linear_multiply(0.86)
                     );
                     
                     // 绘制文本
@@ -890,7 +1232,7 @@ impl VisualizationView {
                         align,
                         text,
                         font_id,
-                        Color32::DARK_GRAY
+                        palette.weak_text
                     );
                 }
                 
@@ -906,9 +1248,9 @@ impl VisualizationView {
                 painter.text(
                     pos2(legend_x, legend_y),
                     Align2::LEFT_TOP,
-                    "小比例项目:",
+                    t!("小比例项目:"),
                     egui::FontId::proportional(12.0),
-                    Color32::DARK_GRAY
+                    palette.weak_text
                 );
                 
                 legend_y += 20.0;
@@ -935,7 +1277,7 @@ impl VisualizationView {
                         Align2::LEFT_TOP,
                         format!("{}: {} ({}%)", label, value, percentage as i32),
                         egui::FontId::proportional(10.0),
-                        Color32::DARK_GRAY
+                        palette.weak_text
                     );
                     
                     legend_y += 15.0;
@@ -946,23 +1288,76 @@ impl VisualizationView {
             painter.circle_filled(
                 center,
                 radius * 0.3,
-                Color32::from_rgba_unmultiplied(255, 255, 255, 200)
+                palette.surface.linear_multiply(0.78)
             );
             
             painter.circle_stroke(
                 center,
                 radius * 0.3,
-                Stroke::new(1.0, Color32::from_rgb(200, 200, 200))
+                Stroke::new(1.0, palette.border_strong)
             );
             
             // 绘制总数标签
             painter.text(
                 center,
                 Align2::CENTER_CENTER,
-                format!("总计\n{}", total),
+                t!("总计\n{}", total),
                 egui::FontId::proportional(14.0),
-                Color32::DARK_GRAY
+                palette.weak_text
             );
+            
+            if hovered_slice.is_some() {
+                ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+                if response.clicked() {
+                    clicked = hovered_slice;
+                }
+            }
         });
+        
+        clicked
     }
-} 
\ No newline at end of file
+} 
+
+// 财富和球迷数量沿用原来的固定区间作为自定义区间的默认值
+fn default_custom_buckets(dimension: ChartDimension) -> Option<Vec<Bucket>> {
+    let ranges: &[(i64, i64)] = match dimension {
+        ChartDimension::TeamWealth => &[(0, 1000), (1001, 5000), (5001, 10000), (10001, 50000), (50001, i64::MAX)],
+        ChartDimension::TeamSupporterCount => &[(0, 10000), (10001, 50000), (50001, 100000), (100001, 500000), (500001, i64::MAX)],
+        _ => return None,
+    };
+    Some(ranges.iter().map(|&(min, max)| Bucket::new(min, max)).collect())
+}
+
+// 选择散点图的坐标轴字段，返回是否修改
+fn field_combo(ui: &mut Ui, id: &str, field: &mut StatField) -> bool {
+    let before = *field;
+    ComboBox::from_id_source(id)
+        .selected_text(before.as_str())
+        .width(110.0)
+        .show_ui(ui, |ui| {
+            for option in StatField::ALL {
+                ui.selectable_value(field, option, option.as_str());
+            }
+        });
+    *field != before
+}
+
+// 只保留数量最多的分类，其余合并为"其他"
+fn top_with_other(rows: &[(String, i64)]) -> Vec<(String, i64)> {
+    if rows.len() <= MAX_CATEGORIES {
+        return rows.to_vec();
+    }
+    let mut entries = rows[..MAX_CATEGORIES].to_vec();
+    let other = rows[MAX_CATEGORIES..].iter().map(|(_, count)| count).sum();
+    entries.push((t!("其他").to_string(), other));
+    entries
+}
+
+// 图表中第index个分类对应的原始分类，"其他"对应所有未单独显示的分类
+fn selected_categories<T>(rows: &[T], index: usize) -> &[T] {
+    if index < MAX_CATEGORIES {
+        &rows[index..(index + 1).min(rows.len())]
+    } else {
+        rows.get(MAX_CATEGORIES..).unwrap_or(&[])
+    }
+}
@@ -1,5 +1,7 @@
 use egui::{Color32, Frame, Rounding, Stroke, Ui, Vec2, Rect, Align2, pos2, epaint::PathShape};
 
+use crate::ui::theme;
+
 /// 创建带有标题的分组框
 pub fn titled_frame(title: &str, ui: &mut Ui, add_contents: impl FnOnce(&mut Ui)) {
     ui.group(|ui| {
@@ -13,9 +15,10 @@ pub fn titled_frame(title: &str, ui: &mut Ui, add_contents: impl FnOnce(&mut Ui)
 
 /// 创建带有圆角和阴影的面板（Mac风格）
 pub fn rounded_frame(ui: &mut Ui, add_contents: impl FnOnce(&mut Ui)) {
+    let palette = theme::palette();
     Frame::none()
-        .fill(Color32::from_rgb(250, 250, 250))
-        .stroke(Stroke::new(1.0, Color32::from_rgb(220, 220, 220)))
+        .fill(palette.surface_alt)
+        .stroke(Stroke::new(1.0, palette.border))
         .rounding(Rounding::same(8.0))
         .shadow(egui::epaint::Shadow {
             extrusion: 4.0,
@@ -30,9 +33,10 @@ pub fn rounded_frame(ui: &mut Ui, add_contents: impl FnOnce(&mut Ui)) {
 
 /// 创建Mac风格的卡片
 pub fn mac_card(ui: &mut Ui, add_contents: impl FnOnce(&mut Ui)) {
+    let palette = theme::palette();
     Frame::none()
-        .fill(Color32::from_rgb(255, 255, 255))
-        .stroke(Stroke::new(1.0, Color32::from_rgb(230, 230, 230)))
+        .fill(palette.surface)
+        .stroke(Stroke::new(1.0, palette.border))
         .rounding(Rounding::same(6.0))
         .shadow(egui::epaint::Shadow {
             extrusion: 2.0,
@@ -86,7 +90,7 @@ pub fn error_message(ui: &mut Ui, message: &str) {
     ui.horizontal(|ui| {
         ui.spacing_mut().item_spacing.x = 5.0;
         ui.label("⚠").on_hover_text(message);
-        ui.colored_label(Color32::from_rgb(200, 0, 0), message);
+        ui.colored_label(theme::palette().error, message);
     });
 }
 
@@ -98,12 +102,13 @@ pub fn mac_button(ui: &mut Ui, text: &str) -> bool {
     .clicked()
 }
 
-/// 创建Mac风格主按钮（蓝色）
+/// 创建Mac风格主按钮（强调色）
 pub fn mac_primary_button(ui: &mut Ui, text: &str) -> bool {
-    let response = ui.add(egui::Button::new(text)
+    let palette = theme::palette();
+    let response = ui.add(egui::Button::new(egui::RichText::new(text).color(palette.on_accent))
         .min_size(Vec2::new(80.0, 24.0))
         .rounding(Rounding::same(6.0))
-        .fill(Color32::from_rgb(80, 145, 245)));
+        .fill(palette.accent));
     
     response.clicked()
 }
@@ -119,15 +124,16 @@ pub fn mac_title_bar(ui: &mut Ui, title: &str) {
     });
 }
 
-/// 绘制简单的条形图
-pub fn draw_bar_chart(ui: &mut Ui, values: &[i64], labels: &[String], title: &str, max_height: f32) {
+/// 绘制简单的条形图，返回被点击的条形下标
+pub fn draw_bar_chart(ui: &mut Ui, values: &[i64], labels: &[String], title: &str, max_height: f32) -> Option<usize> {
+    let palette = theme::palette();
     let max_value = *values.iter().max().unwrap_or(&1);
     let width = ui.available_width();
     let height = max_height;
     let bar_count = values.len();
     
     if bar_count == 0 {
-        return;
+        return None;
     }
     
     // 图表标题
@@ -139,27 +145,33 @@ pub fn draw_bar_chart(ui: &mut Ui, values: &[i64], labels: &[String], title: &st
     let chart_height = height - x_axis_height;
     
     // 绘制图表框架
-    let (response, painter) = ui.allocate_painter(Vec2::new(width, chart_height), egui::Sense::hover());
+    let (response, painter) = ui.allocate_painter(Vec2::new(width, chart_height), egui::Sense::click());
     let rect = response.rect;
     
     // 绘制背景
     painter.rect_filled(
         rect,
         Rounding::same(6.0),
-        Color32::from_rgb(250, 250, 250)
+        palette.surface_alt
     );
     
     // 绘制边框
     painter.rect_stroke(
         rect,
         Rounding::same(6.0),
-        Stroke::new(1.0, Color32::from_rgb(220, 220, 220))
+        Stroke::new(1.0, palette.border)
     );
     
     // 计算条形宽度和间距 - 调整间距以适应更多条形
     let bar_spacing = if bar_count > 10 { 5.0 } else { 10.0 };
     let bar_width = (width - (bar_count as f32 + 1.0) * bar_spacing) / bar_count as f32;
     
+    // 鼠标所在的条形列（包括条形上方的空白区域）
+    let hovered_bar = response.hover_pos().and_then(|pos| {
+        let index = ((pos.x - rect.min.x - bar_spacing / 2.0) / (bar_width + bar_spacing)).floor();
+        (index >= 0.0 && (index as usize) < bar_count).then_some(index as usize)
+    });
+    
     // 计算Y轴刻度的最大值（向上取整到合适的数值）
     let max_display_value = if max_value < 10 {
         max_value + 1
@@ -180,7 +192,7 @@ pub fn draw_bar_chart(ui: &mut Ui, values: &[i64], labels: &[String], title: &st
         // 绘制水平辅助线
         painter.line_segment(
             [pos2(rect.min.x, y_pos), pos2(rect.max.x, y_pos)],
-            Stroke::new(0.5, Color32::from_rgb(220, 220, 220))
+            Stroke::new(0.5, palette.border)
         );
         
         // 绘制刻度值
@@ -189,7 +201,7 @@ pub fn draw_bar_chart(ui: &mut Ui, values: &[i64], labels: &[String], title: &st
             Align2::LEFT_CENTER,
             format!("{}", tick_value),
             egui::FontId::proportional(10.0),
-            Color32::DARK_GRAY
+            palette.weak_text
         );
     }
     
@@ -223,25 +235,30 @@ pub fn draw_bar_chart(ui: &mut Ui, values: &[i64], labels: &[String], title: &st
             color
         );
         
-        // 绘制条形边框
+        // 绘制条形边框，鼠标悬停的条形加深边框
+        let border = if hovered_bar == Some(i) {
+            Stroke::new(2.0, palette.heading)
+        } else {
+            Stroke::new(1.0, palette.surface.linear_multiply(0.4))
+        };
         painter.rect_stroke(
             Rect::from_min_size(
                 pos2(x, y),
                 Vec2::new(bar_width, bar_height)
             ),
             Rounding::same(4.0),
-            Stroke::new(1.0, Color32::from_rgba_unmultiplied(255, 255, 255, 100))
+            border
         );
         
         // 绘制数值（只有当值足够大时才显示）
         if bar_height > 20.0 {
-            // 绘制白色背景确保文字清晰可见
+            // 绘制背景确保文字清晰可见
             let font_id = egui::FontId::proportional(10.0);
             let text = value.to_string();
             let galley = painter.layout_no_wrap(
                 text.clone(),
                 font_id.clone(),
-                Color32::DARK_GRAY
+                palette.weak_text
             );
             let text_pos = pos2(x + bar_width / 2.0, y - 5.0);
             let text_rect = Rect::from_center_size(
@@ -251,7 +268,7 @@ pub fn draw_bar_chart(ui: &mut Ui, values: &[i64], labels: &[String], title: &st
             painter.rect_filled(
                 text_rect,
                 Rounding::same(2.0),
-                Color32::from_rgba_unmultiplied(255, 255, 255, 220)
+                palette.surface.linear_multiply(0.86)
             );
             
             // 绘制数值文本
@@ -260,7 +277,7 @@ pub fn draw_bar_chart(ui: &mut Ui, values: &[i64], labels: &[String], title: &st
                 Align2::CENTER_BOTTOM,
                 text,
                 font_id,
-                Color32::DARK_GRAY
+                palette.weak_text
             );
         }
     }
@@ -293,7 +310,7 @@ pub fn draw_bar_chart(ui: &mut Ui, values: &[i64], labels: &[String], title: &st
                 Align2::LEFT_TOP,
                 label,
                 font_id,
-                Color32::DARK_GRAY
+                palette.weak_text
             );
         } else {
             // 正常显示标签
@@ -302,14 +319,24 @@ pub fn draw_bar_chart(ui: &mut Ui, values: &[i64], labels: &[String], title: &st
                 Align2::CENTER_TOP,
                 label,
                 egui::FontId::proportional(10.0),
-                Color32::DARK_GRAY
+                palette.weak_text
             );
         }
     }
+    
+    if hovered_bar.is_some() {
+        ui.ctx().set_cursor_icon(egui::CursorIcon::PointingHand);
+    }
+    
+    if response.clicked() {
+        hovered_bar
+    } else {
+        None
+    }
 }
 
 /// 绘制简单的饼图（已废弃，请使用visualization.rs中的实现）
 pub fn draw_pie_chart(ui: &mut Ui, values: &[i64], labels: &[String], title: &str, size: f32) {
     ui.heading(title);
-    ui.label("请使用visualization.rs中的实现");
+    ui.label(t!("请使用visualization.rs中的实现"));
 } 
\ No newline at end of file
@@ -28,7 +28,7 @@ pub fn validate_number(input: &str) -> Result<i64> {
     match input.trim().parse::<i64>() {
         Ok(n) => Ok(n),
         Err(_) => Err(crate::error::AppError::InvalidInput(
-            format!("无效的数字输入: {}", input)
+            t!("无效的数字输入: {}", input)
         )),
     }
 }
@@ -38,21 +38,92 @@ pub fn create_logo_path(db_dir: &Path, team_id: i64) -> PathBuf {
     db_dir.join(format!("L{}.png", team_id))
 }
 
+/// 支持打开的数据库文件扩展名
+pub const DATABASE_EXTENSIONS: [&str; 3] = ["db", "sqlite", "sqlite3"];
+
+/// 检查文件扩展名是否为支持的数据库格式
+pub fn is_database_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| DATABASE_EXTENSIONS.iter().any(|e| e.eq_ignore_ascii_case(ext)))
+        .unwrap_or(false)
+}
+
+// 数据库备份目录，与数据库位于同一目录
+const DATABASE_BACKUP_DIR_NAME: &str = "DatabaseBackup";
+
+/// 通过SQLite在线备份把数据库复制到同目录下的备份目录，文件名附加当前时间，返回备份文件路径
+///
+/// 在线备份会读取一致的快照，不会复制到其他程序写入一半的文件。
+pub fn backup_database(path: &Path) -> Result<PathBuf> {
+    let dir = path.parent().unwrap_or_else(|| Path::new(".")).join(DATABASE_BACKUP_DIR_NAME);
+    std::fs::create_dir_all(&dir)?;
+
+    let backup_path = unique_backup_path(&dir, path);
+    let source = rusqlite::Connection::open_with_flags(path, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    source.backup(rusqlite::DatabaseName::Main, &backup_path, None)?;
+    Ok(backup_path)
+}
+
+// 备份文件名精确到毫秒，同一毫秒内已有同名文件时再追加序号
+fn unique_backup_path(dir: &Path, path: &Path) -> PathBuf {
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("database");
+    let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S_%3f").to_string();
+    let extension = path.extension().and_then(|e| e.to_str());
+
+    (0..)
+        .map(|counter| {
+            let name = if counter == 0 {
+                format!("{}_{}", stem, timestamp)
+            } else {
+                format!("{}_{}_{}", stem, timestamp, counter)
+            };
+            match extension {
+                Some(ext) => dir.join(format!("{}.{}", name, ext)),
+                None => dir.join(name),
+            }
+        })
+        .find(|candidate| !candidate.exists())
+        .expect("备份文件序号用尽")
+}
+
+/// 数据库文件的最后修改时间，WAL模式下同时考虑 -wal 文件
+pub fn database_modified_time(path: &Path) -> Option<std::time::SystemTime> {
+    let mut wal_path = path.as_os_str().to_owned();
+    wal_path.push("-wal");
+
+    [path.to_path_buf(), PathBuf::from(wal_path)].iter()
+        .filter_map(|p| std::fs::metadata(p).and_then(|m| m.modified()).ok())
+        .max()
+}
+
 /// 检查文件是否存在
 pub fn file_exists(path: &Path) -> bool {
     path.exists() && path.is_file()
 }
 
-/// 将图像保存为PNG格式，并调整大小
-pub fn save_image_as_png(src_path: &Path, dst_path: &Path, width: u32, height: u32) -> Result<()> {
-    // 加载图像
-    let img = image::open(src_path)?;
-    
-    // 调整大小
-    let resized = img.resize(width, height, image::imageops::FilterType::Lanczos3);
-    
-    // 保存为PNG
-    resized.save(dst_path)?;
-    
-    Ok(())
-} 
\ No newline at end of file
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn consecutive_backups_get_distinct_files() {
+        let dir = std::env::temp_dir().join(format!("cfs_db_backup_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("save.db");
+        let conn = rusqlite::Connection::open(&path).unwrap();
+        conn.execute_batch("PRAGMA journal_mode=WAL; CREATE TABLE Teams (ID INTEGER); INSERT INTO Teams VALUES (1);").unwrap();
+
+        let first = backup_database(&path).unwrap();
+        let second = backup_database(&path).unwrap();
+        assert_ne!(first, second);
+
+        // WAL中尚未写回的数据也在备份里
+        let backup = rusqlite::Connection::open(&second).unwrap();
+        let count: i64 = backup.query_row("SELECT COUNT(*) FROM Teams", [], |row| row.get(0)).unwrap();
+        assert_eq!(count, 1);
+
+        drop(conn);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}